@@ -1,15 +1,687 @@
+//! This module contains general functions that can be used for building
+//! different kinds of domTree nodes in a consistent manner.
 use super::*;
 
+/// Looks up the given symbol in the symbol table, after applying any symbol
+/// replacements defined in the symbol table, and returns the replaced value
+/// along with the character metrics of that value in the given font.
+pub fn lookup_symbol(
+    value: &str,
+    // TODO(#963): Use a union type for this.
+    font_name: &str,
+    mode: Mode,
+) -> (String, Option<CharacterMetrics>) {
+    // Replace the value with its replaced value from the symbol table
+    let value = match SYMBOLS.get(mode).get(value).and_then(|info| info.replace) {
+        Some(replace) => replace.to_string(),
+        None => value.to_string(),
+    };
+    let metrics = get_character_metrics(&value, font_name, mode);
+    (value, metrics)
+}
+
+/// Makes a symbolNode after translation via the list of symbols in symbols.rs.
+/// Correctly pulls out metrics for the character, and optionally takes a list of
+/// classes to be attached to the node.
+///
+/// TODO: make argument order closer to make_span
+/// TODO: add a separate argument for math class (e.g. `mop`, `mbin`), which
+/// should if present come first in `classes`.
+/// TODO(#953): Make `options` mandatory and always pass it in.
+pub fn make_symbol(
+    value: &str,
+    font_name: &str,
+    mode: Mode,
+    options: Option<&Options>,
+    classes: Vec<String>,
+) -> SymbolNode {
+    let (value, metrics) = lookup_symbol(value, font_name, mode);
+
+    let mut symbol_node = match metrics {
+        Some(metrics) => {
+            let italic = if mode == Mode::Text || options.is_some_and(|o| o.font == "mathit") {
+                0.0
+            } else {
+                metrics.italic
+            };
+            SymbolNode {
+                text: value,
+                height: metrics.height,
+                depth: metrics.depth,
+                italic,
+                skew: metrics.skew,
+                width: metrics.width,
+                classes,
+                ..Default::default()
+            }
+        }
+        // TODO(emily): Figure out a good way to only print this in development
+        None => SymbolNode {
+            text: value,
+            classes,
+            ..Default::default()
+        },
+    };
+
+    if let Some(options) = options {
+        symbol_node.max_font_size = options.size_multiplier;
+        if options.style.is_tight() {
+            symbol_node.classes.push("mtight".to_string());
+        }
+        if let Some(color) = options.get_color() {
+            symbol_node.style.insert("color".to_string(), color);
+        }
+    }
+
+    symbol_node
+}
+
+/// Makes a symbol in Main-Regular or AMS-Regular.
+/// Used for rel, bin, open, close, inner, and punct.
+pub fn math_sym(
+    value: &str,
+    mode: Mode,
+    options: &Options,
+    mut classes: Vec<String>,
+) -> SymbolNode {
+    // Decide what font to render the symbol in by its entry in the symbols
+    // table.
+    // Have a special case for when the value = \ because the \ is used as a
+    // textord in unsupported command errors but cannot be parsed as a regular
+    // text ordinal and is therefore not present as a symbol in the symbols
+    // table for text, as well as a special case for boldsymbol because it
+    // can be used for bold + and -
+    if options.font == "boldsymbol" && lookup_symbol(value, "Main-Bold", mode).1.is_some() {
+        classes.push("mathbf".to_string());
+        make_symbol(value, "Main-Bold", mode, Some(options), classes)
+    } else if value == "\\"
+        || SYMBOLS
+            .get(mode)
+            .get(value)
+            .is_none_or(|info| info.font == Font::Main)
+    {
+        make_symbol(value, "Main-Regular", mode, Some(options), classes)
+    } else {
+        classes.push("amsrm".to_string());
+        make_symbol(value, "AMS-Regular", mode, Some(options), classes)
+    }
+}
+
+/// Determines which of the two font names (Main-Bold and Math-BoldItalic) and
+/// corresponding style tags (mathbf or boldsymbol) to use for font
+/// "boldsymbol", returned as `(font_name, font_class)`.
+pub fn boldsymbol(value: &str, mode: Mode, type_: &str) -> (&'static str, &'static str) {
+    if type_ != "textord" && lookup_symbol(value, "Math-BoldItalic", mode).1.is_some() {
+        ("Math-BoldItalic", "boldsymbol")
+    } else {
+        // Some glyphs do not exist in Math-BoldItalic so we need to use
+        // Main-Bold instead.
+        ("Main-Bold", "mathbf")
+    }
+}
+
+/// Makes either a mathord or textord in the correct font and color.
+pub fn make_ord(group: &AnyParseNode, options: &Options, type_: &str) -> VirtualNode {
+    let mode = Mode::from(group.mode());
+    let text = group.symbol_text().unwrap_or_default();
+
+    let classes = vec!["mord".to_string()];
+
+    // Math mode or Old font (i.e. \rm)
+    let is_font = mode == Mode::Math || (mode == Mode::Text && !options.font.is_empty());
+    let font_or_family = if is_font {
+        &options.font
+    } else {
+        &options.font_family
+    };
+
+    if !font_or_family.is_empty() {
+        let (font_name, font_classes) = if font_or_family == "boldsymbol" {
+            let (font_name, font_class) = boldsymbol(text, mode, type_);
+            (font_name.to_string(), vec![font_class.to_string()])
+        } else if is_font {
+            let font_name = FONT_MAP
+                .get(font_or_family.as_str())
+                .map_or("", |font| font.font_name);
+            (font_name.to_string(), vec![font_or_family.clone()])
+        } else {
+            (
+                retrieve_text_font_name(font_or_family, options.font_weight, options.font_shape),
+                vec![
+                    font_or_family.clone(),
+                    options.font_weight.as_str().to_string(),
+                    options.font_shape.as_str().to_string(),
+                ],
+            )
+        };
+        let classes = [classes.clone(), font_classes].concat();
+
+        if lookup_symbol(text, &font_name, mode).1.is_some() {
+            return make_symbol(text, &font_name, mode, Some(options), classes).into();
+        } else if LIGATURES.contains(&text) && font_name.starts_with("Typewriter") {
+            // Deconstruct ligatures in monospace fonts (\texttt, \tt).
+            let parts = text
+                .chars()
+                .map(|ch| {
+                    make_symbol(
+                        &ch.to_string(),
+                        &font_name,
+                        mode,
+                        Some(options),
+                        classes.clone(),
+                    )
+                    .into()
+                })
+                .collect();
+            return make_fragment(parts).into();
+        }
+    }
+
+    // Makes a symbol in the default font for mathords and textords.
+    match type_ {
+        "mathord" => {
+            let classes = [classes, vec!["mathnormal".to_string()]].concat();
+            make_symbol(text, "Math-Italic", mode, Some(options), classes).into()
+        }
+        "textord" => {
+            let font = SYMBOLS.get(mode).get(text).map(|info| info.font);
+            let weight = options.font_weight.as_str().to_string();
+            let shape = options.font_shape.as_str().to_string();
+            if font == Some(Font::Ams) {
+                let font_name =
+                    retrieve_text_font_name("amsrm", options.font_weight, options.font_shape);
+                let classes = [classes, vec!["amsrm".to_string(), weight, shape]].concat();
+                make_symbol(text, &font_name, mode, Some(options), classes).into()
+            } else {
+                let font_name =
+                    retrieve_text_font_name("textrm", options.font_weight, options.font_shape);
+                let classes = [classes, vec![weight, shape]].concat();
+                make_symbol(text, &font_name, mode, Some(options), classes).into()
+            }
+        }
+        _ => panic!("unexpected type: {} in make_ord", type_),
+    }
+}
+
+/// Calculate the height, depth, and maxFontSize of an element based on its
+/// children.
+fn size_from_children(children: &[VirtualNode]) -> (f64, f64, f64) {
+    let mut height = 0.0_f64;
+    let mut depth = 0.0_f64;
+    let mut max_font_size = 0.0_f64;
+
+    for child in children {
+        height = height.max(child.height());
+        depth = depth.max(child.depth());
+        max_font_size = max_font_size.max(child.max_font_size());
+    }
+
+    (height, depth, max_font_size)
+}
+
 /// Makes a span with the given list of classes, list of children, and options.
+///
+/// TODO(#953): Ensure that `options` is always provided (currently some call
+/// sites don't pass it) and make the type below mandatory.
+/// TODO: add a separate argument for math class (e.g. `mop`, `mbin`), which
+/// should if present come first in `classes`.
 pub fn make_span(
-    classes: Option<Vec<String>>,
-    children: Option<Vec<HtmlDomNode>>,
-    options: Option<Options>,
+    classes: Vec<String>,
+    children: Vec<VirtualNode>,
+    options: Option<&Options>,
+    style: Option<CssStyle>,
+) -> DomSpan {
+    let mut span = Span::new(classes, children, options, style.unwrap_or_default());
+
+    let (height, depth, max_font_size) = size_from_children(&span.children);
+    span.height = height;
+    span.depth = depth;
+    span.max_font_size = max_font_size;
+
+    span
+}
+
+/// Makes an anchor with the given href, list of classes, list of children,
+/// and options.
+pub fn make_anchor(
+    href: &str,
+    classes: Vec<String>,
+    children: Vec<VirtualNode>,
+    options: &Options,
+) -> Anchor {
+    let mut anchor = Anchor::new(href, classes, children, options);
+
+    let (height, depth, max_font_size) = size_from_children(&anchor.children);
+    anchor.height = height;
+    anchor.depth = depth;
+    anchor.max_font_size = max_font_size;
+
+    anchor
+}
+
+/// SVG spans are not sized from their children, since an SVG's size is
+/// given by its attributes.
+pub fn make_svg_span(
+    classes: Vec<String>,
+    children: Vec<VirtualNode>,
+    options: Option<&Options>,
     style: Option<CssStyle>,
 ) -> DomSpan {
-    let span = Span::new(classes, children, options, style);
+    Span::new(classes, children, options, style.unwrap_or_default())
+}
+
+/// Makes a span of the given thickness, e.g. for a fraction bar.
+pub fn make_line_span(class_name: &str, options: &Options, thickness: Option<f64>) -> DomSpan {
+    let mut line = make_span(vec![class_name.to_string()], vec![], Some(options), None);
+    line.height = thickness
+        .unwrap_or(options.font_metrics().default_rule_thickness)
+        .max(options.min_rule_thickness);
+    line.style
+        .insert("borderBottomWidth".to_string(), make_em(line.height));
+    line.max_font_size = 1.0;
+    line
+}
 
-    size_element_from_children(&span);
+/// Makes a document fragment with the given list of children.
+pub fn make_fragment(children: Vec<VirtualNode>) -> DocumentFragment {
+    let mut fragment = DocumentFragment::new(children);
+
+    let (height, depth, max_font_size) = size_from_children(&fragment.children);
+    fragment.height = height;
+    fragment.depth = depth;
+    fragment.max_font_size = max_font_size;
+
+    fragment
+}
+
+/// Wraps group in a span if it's a document fragment, allowing to apply
+/// classes and styles
+pub fn wrap_fragment(group: VirtualNode, options: &Options) -> VirtualNode {
+    if let VirtualNode::Fragment(_) = group {
+        return make_span(vec![], vec![group], Some(options), None).into();
+    }
+    group
+}
+
+/// An element in a vertical list, with its shift (for
+/// `VListParam::IndividualShift`) and the classes, style and margins of the
+/// span it gets wrapped in.
+#[derive(Debug, Clone)]
+pub struct VListElem {
+    pub elem: VirtualNode,
+    pub shift: f64,
+    pub margin_left: Option<String>,
+    pub margin_right: Option<String>,
+    pub wrapper_classes: Vec<String>,
+    pub wrapper_style: CssStyle,
+}
+
+impl VListElem {
+    pub fn new(elem: impl Into<VirtualNode>) -> Self {
+        VListElem {
+            elem: elem.into(),
+            shift: 0.0,
+            margin_left: None,
+            margin_right: None,
+            wrapper_classes: vec![],
+            wrapper_style: CssStyle::new(),
+        }
+    }
+
+    /// An element shifted down by `shift`.
+    pub fn shifted(elem: impl Into<VirtualNode>, shift: f64) -> Self {
+        VListElem {
+            shift,
+            ..VListElem::new(elem)
+        }
+    }
+}
+
+/// A child of a vertical list: an element, or a kern of the given size.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum VListChild {
+    Elem(VListElem),
+    Kern(f64),
+}
+
+impl From<VListElem> for VListChild {
+    fn from(elem: VListElem) -> Self {
+        VListChild::Elem(elem)
+    }
+}
+
+/// The parameters of `make_v_list`: the children, listed from bottom to top,
+/// and how to position them.
+#[derive(Debug, Clone)]
+pub enum VListParam {
+    /// Each element is shifted down from the baseline by its own `shift`.
+    IndividualShift(Vec<VListElem>),
+    /// The top of the list is at the given height above the baseline.
+    Top(f64, Vec<VListChild>),
+    /// The bottom of the list is at the given depth below the baseline.
+    Bottom(f64, Vec<VListChild>),
+    /// The baseline of the first element is shifted down by the given amount.
+    Shift(f64, Vec<VListChild>),
+    /// The baseline of the first element is the baseline of the list.
+    FirstBaseline(Vec<VListChild>),
+}
+
+/// Computes the updated `children` list and the overall depth.
+fn get_v_list_children_and_depth(params: VListParam) -> (Vec<VListChild>, f64) {
+    match params {
+        VListParam::IndividualShift(old_children) => {
+            let mut old_children = old_children.into_iter();
+            let Some(first) = old_children.next() else {
+                return (vec![], 0.0);
+            };
+
+            // Add in kerns to the list of params.children to get each element to be
+            // shifted to the correct specified shift
+            let depth = -first.shift - first.elem.depth();
+            let mut curr_pos = depth;
+            let mut prev_size = first.elem.height() + first.elem.depth();
+            let mut children = vec![VListChild::Elem(first)];
+            for child in old_children {
+                let diff = -child.shift - curr_pos - child.elem.depth();
+                let size = diff - prev_size;
+
+                curr_pos += diff;
+                prev_size = child.elem.height() + child.elem.depth();
+
+                children.push(VListChild::Kern(size));
+                children.push(VListChild::Elem(child));
+            }
+
+            (children, depth)
+        }
+        VListParam::Top(position_data, children) => {
+            // We always start at the bottom, so calculate the bottom by adding up
+            // all the sizes
+            let mut bottom = position_data;
+            for child in &children {
+                bottom -= match child {
+                    VListChild::Kern(size) => *size,
+                    VListChild::Elem(child) => child.elem.height() + child.elem.depth(),
+                };
+            }
+            (children, bottom)
+        }
+        VListParam::Bottom(position_data, children) => (children, -position_data),
+        VListParam::Shift(position_data, children) => {
+            let Some(VListChild::Elem(first_child)) = children.first() else {
+                panic!("First child must have type \"elem\".");
+            };
+            let depth = -first_child.elem.depth() - position_data;
+            (children, depth)
+        }
+        VListParam::FirstBaseline(children) => {
+            let Some(VListChild::Elem(first_child)) = children.first() else {
+                panic!("First child must have type \"elem\".");
+            };
+            let depth = -first_child.elem.depth();
+            (children, depth)
+        }
+    }
+}
+
+/// Makes a vertical list by stacking elements and kerns on top of each other.
+/// Allows for many different ways of specifying the positioning method.
+///
+/// See VListParam documentation above.
+pub fn make_v_list(params: VListParam) -> DomSpan {
+    let (children, depth) = get_v_list_children_and_depth(params);
+
+    // Create a strut that is taller than any list item. The strut is added to
+    // each item, where it will determine the item's baseline. Since it has
+    // `overflow:hidden`, the strut's top edge will sit on the item's line box's
+    // top edge and the strut's bottom edge will sit on the item's baseline,
+    // with no additional line-height spacing. This allows the item baseline to
+    // be positioned precisely without worrying about font ascent and
+    // line-height.
+    let mut pstrut_size = 0.0_f64;
+    for child in &children {
+        if let VListChild::Elem(child) = child {
+            pstrut_size = pstrut_size
+                .max(child.elem.max_font_size())
+                .max(child.elem.height());
+        }
+    }
+    pstrut_size += 2.0;
+    let mut pstrut = make_span(vec!["pstrut".to_string()], vec![], None, None);
+    pstrut
+        .style
+        .insert("height".to_string(), make_em(pstrut_size));
+
+    // Create a new list of actual children at the correct offsets
+    let mut real_children = vec![];
+    let mut min_pos = depth;
+    let mut max_pos = depth;
+    let mut curr_pos = depth;
+    for child in children {
+        match child {
+            VListChild::Kern(size) => curr_pos += size,
+            VListChild::Elem(child) => {
+                let VListElem {
+                    elem,
+                    margin_left,
+                    margin_right,
+                    wrapper_classes,
+                    wrapper_style,
+                    ..
+                } = child;
+                let (elem_height, elem_depth) = (elem.height(), elem.depth());
+
+                let mut child_wrap = make_span(
+                    wrapper_classes,
+                    vec![pstrut.clone().into(), elem],
+                    None,
+                    Some(wrapper_style),
+                );
+                child_wrap.style.insert(
+                    "top".to_string(),
+                    make_em(-pstrut_size - curr_pos - elem_depth),
+                );
+                if let Some(margin_left) = margin_left {
+                    child_wrap
+                        .style
+                        .insert("marginLeft".to_string(), margin_left);
+                }
+                if let Some(margin_right) = margin_right {
+                    child_wrap
+                        .style
+                        .insert("marginRight".to_string(), margin_right);
+                }
+
+                real_children.push(child_wrap.into());
+                curr_pos += elem_height + elem_depth;
+            }
+        }
+        min_pos = min_pos.min(curr_pos);
+        max_pos = max_pos.max(curr_pos);
+    }
+
+    // The vlist contents go in a table-cell with `vertical-align:bottom`.
+    // This cell's bottom edge will determine the containing table's baseline
+    // without overly expanding the containing line-box.
+    let mut vlist = make_span(vec!["vlist".to_string()], real_children, None, None);
+    vlist.style.insert("height".to_string(), make_em(max_pos));
+
+    // A second row is used if necessary to represent the vlist's depth.
+    let rows: Vec<VirtualNode> = if min_pos < 0.0 {
+        // We will define depth in an empty span with display: table-cell.
+        // It should render with the height that we define. But Chrome, in
+        // contenteditable mode only, treats that span as if it contains some
+        // text content. And that min-height over-rides our desired height.
+        // So we put another empty span inside the depth strut span.
+        let empty_span = make_span(vec![], vec![], None, None);
+        let mut depth_strut = make_span(
+            vec!["vlist".to_string()],
+            vec![empty_span.into()],
+            None,
+            None,
+        );
+        depth_strut
+            .style
+            .insert("height".to_string(), make_em(-min_pos));
+
+        // Safari wants the first row to have inline content; otherwise it
+        // puts the bottom of the *second* row on the baseline.
+        let top_strut = make_span(
+            vec!["vlist-s".to_string()],
+            vec![SymbolNode {
+                text: "\u{200b}".to_string(),
+                ..Default::default()
+            }
+            .into()],
+            None,
+            None,
+        );
+
+        vec![
+            make_span(
+                vec!["vlist-r".to_string()],
+                vec![vlist.into(), top_strut.into()],
+                None,
+                None,
+            )
+            .into(),
+            make_span(
+                vec!["vlist-r".to_string()],
+                vec![depth_strut.into()],
+                None,
+                None,
+            )
+            .into(),
+        ]
+    } else {
+        vec![make_span(vec!["vlist-r".to_string()], vec![vlist.into()], None, None).into()]
+    };
+
+    let two_rows = rows.len() == 2;
+    let mut vtable = make_span(vec!["vlist-t".to_string()], rows, None, None);
+    if two_rows {
+        vtable.classes.push("vlist-t2".to_string());
+    }
+    vtable.height = max_pos;
+    vtable.depth = -min_pos;
+    vtable
+}
+
+/// Glue is a concept from TeX which is a flexible space between elements in
+/// either a vertical or horizontal list. In KaTeX, at least for now, it's
+/// static space between elements in a horizontal layout.
+pub fn make_glue(measurement: &Measurement, options: &Options) -> DomSpan {
+    // Make an empty span for the space
+    let mut rule = make_span(vec!["mspace".to_string()], vec![], Some(options), None);
+    let size = calculate_size(measurement, options);
+    rule.style.insert("marginRight".to_string(), make_em(size));
+    rule
+}
+
+/// Takes font options, and returns the appropriate font name for the
+/// font family, weight and shape.
+pub fn retrieve_text_font_name(
+    font_family: &str,
+    font_weight: FontWeight,
+    font_shape: FontShape,
+) -> String {
+    let base_font_name = match font_family {
+        "amsrm" => "AMS",
+        "textrm" => "Main",
+        "textsf" => "SansSerif",
+        "texttt" => "Typewriter",
+        _ => font_family, // use fonts added by a plugin
+    };
+
+    let font_styles_name = match (font_weight, font_shape) {
+        (FontWeight::TextBF, FontShape::TextIt) => "BoldItalic",
+        (FontWeight::TextBF, _) => "Bold",
+        (_, FontShape::TextIt) => "Italic",
+        _ => "Regular",
+    };
+
+    format!("{}-{}", base_font_name, font_styles_name)
+}
+
+/// A font for math mode: the MathML `mathvariant` it corresponds to and the
+/// name of the font used for it in HTML.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontMapEntry {
+    pub variant: &'static str,
+    pub font_name: &'static str,
+}
+
+/// Maps TeX font commands to objects containing:
+/// - variant: string used for "mathvariant" attribute in build_mathml.rs
+/// - font_name: the "style" parameter to font_metrics::get_character_metrics
+pub static FONT_MAP: LazyLock<HashMap<&'static str, FontMapEntry>> = LazyLock::new(|| {
+    let entry = |variant, font_name| FontMapEntry { variant, font_name };
+    HashMap::from([
+        // styles
+        ("mathbf", entry("bold", "Main-Bold")),
+        ("mathrm", entry("normal", "Main-Regular")),
+        ("textit", entry("italic", "Main-Italic")),
+        ("mathit", entry("italic", "Main-Italic")),
+        ("mathnormal", entry("italic", "Math-Italic")),
+        ("mathsfit", entry("sans-serif-italic", "SansSerif-Italic")),
+        // Default math font, "mathnormal" and "boldsymbol", are missing because
+        // they require the use of several fonts: Main-Italic and Math-Italic
+        // for default math font, Main-Italic, Math-BoldItalic, and Main-Bold
+        // for boldsymbol.  This is handled by make_ord.
+
+        // families
+        ("mathbb", entry("double-struck", "AMS-Regular")),
+        ("mathcal", entry("script", "Caligraphic-Regular")),
+        ("mathfrak", entry("fraktur", "Fraktur-Regular")),
+        ("mathscr", entry("script", "Script-Regular")),
+        ("mathsf", entry("sans-serif", "SansSerif-Regular")),
+        ("mathtt", entry("monospace", "Typewriter-Regular")),
+    ])
+});
+
+/// The path name, width and height of each of the fixed-size SVG images,
+/// for `static_svg`.
+pub fn svg_data(name: &str) -> Option<(&'static str, f64, f64)> {
+    //   path, width, height
+    let data = match name {
+        "vec" => ("vec", 0.471, 0.714), // values from the font glyph
+        "oiintSize1" => ("oiintSize1", 0.957, 0.499), // oval to overlay the integrand
+        "oiintSize2" => ("oiintSize2", 1.472, 0.659),
+        "oiiintSize1" => ("oiiintSize1", 1.304, 0.499),
+        "oiiintSize2" => ("oiiintSize2", 1.98, 0.659),
+        _ => return None,
+    };
+    Some(data)
+}
 
+pub fn static_svg(value: &str, options: &Options) -> DomSpan {
+    // Create a span with inline SVG for the element.
+    let (path_name, width, height) =
+        svg_data(value).unwrap_or_else(|| panic!("Unknown static SVG: {}", value));
+    let path = PathNode::new(path_name, None);
+    let svg_node = SvgNode::new(
+        vec![SvgChildNode::SvgPathNode(path)],
+        HashMap::from([
+            ("width".to_string(), make_em(width)),
+            ("height".to_string(), make_em(height)),
+            // Override CSS rule `.katex svg { width: 100% }`
+            ("style".to_string(), format!("width:{}", make_em(width))),
+            (
+                "viewBox".to_string(),
+                format!("0 0 {} {}", 1000.0 * width, 1000.0 * height),
+            ),
+            ("preserveAspectRatio".to_string(), "xMinYMin".to_string()),
+        ]),
+    );
+    let mut span = make_svg_span(
+        vec!["overlay".to_string()],
+        vec![svg_node.into()],
+        Some(options),
+        None,
+    );
+    span.height = height;
+    span.style.insert("height".to_string(), make_em(height));
+    span.style.insert("width".to_string(), make_em(width));
     span
 }
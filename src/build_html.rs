@@ -0,0 +1,511 @@
+//! This file does the main work of building a domTree structure from a parse
+//! tree. The entry point is the `build_html` function, which takes a parse tree.
+//! Then, the build_expression, build_group, and various group builders (in
+//! `functions/`) are used to produce the final HTML tree.
+use super::*;
+
+// Binary atoms (first class `mbin`) change into ordinary atoms (`mord`)
+// depending on their surroundings. See TeXbook pg. 442-446, Rules 5 and 6,
+// and the text before Rule 19.
+const BIN_LEFT_CANCELLER: &[&str] = &["leftmost", "mbin", "mopen", "mrel", "mop", "mpunct"];
+const BIN_RIGHT_CANCELLER: &[&str] = &["rightmost", "mrel", "mclose", "mpunct"];
+
+/// The style named by a `styling` node.
+pub fn style_map(style: &str) -> Style {
+    match style {
+        "display" => Style::DISPLAY,
+        "script" => Style::SCRIPT,
+        "scriptscript" => Style::SCRIPTSCRIPT,
+        _ => Style::TEXT,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+const DOM_ENUM: &[&str] = &[
+    "mord", "mop", "mbin", "mrel", "mopen", "mclose", "mpunct", "minner",
+];
+
+/// Take a list of nodes, build them in order, and return a list of the built
+/// nodes. document fragments are flattened into their contents, so the
+/// returned list contains no fragments. `is_real_group` is true if `expression`
+/// is a real group (no atoms will be added on either side), as opposed to
+/// a partial group (e.g. one created by \color). `is_root` is true for the
+/// whole expression. `surrounding` is an array consisting type of nodes that
+/// will be added to the left and right.
+pub fn build_expression(
+    expression: &[AnyParseNode],
+    options: &Options,
+    is_real_group: bool,
+    is_root: bool,
+    surrounding: [Option<&str>; 2],
+) -> Vec<VirtualNode> {
+    // Parse expressions into `groups`.
+    let mut groups = vec![];
+    for group in expression {
+        match build_group(Some(group), options, None) {
+            VirtualNode::Fragment(fragment) => groups.extend(fragment.children),
+            output => groups.push(output),
+        }
+    }
+
+    // If `expression` is a partial group, let the parent handle spacings
+    // to avoid processing groups multiple times.
+    if !is_real_group {
+        return groups;
+    }
+
+    let glue_options = match expression {
+        [AnyParseNode::Sizing(node)] => options.having_size(node.size as usize),
+        [AnyParseNode::Styling(node)] => options.having_style(style_map(&node.style)),
+        _ => options.clone(),
+    };
+
+    // Dummy spans for determining spacings between surrounding atoms.
+    // If `expression` has no atoms on the left or right, class "leftmost"
+    // or "rightmost", respectively, is used to indicate it.
+    let dummy_prev = make_span(
+        vec![surrounding[0].unwrap_or("leftmost").to_string()],
+        vec![],
+        Some(options),
+        None,
+    );
+    let dummy_next = make_span(
+        vec![surrounding[1].unwrap_or("rightmost").to_string()],
+        vec![],
+        Some(options),
+        None,
+    );
+    let mut nodes = TraversedNodes {
+        groups,
+        prev: dummy_prev.into(),
+        next: dummy_next.into(),
+        // The line start after a \newline, treated like the beginning of the
+        // expression.
+        leftmost: make_span(vec!["leftmost".to_string()], vec![], None, None).into(),
+    };
+    let visits = traverse_non_space_nodes(&nodes.groups, is_root);
+
+    // TODO: These code assumes that a node's math class is the first element
+    // of its `classes` array. A later cleanup should ensure this, for
+    // instance by changing the signature of `make_span`.
+
+    // Before determining what spaces to insert, perform bin cancellation.
+    // Binary operators change to ordinary symbols in some contexts.
+    for visit in &visits {
+        let prev_type = nodes.get(&visit.prev).classes().first().cloned();
+        let type_ = nodes.get(&visit.node).classes().first().cloned();
+        if prev_type.as_deref() == Some("mbin")
+            && type_
+                .as_deref()
+                .is_some_and(|type_| BIN_RIGHT_CANCELLER.contains(&type_))
+        {
+            nodes.set_math_class(&visit.prev, "mord");
+        } else if type_.as_deref() == Some("mbin")
+            && prev_type
+                .as_deref()
+                .is_some_and(|prev_type| BIN_LEFT_CANCELLER.contains(&prev_type))
+        {
+            nodes.set_math_class(&visit.node, "mord");
+        }
+    }
+
+    let mut glue = vec![];
+    for visit in &visits {
+        let node = nodes.get(&visit.node);
+        let prev_type = get_type_of_dom_tree(nodes.get(&visit.prev), None);
+        let type_ = get_type_of_dom_tree(node, None);
+
+        // 'mtight' indicates that the node is script or scriptscript style.
+        let space = match (prev_type, type_) {
+            (Some(prev_type), Some(type_)) if node.has_class("mtight") => {
+                tight_spacings(prev_type, type_)
+            }
+            (Some(prev_type), Some(type_)) => spacings(prev_type, type_),
+            _ => None,
+        };
+        if let Some(space) = space {
+            // Insert glue (spacing) after the `prev`.
+            glue.push((visit.insertion.clone(), make_glue(&space, &glue_options)));
+        }
+    }
+    // Insert from the back, so the paths of earlier insertions stay valid.
+    for (insertion, glue) in glue.into_iter().rev() {
+        match insertion {
+            Insertion::After(path) => {
+                let (index, parent) = path.split_last().unwrap();
+                list_at_mut(&mut nodes.groups, parent).insert(index + 1, glue.into());
+            }
+            Insertion::Front(parent) => {
+                list_at_mut(&mut nodes.groups, &parent).insert(0, glue.into());
+            }
+        }
+    }
+
+    nodes.groups
+}
+
+/// A node visited by `traverse_non_space_nodes`: the dummy nodes on either
+/// side, the line start after a `\newline`, or a node of the expression given
+/// by its path through partial groups.
+#[derive(Debug, Clone)]
+enum Slot {
+    Prev,
+    Next,
+    Leftmost,
+    Path(Vec<usize>),
+}
+
+/// Where to insert a node after the previous node: after the node at the given
+/// path, or at the front of the given list if nothing came before.
+#[derive(Debug, Clone)]
+enum Insertion {
+    After(Vec<usize>),
+    Front(Vec<usize>),
+}
+
+/// A non-space node, the node before it, and where to insert a node between
+/// the two.
+struct Visit {
+    node: Slot,
+    prev: Slot,
+    insertion: Insertion,
+}
+
+/// The built nodes of an expression together with the dummy nodes that
+/// `Slot` refers to.
+struct TraversedNodes {
+    groups: Vec<VirtualNode>,
+    prev: VirtualNode,
+    next: VirtualNode,
+    leftmost: VirtualNode,
+}
+
+impl TraversedNodes {
+    fn get(&self, slot: &Slot) -> &VirtualNode {
+        match slot {
+            Slot::Prev => &self.prev,
+            Slot::Next => &self.next,
+            Slot::Leftmost => &self.leftmost,
+            Slot::Path(path) => {
+                let (index, parent) = path.split_last().unwrap();
+                &list_at(&self.groups, parent)[*index]
+            }
+        }
+    }
+
+    /// Replace the math class (the first class) of a node.
+    fn set_math_class(&mut self, slot: &Slot, class: &str) {
+        let node = match slot {
+            Slot::Prev => &mut self.prev,
+            Slot::Next => &mut self.next,
+            Slot::Leftmost => &mut self.leftmost,
+            Slot::Path(path) => {
+                let (index, parent) = path.split_last().unwrap();
+                &mut list_at_mut(&mut self.groups, parent)[*index]
+            }
+        };
+        if let Some(first) = node.classes_mut().and_then(|classes| classes.first_mut()) {
+            *first = class.to_string();
+        }
+    }
+}
+
+/// The list of nodes at the given path through partial groups.
+fn list_at<'a>(nodes: &'a [VirtualNode], path: &[usize]) -> &'a [VirtualNode] {
+    match path.split_first() {
+        None => nodes,
+        Some((index, rest)) => list_at(check_partial_group(&nodes[*index]).unwrap(), rest),
+    }
+}
+
+fn list_at_mut<'a>(nodes: &'a mut Vec<VirtualNode>, path: &[usize]) -> &'a mut Vec<VirtualNode> {
+    match path.split_first() {
+        None => nodes,
+        Some((index, rest)) => {
+            list_at_mut(check_partial_group_mut(&mut nodes[*index]).unwrap(), rest)
+        }
+    }
+}
+
+/// Depth-first traverse the non-space nodes of `nodes`, listing each with the
+/// previous node and where to insert a node after that previous node. The
+/// dummy `Slot::Prev` comes before the first node and the dummy `Slot::Next`
+/// is visited after the last one.
+/// Used for bin cancellation and inserting spacings.
+fn traverse_non_space_nodes(nodes: &[VirtualNode], is_root: bool) -> Vec<Visit> {
+    struct State {
+        prev: Slot,
+        insert_after: Option<Vec<usize>>,
+        visits: Vec<Visit>,
+    }
+
+    impl State {
+        fn visit(&mut self, node: Slot, parent: &[usize]) {
+            let insertion = match &self.insert_after {
+                Some(path) => Insertion::After(path.clone()),
+                // insert at front
+                None => Insertion::Front(parent.to_vec()),
+            };
+            self.visits.push(Visit {
+                node,
+                prev: self.prev.clone(),
+                insertion,
+            });
+        }
+    }
+
+    fn traverse(nodes: &[VirtualNode], parent: &[usize], is_root: bool, state: &mut State) {
+        for (i, node) in nodes.iter().enumerate() {
+            let path = [parent, &[i]].concat();
+            if let Some(children) = check_partial_group(node) {
+                // Recursive DFS
+                traverse(children, &path, is_root, state);
+                continue;
+            }
+
+            // Ignore explicit spaces (e.g., \;, \,) when determining what implicit
+            // spacing should go between atoms of different classes
+            let nonspace = !node.has_class("mspace");
+            if nonspace {
+                state.visit(Slot::Path(path.clone()), parent);
+                state.prev = Slot::Path(path.clone());
+            } else if is_root && node.has_class("newline") {
+                state.prev = Slot::Leftmost; // treat like beginning of line
+            }
+            state.insert_after = Some(path);
+        }
+    }
+
+    let mut state = State {
+        prev: Slot::Prev,
+        insert_after: None,
+        visits: vec![],
+    };
+    traverse(nodes, &[], is_root, &mut state);
+    // the right node, if exists
+    state.visit(Slot::Next, &[]);
+    state.visits
+}
+
+/// Check if given node is a partial group, i.e., does not affect spacing
+/// around, and return its children if so.
+fn check_partial_group(node: &VirtualNode) -> Option<&Vec<VirtualNode>> {
+    match node {
+        VirtualNode::Fragment(fragment) => Some(&fragment.children),
+        VirtualNode::Anchor(anchor) => Some(&anchor.children),
+        VirtualNode::Span(span) if span.has_class("enclosing") => Some(&span.children),
+        _ => None,
+    }
+}
+
+fn check_partial_group_mut(node: &mut VirtualNode) -> Option<&mut Vec<VirtualNode>> {
+    match node {
+        VirtualNode::Fragment(fragment) => Some(&mut fragment.children),
+        VirtualNode::Anchor(anchor) => Some(&mut anchor.children),
+        VirtualNode::Span(span) if span.has_class("enclosing") => Some(&mut span.children),
+        _ => None,
+    }
+}
+
+/// Return the outermost node of a domTree.
+fn get_outermost_node(node: &VirtualNode, side: Side) -> &VirtualNode {
+    if let Some(children) = check_partial_group(node) {
+        let child = match side {
+            Side::Right => children.last(),
+            Side::Left => children.first(),
+        };
+        if let Some(child) = child {
+            return get_outermost_node(child, side);
+        }
+    }
+    node
+}
+
+/// Return math atom class (mclass) of a domTree.
+/// If `side` is given, it will get the type of the outermost node at given side.
+pub fn get_type_of_dom_tree(node: &VirtualNode, side: Option<Side>) -> Option<&'static str> {
+    let node = match side {
+        Some(side) => get_outermost_node(node, side),
+        None => node,
+    };
+    // This makes a lot of assumptions as to where the type of atom
+    // appears.  We should do a better job of enforcing this.
+    let class = node.classes().first()?;
+    DOM_ENUM.iter().copied().find(|type_| type_ == class)
+}
+
+pub fn make_null_delimiter(options: &Options, classes: Vec<String>) -> DomSpan {
+    let more_classes = [
+        vec!["nulldelimiter".to_string()],
+        options.base_sizing_classes(),
+    ]
+    .concat();
+    make_span([classes, more_classes].concat(), vec![], None, None)
+}
+
+/// build_group is the function that takes a group and calls the correct groupType
+/// function for it. It also handles the interaction of size and style changes
+/// between parents and children.
+pub fn build_group(
+    group: Option<&AnyParseNode>,
+    options: &Options,
+    base_options: Option<&Options>,
+) -> VirtualNode {
+    let Some(group) = group else {
+        return make_span(vec![], vec![], None, None).into();
+    };
+
+    let Some(group_builder) = functions::HTML_GROUP_BUILDERS.get(group.type_()) else {
+        panic!("Got group of unknown type: '{}'", group.type_());
+    };
+
+    // Call the group builder function
+    let mut group_node = group_builder(group, options);
+
+    // If the size changed between the parent and the current group, account
+    // for that size difference.
+    if let Some(base_options) = base_options {
+        if options.size != base_options.size {
+            group_node = make_span(
+                options.sizing_classes(base_options),
+                vec![group_node],
+                Some(options),
+                None,
+            )
+            .into();
+
+            let multiplier = options.size_multiplier / base_options.size_multiplier;
+
+            group_node.set_height(group_node.height() * multiplier);
+            group_node.set_depth(group_node.depth() * multiplier);
+        }
+    }
+
+    group_node
+}
+
+/// Combine an array of HTML DOM nodes (e.g., the output of `build_expression`)
+/// into an unbreakable HTML node of class .base, with proper struts to
+/// guarantee correct vertical extent.  `build_html` calls this repeatedly to
+/// make up the entire expression as a sequence of unbreakable units.
+fn build_html_unbreakable(children: Vec<VirtualNode>, options: &Options) -> DomSpan {
+    // Compute height and depth of this chunk.
+    let mut body = make_span(vec!["base".to_string()], children, Some(options), None);
+
+    // Add strut, which ensures that the top of the HTML element falls at
+    // the height of the expression, and the bottom of the HTML element
+    // falls at the depth of the expression.
+    let mut strut = make_span(vec!["strut".to_string()], vec![], None, None);
+    strut
+        .style
+        .insert("height".to_string(), make_em(body.height + body.depth));
+    if body.depth != 0.0 {
+        strut
+            .style
+            .insert("verticalAlign".to_string(), make_em(-body.depth));
+    }
+    body.children.insert(0, strut.into());
+
+    body
+}
+
+/// Take an entire parse tree, and build it into an appropriate set of HTML
+/// nodes.
+pub fn build_html(tree: &[AnyParseNode], options: &Options) -> DomSpan {
+    // Strip off outer tag wrapper for processing below.
+    let (tree, tag) = match tree {
+        [AnyParseNode::Tag(tag)] => (tag.body.as_slice(), Some(&tag.tag)),
+        _ => (tree, None),
+    };
+
+    // Build the expression contained in the tree
+    let mut expression = build_expression(tree, options, true, true, [None, None]);
+
+    let mut eqn_num = None;
+    if expression.len() == 2 && expression[1].has_class("tag") {
+        // An environment with automatic equation numbers, e.g. {gather}.
+        eqn_num = expression.pop();
+    }
+
+    let mut children: Vec<VirtualNode> = vec![];
+
+    // Create one base node for each chunk between potential line breaks.
+    // The TeXBook [p.173] says "A formula will be broken only after a
+    // relation symbol like $=$ or $<$ or $\rightarrow$, or after a binary
+    // operation symbol like $+$ or $-$ or $\times$, where the relation or
+    // binary operation is on the ``outer level'' of the formula (i.e., not
+    // enclosed in {...} and not part of an \over construction)."
+
+    let mut parts = vec![];
+    let mut expression = expression.into_iter().peekable();
+    while let Some(node) = expression.next() {
+        if node.has_class("mbin") || node.has_class("mrel") || node.has_class("allowbreak") {
+            parts.push(node);
+            // Put any post-operator glue on same line as operator.
+            // Watch for \nobreak along the way, and stop at \newline.
+            let mut nobreak = false;
+            while let Some(glue) =
+                expression.next_if(|next| next.has_class("mspace") && !next.has_class("newline"))
+            {
+                if glue.has_class("nobreak") {
+                    nobreak = true;
+                }
+                parts.push(glue);
+            }
+            // Don't allow break if \nobreak among the post-operator glue.
+            if !nobreak {
+                children.push(build_html_unbreakable(std::mem::take(&mut parts), options).into());
+            }
+        } else if node.has_class("newline") {
+            // Write the line except the newline
+            if !parts.is_empty() {
+                children.push(build_html_unbreakable(std::mem::take(&mut parts), options).into());
+            }
+            // Put the newline at the top level
+            children.push(node);
+        } else {
+            parts.push(node);
+        }
+    }
+    if !parts.is_empty() {
+        children.push(build_html_unbreakable(parts, options).into());
+    }
+
+    // Now, if there was a tag, build it too and append it as a final child.
+    let mut has_tag_child = false;
+    if let Some(tag) = tag {
+        let mut tag_child = build_html_unbreakable(
+            build_expression(tag, options, true, false, [None, None]),
+            options,
+        );
+        tag_child.classes = vec!["tag".to_string()];
+        children.push(tag_child.into());
+        has_tag_child = true;
+    } else if let Some(eqn_num) = eqn_num {
+        children.push(eqn_num);
+    }
+
+    let mut html_node = make_span(vec!["katex-html".to_string()], children, None, None);
+    html_node.set_attribute("aria-hidden", "true");
+
+    // Adjust the strut of the tag to be the maximum height of all children
+    // (the height of the enclosing html_node) for proper vertical alignment.
+    if has_tag_child {
+        let (height, depth) = (html_node.height, html_node.depth);
+        if let Some(VirtualNode::Span(tag_child)) = html_node.children.last_mut() {
+            if let Some(style) = tag_child.children[0].style_mut() {
+                style.insert("height".to_string(), make_em(height + depth));
+                if depth != 0.0 {
+                    style.insert("verticalAlign".to_string(), make_em(-depth));
+                }
+            }
+        }
+    }
+
+    html_node
+}
@@ -0,0 +1,295 @@
+//! This file converts a parse tree into a corresponding MathML tree. The main
+//! entry point is the `build_math_ml` function, which takes a parse tree from
+//! the parser.
+use super::*;
+
+/// Takes a symbol and converts it into a MathML text node after performing
+/// optional replacement from symbols.rs.
+pub fn make_text(text: &str, mode: Mode, options: Option<&Options>) -> TextNode {
+    let is_typewriter = |font: &str| font.get(4..6) == Some("tt");
+    if let Some(replace) = SYMBOLS.get(mode).get(text).and_then(|info| info.replace) {
+        // Characters from the Mathematical Alphanumeric Symbols block keep
+        // their own glyph.
+        let is_wide = text
+            .chars()
+            .next()
+            .is_some_and(|ch| ('\u{1d400}'..='\u{1d7ff}').contains(&ch));
+        let is_typewriter_ligature = LIGATURES.contains(&text)
+            && options.is_some_and(|options| {
+                is_typewriter(&options.font_family) || is_typewriter(&options.font)
+            });
+        if !is_wide && !is_typewriter_ligature {
+            return TextNode::new(replace);
+        }
+    }
+    TextNode::new(text)
+}
+
+/// Wrap the given array of nodes in an <mrow> node if needed, i.e.,
+/// unless the array has length 1.  Always returns a single node.
+pub fn make_row(mut body: Vec<MathDomNode>) -> MathDomNode {
+    if body.len() == 1 {
+        body.remove(0)
+    } else {
+        MathNode::new(MathNodeType::Mrow, body, vec![]).into()
+    }
+}
+
+/// Returns the math variant as a string or None if none is required.
+pub fn get_variant(group: &AnyParseNode, options: &Options) -> Option<&'static str> {
+    let text_it = options.font_shape == FontShape::TextIt;
+    let text_bf = options.font_weight == FontWeight::TextBF;
+
+    // Handle \text... font specifiers as best we can.
+    // MathML has a limited list of allowable mathvariant specifiers; see
+    // https://www.w3.org/TR/MathML3/chapter3.html#presm.commatt
+    if options.font_family == "texttt" {
+        return Some("monospace");
+    } else if options.font_family == "textsf" {
+        return Some(match (text_it, text_bf) {
+            (true, true) => "sans-serif-bold-italic",
+            (true, false) => "sans-serif-italic",
+            (false, true) => "bold-sans-serif",
+            (false, false) => "sans-serif",
+        });
+    } else if text_it && text_bf {
+        return Some("bold-italic");
+    } else if text_it {
+        return Some("italic");
+    } else if text_bf {
+        return Some("bold");
+    }
+
+    let font = options.font.as_str();
+    match font {
+        "" | "mathnormal" => return None,
+        "mathit" => return Some("italic"),
+        "boldsymbol" => {
+            return Some(if group.type_() == "textord" {
+                "bold"
+            } else {
+                "bold-italic"
+            })
+        }
+        "mathbf" => return Some("bold"),
+        "mathbb" => return Some("double-struck"),
+        "mathsfit" => return Some("sans-serif-italic"),
+        "mathfrak" => return Some("fraktur"),
+        // MathML makes no distinction between script and calligraphic
+        "mathscr" | "mathcal" => return Some("script"),
+        "mathsf" => return Some("sans-serif"),
+        "mathtt" => return Some("monospace"),
+        _ => {}
+    }
+
+    let mode = Mode::from(group.mode());
+    let mut text = group.symbol_text()?;
+    if ["\\imath", "\\jmath"].contains(&text) {
+        return None;
+    }
+
+    if let Some(replace) = SYMBOLS.get(mode).get(text).and_then(|info| info.replace) {
+        text = replace;
+    }
+
+    let font = FONT_MAP.get(font)?;
+    if get_character_metrics(text, font.font_name, mode).is_some() {
+        return Some(font.variant);
+    }
+
+    None
+}
+
+/// Check for <mi>.</mi> which is how a dot renders in MathML,
+/// or <mo separator="true" lspace="0em" rspace="0em">,</mo>
+/// which is how a braced comma {,} renders in MathML
+fn is_number_punctuation(group: &MathNode) -> bool {
+    let only_text = |text: &str| matches!(group.children.as_slice(), [MathDomNode::Text(child)] if child.text == text);
+    match group.type_ {
+        MathNodeType::Mi => only_text("."),
+        MathNodeType::Mo => {
+            group.get_attribute("separator") == Some("true")
+                && group.get_attribute("lspace") == Some("0em")
+                && group.get_attribute("rspace") == Some("0em")
+                && only_text(",")
+        }
+        _ => false,
+    }
+}
+
+/// Takes a list of nodes, builds them, and returns a list of the generated
+/// MathML nodes.  Also combine consecutive <mtext> outputs into a single
+/// <mtext> tag.
+pub fn build_expression(
+    expression: &[AnyParseNode],
+    options: &Options,
+    is_ordgroup: bool,
+) -> Vec<MathDomNode> {
+    if let [group] = expression {
+        let mut group = build_group(Some(group), options);
+        if let MathDomNode::Math(node) = &mut group {
+            if is_ordgroup && node.type_ == MathNodeType::Mo {
+                // When TeX writers want to suppress spacing on an operator,
+                // they often put the operator by itself inside braces.
+                node.set_attribute("lspace", "0em");
+                node.set_attribute("rspace", "0em");
+            }
+        }
+        return vec![group];
+    }
+
+    let mut groups: Vec<MathDomNode> = vec![];
+    for group in expression {
+        let mut group = build_group(Some(group), options);
+        if let (MathDomNode::Math(node), Some(MathDomNode::Math(last_group))) =
+            (&mut group, groups.last_mut())
+        {
+            // Concatenate adjacent <mtext>s
+            if node.type_ == MathNodeType::Mtext
+                && last_group.type_ == MathNodeType::Mtext
+                && node.get_attribute("mathvariant") == last_group.get_attribute("mathvariant")
+            {
+                last_group.children.append(&mut node.children);
+                continue;
+            // Concatenate adjacent <mn>s
+            } else if node.type_ == MathNodeType::Mn && last_group.type_ == MathNodeType::Mn {
+                last_group.children.append(&mut node.children);
+                continue;
+            // Concatenate <mn>...</mn> followed by <mi>.</mi>
+            } else if is_number_punctuation(node) && last_group.type_ == MathNodeType::Mn {
+                last_group.children.append(&mut node.children);
+                continue;
+            // Concatenate <mi>.</mi> followed by <mn>...</mn>
+            } else if node.type_ == MathNodeType::Mn && is_number_punctuation(last_group) {
+                node.children = [
+                    std::mem::take(&mut last_group.children),
+                    std::mem::take(&mut node.children),
+                ]
+                .concat();
+                groups.pop();
+            // Put preceding <mn>...</mn> or <mi>.</mi> inside base of
+            // <msup><mn>...base...</mn>...exponent...</msup> (or <msub>)
+            } else if matches!(node.type_, MathNodeType::Msup | MathNodeType::Msub)
+                && !node.children.is_empty()
+                && (last_group.type_ == MathNodeType::Mn || is_number_punctuation(last_group))
+            {
+                if let MathDomNode::Math(base) = &mut node.children[0] {
+                    if base.type_ == MathNodeType::Mn {
+                        base.children = [
+                            std::mem::take(&mut last_group.children),
+                            std::mem::take(&mut base.children),
+                        ]
+                        .concat();
+                        groups.pop();
+                    }
+                }
+            // \not
+            } else if last_group.type_ == MathNodeType::Mi
+                && matches!(last_group.children.as_slice(), [MathDomNode::Text(last_child)] if last_child.text == "\u{338}")
+                && matches!(
+                    node.type_,
+                    MathNodeType::Mo | MathNodeType::Mi | MathNodeType::Mn
+                )
+            {
+                if let Some(MathDomNode::Text(child)) = node.children.first_mut() {
+                    if let Some(first) = child.text.chars().next() {
+                        // Overlay with combining character long solidus
+                        child.text = format!("{}\u{338}{}", first, &child.text[first.len_utf8()..]);
+                        groups.pop();
+                    }
+                }
+            }
+        }
+        groups.push(group);
+    }
+    groups
+}
+
+/// Equivalent to build_expression, but wraps the elements in an <mrow>
+/// if there's more than one.  Returns a single node instead of an array.
+pub fn build_expression_row(
+    expression: &[AnyParseNode],
+    options: &Options,
+    is_ordgroup: bool,
+) -> MathDomNode {
+    make_row(build_expression(expression, options, is_ordgroup))
+}
+
+/// Takes a group from the parser and calls the appropriate group builder
+/// function on it to produce a MathML node.
+pub fn build_group(group: Option<&AnyParseNode>, options: &Options) -> MathDomNode {
+    let Some(group) = group else {
+        return MathNode::new(MathNodeType::Mrow, vec![], vec![]).into();
+    };
+
+    match functions::MATHML_GROUP_BUILDERS.get(group.type_()) {
+        // Call the group builder function
+        Some(group_builder) => group_builder(group, options),
+        None => panic!("Got group of unknown type: '{}'", group.type_()),
+    }
+}
+
+/// Takes a full parse tree and settings and builds a MathML representation of
+/// it. In particular, we put the elements from building the parse tree into a
+/// <semantics> tag so we can also include that TeX source as an annotation.
+///
+/// Note that we actually return a domTree element with a `<math>` inside it so
+/// we can do appropriate styling.
+pub fn build_math_ml(
+    tree: &[AnyParseNode],
+    tex_expression: &str,
+    options: &Options,
+    is_display_mode: bool,
+    for_mathml_only: bool,
+) -> DomSpan {
+    let mut expression = build_expression(tree, options, false);
+
+    // TODO: Make a pass thru the MathML similar to build_html's traversal of
+    // non-space nodes and add spacing nodes. This is necessary only adjacent to
+    // math operators like \sin or \lim or to subsup elements that contain math
+    // operators. MathML takes care of the other spacing issues.
+
+    // Wrap up the expression in an mrow so it is presented in the semantics
+    // tag correctly, unless it's a single <mrow> or <mtable>.
+    let wrapper = match expression.as_slice() {
+        [MathDomNode::Math(node)]
+            if matches!(node.type_, MathNodeType::Mrow | MathNodeType::Mtable) =>
+        {
+            expression.remove(0)
+        }
+        _ => MathNode::new(MathNodeType::Mrow, expression, vec![]).into(),
+    };
+
+    // Build a TeX annotation of the source
+    let mut annotation = MathNode::new(
+        MathNodeType::Annotation,
+        vec![TextNode::new(tex_expression).into()],
+        vec![],
+    );
+    annotation.set_attribute("encoding", "application/x-tex");
+
+    let semantics = MathNode::new(
+        MathNodeType::Semantics,
+        vec![wrapper, annotation.into()],
+        vec![],
+    );
+
+    let mut math = MathNode::new(MathNodeType::Math, vec![semantics.into()], vec![]);
+    math.set_attribute("xmlns", "http://www.w3.org/1998/Math/MathML");
+    if is_display_mode {
+        math.set_attribute("display", "block");
+    }
+
+    // You can't style <math> nodes, so we wrap the node in a span.
+    let wrapper_class = if for_mathml_only {
+        "katex"
+    } else {
+        "katex-mathml"
+    };
+    make_span(
+        vec![wrapper_class.to_string()],
+        vec![VirtualNode::MathMl(math)],
+        None,
+        None,
+    )
+}
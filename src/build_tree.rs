@@ -1,7 +1,7 @@
 use super::*;
 
 fn options_from_settings(settings: &Settings) -> Options {
-    Options {
+    Options::new(OptionsData {
         style: if settings.display_mode {
             Style::DISPLAY
         } else {
@@ -9,19 +9,22 @@ fn options_from_settings(settings: &Settings) -> Options {
         },
         max_size: settings.max_size,
         min_rule_thickness: settings.min_rule_thickness,
-    }
+        leqno: settings.leqno,
+        fleqn: settings.fleqn,
+        ..Default::default()
+    })
 }
 
 fn display_wrap(node: DomSpan, settings: &Settings) -> DomSpan {
     if settings.display_mode {
-        let mut classes = vec!["katex-display"];
+        let mut classes = vec!["katex-display".to_string()];
         if settings.leqno {
-            classes.push("leqno");
+            classes.push("leqno".to_string());
         }
         if settings.fleqn {
-            classes.push("fleqn");
+            classes.push("fleqn".to_string());
         }
-        let node = make_span(&classes, vec![node]);
+        let node = make_span(classes, vec![node.into()], None, None);
         return node;
     }
     node
@@ -29,25 +32,54 @@ fn display_wrap(node: DomSpan, settings: &Settings) -> DomSpan {
 
 pub fn build_tree(tree: Vec<AnyParseNode>, expression: &str, settings: &Settings) -> DomSpan {
     let options = options_from_settings(settings);
-    let katex_node: DomSpan;
-
-    if settings.output == "mathml" {
-        return build_math_ml(&tree, expression, &options, settings.display_mode, true);
-    } else if settings.output == "html" {
-        let html_node = build_html(&tree, &options);
-        katex_node = make_span(&["katex"], vec![html_node]);
-    } else {
-        let mathml_node = build_math_ml(&tree, expression, &options, settings.display_mode, false);
-        let html_node = build_html(&tree, &options);
-        katex_node = make_span(&["katex"], vec![mathml_node, html_node]);
-    }
+    let katex_node = match settings.output {
+        OutputFormat::Mathml => {
+            return build_mathml::build_math_ml(
+                &tree,
+                expression,
+                &options,
+                settings.display_mode,
+                true,
+            );
+        }
+        OutputFormat::Html => {
+            let html_node = build_html::build_html(&tree, &options);
+            make_span(
+                vec!["katex".to_string()],
+                vec![html_node.into()],
+                None,
+                None,
+            )
+        }
+        OutputFormat::HtmlAndMathml => {
+            let mathml_node = build_mathml::build_math_ml(
+                &tree,
+                expression,
+                &options,
+                settings.display_mode,
+                false,
+            );
+            let html_node = build_html::build_html(&tree, &options);
+            make_span(
+                vec!["katex".to_string()],
+                vec![mathml_node.into(), html_node.into()],
+                None,
+                None,
+            )
+        }
+    };
 
     display_wrap(katex_node, settings)
 }
 
-pub fn build_html_tree(tree: Vec<AnyParseNode>, expression: &str, settings: &Settings) -> DomSpan {
+pub fn build_html_tree(tree: Vec<AnyParseNode>, _expression: &str, settings: &Settings) -> DomSpan {
     let options = options_from_settings(settings);
-    let html_node = build_html(&tree, &options);
-    let katex_node = make_span(&["katex"], vec![html_node]);
+    let html_node = build_html::build_html(&tree, &options);
+    let katex_node = make_span(
+        vec!["katex".to_string()],
+        vec![html_node.into()],
+        None,
+        None,
+    );
     display_wrap(katex_node, settings)
 }
@@ -0,0 +1,211 @@
+//! The types and registration helpers shared by everything in `functions/`.
+//!
+//! Each function module defines the commands it handles with
+//! `FunctionRegistry::define_function`, giving their argument properties, the
+//! handler that turns the parsed arguments into a parse node, and the builders
+//! that turn that parse node type into HTML and MathML.
+use super::*;
+
+/// The kinds of arguments a function can take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgType {
+    /// An argument parsed in the given mode.
+    Mode(Mode),
+    /// An argument parsed in the current mode.
+    Original,
+    /// A color name or hex code, parsed into a `color-token` node.
+    Color,
+    /// An argument parsed in text mode, wrapped in a text-style `styling`.
+    Hbox,
+    /// The raw text of a braced argument.
+    Raw,
+    /// A TeX size such as `1.5em`, parsed into a `size` node.
+    Size,
+    /// A URL, read verbatim into a `url` node.
+    Url,
+    /// A single token or group, as in TeX's undelimited macro arguments.
+    Primitive,
+}
+
+/// The context passed to a function handler.
+pub struct FunctionContext<'p, 'a> {
+    pub func_name: String,
+    pub parser: &'p mut Parser<'a>,
+    pub token: Option<Token>,
+    pub break_on_token_text: Option<String>,
+}
+
+/// Turns the parsed arguments of a function into a parse node.
+pub type FunctionHandler = fn(
+    context: FunctionContext,
+    args: Vec<AnyParseNode>,
+    opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError>;
+
+/// Builds the HTML for a parse node type.
+pub type HtmlBuilder = fn(group: &AnyParseNode, options: &Options) -> VirtualNode;
+
+/// Builds the MathML for a parse node type.
+pub type MathMlBuilder = fn(group: &AnyParseNode, options: &Options) -> MathDomNode;
+
+pub struct FunctionPropSpec {
+    /// The number of arguments the function takes.
+    pub num_args: usize,
+    /// An array corresponding to each argument of the function, giving the
+    /// type of argument that should be parsed. Its length should be equal
+    /// to `num_optional_args + num_args`, and types for optional arguments
+    /// should appear before types for mandatory arguments.
+    pub arg_types: Option<Vec<ArgType>>,
+    /// Whether it expands to a single token or a braced group of tokens.
+    /// If it's grouped, it can be used as an argument to primitive commands,
+    /// such as \sqrt (without the optional argument) and super/subscript.
+    pub allowed_in_argument: bool,
+    /// Whether or not the function is allowed inside text mode.
+    pub allowed_in_text: bool,
+    /// Whether or not the function is allowed inside math mode.
+    pub allowed_in_math: bool,
+    /// The number of optional arguments the function should parse. If the
+    /// optional arguments aren't found, `None` will be passed to the handler
+    /// in their place.
+    pub num_optional_args: usize,
+    /// Must be true if the function is an infix operator.
+    pub infix: bool,
+    /// Whether or not the function is a TeX primitive.
+    pub primitive: bool,
+}
+
+impl Default for FunctionPropSpec {
+    fn default() -> Self {
+        FunctionPropSpec {
+            num_args: 0,
+            arg_types: None,
+            allowed_in_argument: false,
+            allowed_in_text: false,
+            allowed_in_math: true,
+            num_optional_args: 0,
+            infix: false,
+            primitive: false,
+        }
+    }
+}
+
+/// The full description of a function, as given to `define_function`.
+pub struct FunctionDefSpec {
+    /// Unique string to differentiate parse nodes.
+    /// Also determines the type of the value returned by `handler`.
+    pub node_type: &'static str,
+    /// The first argument to define_function is a single name or a list of
+    /// names. All functions named in such a list will share a single
+    /// implementation.
+    pub names: &'static [&'static str],
+    /// Properties that control how the functions are parsed.
+    pub props: FunctionPropSpec,
+    /// The handler is called to handle these functions and their arguments
+    /// and returns a parse node.
+    pub handler: Option<FunctionHandler>,
+    /// This function returns an object representing the DOM structure to be
+    /// created when rendering the defined LaTeX function.
+    /// This should not modify the `ParseNode`.
+    pub html_builder: Option<HtmlBuilder>,
+    /// This function returns an object representing the MathML structure to be
+    /// created when rendering the defined LaTeX function.
+    /// This should not modify the `ParseNode`.
+    pub mathml_builder: Option<MathMlBuilder>,
+}
+
+/// Final function spec for use at parse time.
+/// This is almost identical to `FunctionPropSpec`, except it
+/// 1. includes the function handler, and
+/// 2. requires all arguments except arg_types.
+pub struct FunctionSpec {
+    pub node_type: &'static str,
+    pub num_args: usize,
+    pub arg_types: Option<Vec<ArgType>>,
+    pub allowed_in_argument: bool,
+    pub allowed_in_text: bool,
+    pub allowed_in_math: bool,
+    pub num_optional_args: usize,
+    pub infix: bool,
+    pub primitive: bool,
+    pub handler: Option<FunctionHandler>,
+}
+
+/// All registered functions, and the builders for their parse node types.
+#[derive(Default)]
+pub struct FunctionRegistry {
+    pub functions: HashMap<&'static str, FunctionSpec>,
+    pub html_group_builders: HashMap<&'static str, HtmlBuilder>,
+    pub mathml_group_builders: HashMap<&'static str, MathMlBuilder>,
+}
+
+impl FunctionRegistry {
+    pub fn define_function(&mut self, spec: FunctionDefSpec) {
+        let FunctionDefSpec {
+            node_type,
+            names,
+            props,
+            handler,
+            html_builder,
+            mathml_builder,
+        } = spec;
+        for name in names {
+            self.functions.insert(
+                name,
+                FunctionSpec {
+                    node_type,
+                    num_args: props.num_args,
+                    arg_types: props.arg_types.clone(),
+                    allowed_in_argument: props.allowed_in_argument,
+                    allowed_in_text: props.allowed_in_text,
+                    allowed_in_math: props.allowed_in_math,
+                    num_optional_args: props.num_optional_args,
+                    infix: props.infix,
+                    primitive: props.primitive,
+                    handler,
+                },
+            );
+        }
+        if let Some(html_builder) = html_builder {
+            self.html_group_builders.insert(node_type, html_builder);
+        }
+        if let Some(mathml_builder) = mathml_builder {
+            self.mathml_group_builders.insert(node_type, mathml_builder);
+        }
+    }
+
+    /// Use this to register only the HTML and MathML builders for a function
+    /// (e.g. if the function's ParseNode is generated in Parser.js rather than
+    /// via a stand-alone handler provided to define_function).
+    pub fn define_function_builders(
+        &mut self,
+        node_type: &'static str,
+        html_builder: Option<HtmlBuilder>,
+        mathml_builder: Option<MathMlBuilder>,
+    ) {
+        self.define_function(FunctionDefSpec {
+            node_type,
+            names: &[],
+            props: FunctionPropSpec::default(),
+            handler: None,
+            html_builder,
+            mathml_builder,
+        });
+    }
+}
+
+/// Since the corresponding buildHTML/buildMathML function expects a
+/// list of elements, we normalize for different kinds of arguments
+pub fn ordargument(arg: AnyParseNode) -> Vec<AnyParseNode> {
+    match arg {
+        AnyParseNode::OrdGroup(group) => group.body,
+        arg => vec![arg],
+    }
+}
+
+/// Unwrap an ordgroup holding a single element.
+pub fn normalize_argument(arg: AnyParseNode) -> AnyParseNode {
+    match arg {
+        AnyParseNode::OrdGroup(mut group) if group.body.len() == 1 => group.body.remove(0),
+        arg => arg,
+    }
+}
@@ -0,0 +1,58 @@
+//! Types for macro definitions, shared by the built-in macros in `macros.rs`,
+//! user-supplied macros in `Settings.macros`, and the `MacroExpander`.
+use super::*;
+
+/// The expansion of a macro: its body tokens (in reverse order, to fit in
+/// with the expander's stack) and the arguments it takes.
+#[derive(Clone, Debug)]
+pub struct MacroExpansion {
+    pub tokens: Vec<Token>,
+    pub num_args: usize,
+    /// Delimiters before the first argument and after each argument, as
+    /// in `\def\foo#1.#2;{...}`.
+    pub delimiters: Option<Vec<Vec<String>>>,
+    /// Used in \let; the macro is not expanded by `expand_tokens`.
+    pub unexpandable: bool,
+}
+
+/// A macro implemented in Rust. It receives the `MacroExpander` as context,
+/// so it can look ahead, consume arguments or check the mode, and returns
+/// the expansion to use.
+pub type MacroFunction = fn(&mut MacroExpander) -> Result<MacroDefinition, ParseError>;
+
+/// The definition of a macro.
+#[derive(Clone, Debug)]
+pub enum MacroDefinition {
+    /// A macro body in TeX source; `#1`…`#9` refer to arguments.
+    Text(String),
+    Expansion(MacroExpansion),
+    Function(MacroFunction),
+}
+
+impl From<&str> for MacroDefinition {
+    fn from(body: &str) -> Self {
+        MacroDefinition::Text(body.to_string())
+    }
+}
+
+impl From<String> for MacroDefinition {
+    fn from(body: String) -> Self {
+        MacroDefinition::Text(body)
+    }
+}
+
+impl From<Vec<Token>> for MacroDefinition {
+    /// An expansion to the given tokens (in reverse order), taking no
+    /// arguments.
+    fn from(tokens: Vec<Token>) -> Self {
+        MacroDefinition::Expansion(MacroExpansion {
+            tokens,
+            num_args: 0,
+            delimiters: None,
+            unexpandable: false,
+        })
+    }
+}
+
+/// The table of built-in macros.
+pub type BuiltinMacros = HashMap<&'static str, MacroDefinition>;
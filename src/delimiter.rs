@@ -0,0 +1,872 @@
+//! This file deals with creating delimiters. When people write `\left(` or
+//! `\binom`, TeX chooses one of a few sizes of the delimiter glyph, or, if the
+//! delimiter has to be taller than all of them, builds it out of pieces.
+//!
+//! There are three kinds of delimiters:
+//!  - delimiters that only come in the small (scaled) sizes and Size1 to
+//!    Size4, like `\langle`;
+//!  - delimiters that are stacked from pieces once even the small sizes are
+//!    too small, like `|`;
+//!  - delimiters that are stacked once Size4 is too small, like `(`.
+//!
+//! `custom_sized_delim` picks the smallest delimiter of the right kind that is
+//! at least as tall as requested.
+use super::*;
+
+/// Get the metrics for a given symbol and font, after transformation (i.e.
+/// after following replacement from symbols.rs)
+fn get_metrics(symbol: &str, font: &str, mode: Mode) -> CharacterMetrics {
+    let replace = MATH_SYMBOLS.get(symbol).and_then(|info| info.replace);
+    get_character_metrics(replace.unwrap_or(symbol), font, mode).unwrap_or_else(|| {
+        panic!("Unsupported symbol {} and font size {}.", symbol, font)
+    })
+}
+
+/// Puts a delimiter span in a given style, and adds appropriate height, depth,
+/// and maxFontSizes.
+fn style_wrap(
+    delim: VirtualNode,
+    to_style: Style,
+    options: &Options,
+    classes: Vec<String>,
+) -> DomSpan {
+    let new_options = options.having_base_style(Some(to_style));
+
+    let mut span = make_span(
+        [classes, new_options.sizing_classes(options)].concat(),
+        vec![delim],
+        Some(options),
+        None,
+    );
+    let delim_size_multiplier = new_options.size_multiplier / options.size_multiplier;
+    span.height *= delim_size_multiplier;
+    span.depth *= delim_size_multiplier;
+    span.max_font_size = new_options.size_multiplier;
+
+    span
+}
+
+fn center_span(span: &mut DomSpan, options: &Options, style: Style) {
+    let new_options = options.having_base_style(Some(style));
+    let shift = (1.0 - options.size_multiplier / new_options.size_multiplier)
+        * options.font_metrics().axis_height;
+
+    span.classes.push("delimcenter".to_string());
+    span.style.insert("top".to_string(), make_em(shift));
+    span.height -= shift;
+    span.depth += shift;
+}
+
+/// Makes a small delimiter. This is a delimiter that comes in the Main-Regular
+/// font, but is restyled to either be in textstyle, scriptstyle, or
+/// scriptscriptstyle.
+fn make_small_delim(
+    delim: &str,
+    style: Style,
+    center: bool,
+    options: &Options,
+    mode: Mode,
+    classes: Vec<String>,
+) -> DomSpan {
+    let text = make_symbol(delim, "Main-Regular", mode, Some(options), vec![]);
+    let mut span = style_wrap(text.into(), style, options, classes);
+    if center {
+        center_span(&mut span, options, style);
+    }
+    span
+}
+
+/// Builds a symbol in the given font size (note size is an integer)
+fn mathrm_size(value: &str, size: usize, mode: Mode, options: &Options) -> SymbolNode {
+    make_symbol(
+        value,
+        &format!("Size{}-Regular", size),
+        mode,
+        Some(options),
+        vec![],
+    )
+}
+
+/// Makes a large delimiter. This is a delimiter that comes in the Size1, Size2,
+/// Size3, or Size4 fonts. It is always rendered in textstyle.
+fn make_large_delim(
+    delim: &str,
+    size: usize,
+    center: bool,
+    options: &Options,
+    mode: Mode,
+    classes: Vec<String>,
+) -> DomSpan {
+    let inner = mathrm_size(delim, size, mode, options);
+    let delimsizing = make_span(
+        vec!["delimsizing".to_string(), format!("size{}", size)],
+        vec![inner.into()],
+        Some(options),
+        None,
+    );
+    let mut span = style_wrap(delimsizing.into(), Style::TEXT, options, classes);
+    if center {
+        center_span(&mut span, options, Style::TEXT);
+    }
+    span
+}
+
+/// Make a span from a font glyph with the given offset and in the given font.
+/// This is used in make_stacked_delim to make the stacking pieces for the
+/// delimiter.
+fn make_glyph_span(symbol: &str, font: &str, mode: Mode) -> VListChild {
+    // Apply the correct CSS class to choose the right font.
+    let size_class = if font == "Size1-Regular" {
+        "delim-size1"
+    } else {
+        "delim-size4"
+    };
+
+    let corner = make_span(
+        vec!["delimsizinginner".to_string(), size_class.to_string()],
+        vec![make_span(
+            vec![],
+            vec![make_symbol(symbol, font, mode, None, vec![]).into()],
+            None,
+            None,
+        )
+        .into()],
+        None,
+        None,
+    );
+
+    VListElem::new(corner).into()
+}
+
+/// Makes the repeated middle piece of a stacked delimiter, drawn as an SVG of
+/// the given height.
+fn make_inner(ch: &str, height: f64, options: &Options) -> VListChild {
+    // Define width by the same method used to set the width of the top
+    // and bottom glyphs.
+    let code = ch.chars().next().map_or(0, |c| c as u32);
+    let width = ["Size4-Regular", "Size1-Regular"]
+        .iter()
+        .find_map(|font| METRIC_MAP.get(font).and_then(|metrics| metrics.get(&code)))
+        .map_or(0.0, |metrics| metrics[4]);
+    let path = PathNode::new("inner", Some(inner_path(ch, (1000.0 * height).round())));
+    let svg_node = SvgNode::new(
+        vec![SvgChildNode::SvgPathNode(path)],
+        HashMap::from([
+            ("width".to_string(), make_em(width)),
+            ("height".to_string(), make_em(height)),
+            // Override CSS rule `.katex svg { width: 100% }`
+            ("style".to_string(), format!("width:{}", make_em(width))),
+            (
+                "viewBox".to_string(),
+                format!("0 0 {} {}", 1000.0 * width, (1000.0 * height).round()),
+            ),
+            ("preserveAspectRatio".to_string(), "xMinYMin".to_string()),
+        ]),
+    );
+    let mut span = make_svg_span(vec![], vec![svg_node.into()], Some(options), None);
+    span.height = height;
+    span.style.insert("height".to_string(), make_em(height));
+    span.style.insert("width".to_string(), make_em(width));
+    VListElem::new(span).into()
+}
+
+// Helpers for make_stacked_delim
+const LAP_IN_EMS: f64 = 0.008;
+const VERTS: &[&str] = &["|", "\\lvert", "\\rvert", "\\vert"];
+const DOUBLE_VERTS: &[&str] = &["\\|", "\\lVert", "\\rVert", "\\Vert"];
+
+/// Make a stacked delimiter out of a given delimiter, with the total height at
+/// least `height_total`. This routine is mentioned on page 442 of the TeXbook.
+fn make_stacked_delim(
+    delim: &str,
+    height_total: f64,
+    center: bool,
+    options: &Options,
+    mode: Mode,
+    classes: Vec<String>,
+) -> DomSpan {
+    // There are four parts, the top, an optional middle, a repeated part, and a
+    // bottom.
+    let mut top = delim;
+    let mut middle = None;
+    let mut repeat = delim;
+    let mut bottom = delim;
+    let mut svg_label = None;
+    let mut view_box_width = 0.0;
+    // Also keep track of what font the delimiters are in
+    let mut font = "Size1-Regular";
+
+    // We set the parts and font based on the symbol. Note that we use
+    // '⏐' instead of '|' and '‖' instead of '\\|' for the
+    // repeats of the arrows
+    match delim {
+        "\\uparrow" => {
+            repeat = "\u{23d0}";
+            bottom = "\u{23d0}";
+        }
+        "\\Uparrow" => {
+            repeat = "\u{2016}";
+            bottom = "\u{2016}";
+        }
+        "\\downarrow" => {
+            top = "\u{23d0}";
+            repeat = "\u{23d0}";
+        }
+        "\\Downarrow" => {
+            top = "\u{2016}";
+            repeat = "\u{2016}";
+        }
+        "\\updownarrow" => {
+            top = "\\uparrow";
+            repeat = "\u{23d0}";
+            bottom = "\\downarrow";
+        }
+        "\\Updownarrow" => {
+            top = "\\Uparrow";
+            repeat = "\u{2016}";
+            bottom = "\\Downarrow";
+        }
+        _ if VERTS.contains(&delim) => {
+            repeat = "\u{2223}";
+            svg_label = Some(TallDelim::Vert);
+            view_box_width = 333.0;
+        }
+        _ if DOUBLE_VERTS.contains(&delim) => {
+            repeat = "\u{2225}";
+            svg_label = Some(TallDelim::DoubleVert);
+            view_box_width = 556.0;
+        }
+        "[" | "\\lbrack" => {
+            top = "\u{23a1}";
+            repeat = "\u{23a2}";
+            bottom = "\u{23a3}";
+            font = "Size4-Regular";
+            svg_label = Some(TallDelim::Lbrack);
+            view_box_width = 667.0;
+        }
+        "]" | "\\rbrack" => {
+            top = "\u{23a4}";
+            repeat = "\u{23a5}";
+            bottom = "\u{23a6}";
+            font = "Size4-Regular";
+            svg_label = Some(TallDelim::Rbrack);
+            view_box_width = 667.0;
+        }
+        "\\lfloor" | "\u{230a}" => {
+            top = "\u{23a2}";
+            repeat = "\u{23a2}";
+            bottom = "\u{23a3}";
+            font = "Size4-Regular";
+            svg_label = Some(TallDelim::Lfloor);
+            view_box_width = 667.0;
+        }
+        "\\lceil" | "\u{2308}" => {
+            top = "\u{23a1}";
+            repeat = "\u{23a2}";
+            bottom = "\u{23a2}";
+            font = "Size4-Regular";
+            svg_label = Some(TallDelim::Lceil);
+            view_box_width = 667.0;
+        }
+        "\\rfloor" | "\u{230b}" => {
+            top = "\u{23a5}";
+            repeat = "\u{23a5}";
+            bottom = "\u{23a6}";
+            font = "Size4-Regular";
+            svg_label = Some(TallDelim::Rfloor);
+            view_box_width = 667.0;
+        }
+        "\\rceil" | "\u{2309}" => {
+            top = "\u{23a4}";
+            repeat = "\u{23a5}";
+            bottom = "\u{23a5}";
+            font = "Size4-Regular";
+            svg_label = Some(TallDelim::Rceil);
+            view_box_width = 667.0;
+        }
+        "(" | "\\lparen" => {
+            top = "\u{239b}";
+            repeat = "\u{239c}";
+            bottom = "\u{239d}";
+            font = "Size4-Regular";
+            svg_label = Some(TallDelim::Lparen);
+            view_box_width = 875.0;
+        }
+        ")" | "\\rparen" => {
+            top = "\u{239e}";
+            repeat = "\u{239f}";
+            bottom = "\u{23a0}";
+            font = "Size4-Regular";
+            svg_label = Some(TallDelim::Rparen);
+            view_box_width = 875.0;
+        }
+        "\\{" | "\\lbrace" => {
+            top = "\u{23a7}";
+            middle = Some("\u{23a8}");
+            bottom = "\u{23a9}";
+            repeat = "\u{23aa}";
+            font = "Size4-Regular";
+        }
+        "\\}" | "\\rbrace" => {
+            top = "\u{23ab}";
+            middle = Some("\u{23ac}");
+            bottom = "\u{23ad}";
+            repeat = "\u{23aa}";
+            font = "Size4-Regular";
+        }
+        "\\lgroup" | "\u{27ee}" => {
+            top = "\u{23a7}";
+            bottom = "\u{23a9}";
+            repeat = "\u{23aa}";
+            font = "Size4-Regular";
+        }
+        "\\rgroup" | "\u{27ef}" => {
+            top = "\u{23ab}";
+            bottom = "\u{23ad}";
+            repeat = "\u{23aa}";
+            font = "Size4-Regular";
+        }
+        "\\lmoustache" | "\u{23b0}" => {
+            top = "\u{23a7}";
+            bottom = "\u{23ad}";
+            repeat = "\u{23aa}";
+            font = "Size4-Regular";
+        }
+        "\\rmoustache" | "\u{23b1}" => {
+            top = "\u{23ab}";
+            bottom = "\u{23a9}";
+            repeat = "\u{23aa}";
+            font = "Size4-Regular";
+        }
+        _ => {}
+    }
+
+    // Get the metrics of the four sections
+    let top_metrics = get_metrics(top, font, mode);
+    let top_height_total = top_metrics.height + top_metrics.depth;
+    let repeat_metrics = get_metrics(repeat, font, mode);
+    let repeat_height_total = repeat_metrics.height + repeat_metrics.depth;
+    let bottom_metrics = get_metrics(bottom, font, mode);
+    let bottom_height_total = bottom_metrics.height + bottom_metrics.depth;
+    let mut middle_height_total = 0.0;
+    let mut middle_factor = 1.0;
+    if let Some(middle) = middle {
+        let middle_metrics = get_metrics(middle, font, mode);
+        middle_height_total = middle_metrics.height + middle_metrics.depth;
+        middle_factor = 2.0; // repeat symmetrically above and below middle
+    }
+
+    // Calcuate the minimal height that the delimiter can have.
+    // It is at least the size of the top, bottom, and optional middle combined.
+    let min_height = top_height_total + bottom_height_total + middle_height_total;
+
+    // Compute the number of copies of the repeat symbol we will need
+    // A repeat piece with no height could never make up the difference.
+    let repeat_count = if repeat_height_total > 0.0 {
+        ((height_total - min_height) / (middle_factor * repeat_height_total))
+            .ceil()
+            .max(0.0)
+    } else {
+        0.0
+    };
+
+    // Compute the total height of the delimiter including all the symbols
+    let real_height_total = min_height + repeat_count * middle_factor * repeat_height_total;
+
+    // The center of the delimiter is placed at the center of the axis. Note
+    // that in this context, "center" means that the delimiter should be
+    // centered around the axis in the current style, while normally it is
+    // centered around the axis in textstyle.
+    let mut axis_height = options.font_metrics().axis_height;
+    if center {
+        axis_height *= options.size_multiplier;
+    }
+    // Calculate the depth
+    let depth = real_height_total / 2.0 - axis_height;
+
+    // Now, we start building the pieces that will go into the vlist
+    // Keep a list of the pieces of the stacked delimiter
+    let mut stack = vec![];
+
+    if let Some(svg_label) = svg_label {
+        // Instead of stacking glyphs, create a single SVG.
+        // This evades browser problems with imprecise positioning of spans.
+        let mid_height = real_height_total - top_height_total - bottom_height_total;
+        let view_box_height = (real_height_total * 1000.0).round();
+        let path_str = tall_delim(svg_label, (mid_height * 1000.0).round());
+        let path = PathNode::new(svg_label.label(), Some(path_str));
+        let width = format!("{:.3}em", view_box_width / 1000.0);
+        let height = format!("{:.3}em", view_box_height / 1000.0);
+        let svg = SvgNode::new(
+            vec![SvgChildNode::SvgPathNode(path)],
+            HashMap::from([
+                ("width".to_string(), width.clone()),
+                ("height".to_string(), height.clone()),
+                (
+                    "viewBox".to_string(),
+                    format!("0 0 {} {}", view_box_width, view_box_height),
+                ),
+            ]),
+        );
+        let mut wrapper = make_svg_span(vec![], vec![svg.into()], Some(options), None);
+        wrapper.height = view_box_height / 1000.0;
+        wrapper.style.insert("width".to_string(), width);
+        wrapper.style.insert("height".to_string(), height);
+        stack.push(VListElem::new(wrapper).into());
+    } else {
+        // Stack glyphs
+        // Start by adding the bottom symbol
+        stack.push(make_glyph_span(bottom, font, mode));
+        stack.push(VListChild::Kern(-LAP_IN_EMS)); // overlap
+
+        match middle {
+            None => {
+                // The middle section will be an SVG. Make it an extra 0.016em tall.
+                // We'll overlap by 0.008em at top and bottom.
+                let inner_height =
+                    real_height_total - top_height_total - bottom_height_total + 2.0 * LAP_IN_EMS;
+                stack.push(make_inner(repeat, inner_height, options));
+            }
+            Some(middle) => {
+                // When there is a middle bit, we need the middle part and two repeated
+                // sections
+                let inner_height = (real_height_total
+                    - top_height_total
+                    - bottom_height_total
+                    - middle_height_total)
+                    / 2.0
+                    + 2.0 * LAP_IN_EMS;
+                stack.push(make_inner(repeat, inner_height, options));
+                // Now insert the middle of the brace.
+                stack.push(VListChild::Kern(-LAP_IN_EMS));
+                stack.push(make_glyph_span(middle, font, mode));
+                stack.push(VListChild::Kern(-LAP_IN_EMS));
+                stack.push(make_inner(repeat, inner_height, options));
+            }
+        }
+
+        // Add the top symbol
+        stack.push(VListChild::Kern(-LAP_IN_EMS));
+        stack.push(make_glyph_span(top, font, mode));
+    }
+
+    // Finally, build the vlist
+    let new_options = options.having_base_style(Some(Style::TEXT));
+    let inner = make_v_list(VListParam::Bottom(depth, stack));
+
+    style_wrap(
+        make_span(
+            vec!["delimsizing".to_string(), "mult".to_string()],
+            vec![inner.into()],
+            Some(&new_options),
+            None,
+        )
+        .into(),
+        Style::TEXT,
+        options,
+        classes,
+    )
+}
+
+// All surds have 0.08em padding above the vinculum inside the SVG.
+// That keeps browser span height rounding error from pinching the line.
+const VB_PAD: f64 = 80.0; // padding above the surd, measured inside the viewBox.
+const EM_PAD: f64 = 0.08; // padding, in ems, measured in the document.
+
+fn sqrt_svg(
+    sqrt_name: &str,
+    height: f64,
+    view_box_height: f64,
+    extra_vinculum: f64,
+    options: &Options,
+) -> DomSpan {
+    let path = sqrt_path(sqrt_name, extra_vinculum, view_box_height);
+    let path_node = PathNode::new(sqrt_name, Some(path));
+
+    let svg = SvgNode::new(
+        vec![SvgChildNode::SvgPathNode(path_node)],
+        HashMap::from([
+            // Note: 1000:1 ratio of viewBox to document em width.
+            ("width".to_string(), "400em".to_string()),
+            ("height".to_string(), make_em(height)),
+            (
+                "viewBox".to_string(),
+                format!("0 0 400000 {}", view_box_height),
+            ),
+            (
+                "preserveAspectRatio".to_string(),
+                "xMinYMin slice".to_string(),
+            ),
+        ]),
+    );
+
+    make_svg_span(
+        vec!["hide-tail".to_string()],
+        vec![svg.into()],
+        Some(options),
+        None,
+    )
+}
+
+/// A surd image from `make_sqrt_image`, with the thickness of its vinculum
+/// and the horizontal space it takes up before the radicand.
+pub struct SqrtImage {
+    pub span: DomSpan,
+    pub rule_width: f64,
+    pub advance_width: f64,
+}
+
+/// Make a sqrt image of the given height.
+pub fn make_sqrt_image(height: f64, options: &Options) -> SqrtImage {
+    // Define a new_options that removes the effect of size changes such as \Huge.
+    // We don't pick different a height surd for \Huge. For it, we scale up.
+    let new_options = options.having_base_sizing();
+
+    // Pick the desired surd glyph from a sequence of surds.
+    let delim = traverse_sequence(
+        "\\surd",
+        height * new_options.size_multiplier,
+        STACK_LARGE_DELIMITER_SEQUENCE,
+        &new_options,
+    );
+
+    let mut size_multiplier = new_options.size_multiplier; // default
+
+    // The standard sqrt SVGs each have a 0.04em thick vinculum.
+    // If Settings.min_rule_thickness is larger than that, we add extra_vinculum.
+    let extra_vinculum =
+        (options.min_rule_thickness - options.font_metrics().sqrt_rule_thickness).max(0.0);
+
+    // Create a span containing an SVG image of a sqrt symbol.
+    let mut span;
+    let span_height;
+    let tex_height;
+    let view_box_height;
+    let advance_width;
+
+    // We create view_boxes with 80 units of "padding" above each surd.
+    // Then browser rounding error on the parent span height will not
+    // encroach on the ink of the vinculum. But that padding is not
+    // included in the TeX-like `height` used for calculation of
+    // vertical alignment. So tex_height = span.height < span.style.height.
+
+    match delim {
+        DelimType::Small(_) => {
+            // Get an SVG that is derived from glyph U+221A in font KaTeX-Main.
+            // 1000 unit normal glyph height.
+            view_box_height = 1000.0 + 1000.0 * extra_vinculum + VB_PAD;
+            if height < 1.0 {
+                size_multiplier = 1.0; // mimic a \textfont radical
+            } else if height < 1.4 {
+                size_multiplier = 0.7; // mimic a \scriptfont radical
+            }
+            span_height = (1.0 + extra_vinculum + EM_PAD) / size_multiplier;
+            tex_height = (1.00 + extra_vinculum) / size_multiplier;
+            span = sqrt_svg(
+                "sqrtMain",
+                span_height,
+                view_box_height,
+                extra_vinculum,
+                options,
+            );
+            span.style
+                .insert("minWidth".to_string(), "0.853em".to_string());
+            advance_width = 0.833 / size_multiplier; // from the font.
+        }
+        DelimType::Large(size) => {
+            // These SVGs come from fonts: KaTeX_Size1, _Size2, etc.
+            view_box_height = (1000.0 + VB_PAD) * SIZE_TO_MAX_HEIGHT[size];
+            tex_height = (SIZE_TO_MAX_HEIGHT[size] + extra_vinculum) / size_multiplier;
+            span_height = (SIZE_TO_MAX_HEIGHT[size] + extra_vinculum + EM_PAD) / size_multiplier;
+            span = sqrt_svg(
+                &format!("sqrtSize{}", size),
+                span_height,
+                view_box_height,
+                extra_vinculum,
+                options,
+            );
+            span.style
+                .insert("minWidth".to_string(), "1.02em".to_string());
+            advance_width = 1.0 / size_multiplier; // 1.0 from the font.
+        }
+        DelimType::Stack => {
+            // Tall sqrt. In TeX, this would be stacked using multiple glyphs.
+            // We'll use a single SVG to accomplish the same thing.
+            span_height = height + extra_vinculum + EM_PAD;
+            tex_height = height + extra_vinculum;
+            view_box_height = (1000.0 * height + extra_vinculum).floor() + VB_PAD;
+            span = sqrt_svg(
+                "sqrtTall",
+                span_height,
+                view_box_height,
+                extra_vinculum,
+                options,
+            );
+            span.style
+                .insert("minWidth".to_string(), "0.742em".to_string());
+            advance_width = 1.056;
+        }
+    }
+
+    span.height = tex_height;
+    span.style
+        .insert("height".to_string(), make_em(span_height));
+
+    SqrtImage {
+        span,
+        advance_width,
+        // Calculate the actual line width.
+        // This actually should depend on the chosen font -- e.g. \boldmath
+        // should use the thicker surd symbols from e.g. KaTeX_Main-Bold, and
+        // have thicker rules.
+        rule_width: (options.font_metrics().sqrt_rule_thickness + extra_vinculum) * size_multiplier,
+    }
+}
+
+/// The heights of the four large sizes of delimiters, indexed by size.
+pub const SIZE_TO_MAX_HEIGHT: [f64; 5] = [0.0, 1.2, 1.8, 2.4, 3.0];
+
+// There are three kinds of delimiters, delimiters that stack when they become
+// too large
+const STACK_LARGE_DELIMITERS: &[&str] = &[
+    "(", "\\lparen", ")", "\\rparen", "[", "\\lbrack", "]", "\\rbrack", "\\{", "\\lbrace", "\\}",
+    "\\rbrace", "\\lfloor", "\\rfloor", "\u{230a}", "\u{230b}", "\\lceil", "\\rceil", "\u{2308}",
+    "\u{2309}", "\\surd",
+];
+
+// delimiters that always stack
+const STACK_ALWAYS_DELIMITERS: &[&str] = &[
+    "\\uparrow",
+    "\\downarrow",
+    "\\updownarrow",
+    "\\Uparrow",
+    "\\Downarrow",
+    "\\Updownarrow",
+    "|",
+    "\\|",
+    "\\vert",
+    "\\Vert",
+    "\\lvert",
+    "\\rvert",
+    "\\lVert",
+    "\\rVert",
+    "\\lgroup",
+    "\\rgroup",
+    "\u{27ee}",
+    "\u{27ef}",
+    "\\lmoustache",
+    "\\rmoustache",
+    "\u{23b0}",
+    "\u{23b1}",
+];
+
+// and delimiters that never stack
+const STACK_NEVER_DELIMITERS: &[&str] = &[
+    "<",
+    ">",
+    "\\langle",
+    "\\rangle",
+    "/",
+    "\\backslash",
+    "\\lt",
+    "\\gt",
+];
+
+/// Used to create a delimiter of a specific size, where `size` is 1, 2, 3, or 4.
+pub fn make_sized_delim(
+    delim: &str,
+    size: usize,
+    options: &Options,
+    mode: Mode,
+    classes: Vec<String>,
+) -> DomSpan {
+    // < and > turn into \langle and \rangle in delimiters
+    let delim = match delim {
+        "<" | "\\lt" | "\u{27e8}" => "\\langle",
+        ">" | "\\gt" | "\u{27e9}" => "\\rangle",
+        delim => delim,
+    };
+
+    // Sized delimiters are never centered.
+    if STACK_LARGE_DELIMITERS.contains(&delim) || STACK_NEVER_DELIMITERS.contains(&delim) {
+        make_large_delim(delim, size, false, options, mode, classes)
+    } else if STACK_ALWAYS_DELIMITERS.contains(&delim) {
+        make_stacked_delim(
+            delim,
+            SIZE_TO_MAX_HEIGHT[size],
+            false,
+            options,
+            mode,
+            classes,
+        )
+    } else {
+        panic!("Illegal delimiter: '{}'", delim);
+    }
+}
+
+/// An entry in one of the delimiter sequences below.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DelimType {
+    Small(Style),
+    Large(usize),
+    Stack,
+}
+
+// Delimiters that never stack try small delimiters and large delimiters only
+const STACK_NEVER_DELIMITER_SEQUENCE: &[DelimType] = &[
+    DelimType::Small(Style::SCRIPTSCRIPT),
+    DelimType::Small(Style::SCRIPT),
+    DelimType::Small(Style::TEXT),
+    DelimType::Large(1),
+    DelimType::Large(2),
+    DelimType::Large(3),
+    DelimType::Large(4),
+];
+
+// Delimiters that always stack try the small delimiters first, then stack
+const STACK_ALWAYS_DELIMITER_SEQUENCE: &[DelimType] = &[
+    DelimType::Small(Style::SCRIPTSCRIPT),
+    DelimType::Small(Style::SCRIPT),
+    DelimType::Small(Style::TEXT),
+    DelimType::Stack,
+];
+
+// Delimiters that stack when large try the small and then large delimiters, and
+// stack afterwards
+const STACK_LARGE_DELIMITER_SEQUENCE: &[DelimType] = &[
+    DelimType::Small(Style::SCRIPTSCRIPT),
+    DelimType::Small(Style::SCRIPT),
+    DelimType::Small(Style::TEXT),
+    DelimType::Large(1),
+    DelimType::Large(2),
+    DelimType::Large(3),
+    DelimType::Large(4),
+    DelimType::Stack,
+];
+
+/// Get the font used in a delimiter based on what kind of delimiter it is.
+fn delim_type_to_font(type_: DelimType) -> String {
+    match type_ {
+        DelimType::Small(_) => "Main-Regular".to_string(),
+        DelimType::Large(size) => format!("Size{}-Regular", size),
+        DelimType::Stack => "Size4-Regular".to_string(),
+    }
+}
+
+/// Traverse a sequence of types of delimiters to decide what kind of delimiter
+/// should be used to create a delimiter of the given height+depth.
+fn traverse_sequence(
+    delim: &str,
+    height: f64,
+    sequence: &[DelimType],
+    options: &Options,
+) -> DelimType {
+    // Here, we choose the index we should start at in the sequences. In smaller
+    // sizes (which correspond to larger numbers in style.size) we start earlier
+    // in the sequence. Thus, scriptscript starts at index 3-3=0, script starts
+    // at index 3-2=1, text starts at 3-1=2, and display starts at min(2,3-0)=2
+    let start = 2.min(3 - options.style.size);
+    for &type_ in &sequence[start..] {
+        if type_ == DelimType::Stack {
+            // This is always the last delimiter, so we just break the loop now.
+            break;
+        }
+
+        let metrics = get_metrics(delim, &delim_type_to_font(type_), Mode::Math);
+        let mut height_depth = metrics.height + metrics.depth;
+
+        // Small delimiters are scaled down versions of the same font, so we
+        // account for the style change size.
+
+        if let DelimType::Small(style) = type_ {
+            let new_options = options.having_base_style(Some(style));
+            height_depth *= new_options.size_multiplier;
+        }
+
+        // Check if the delimiter at this size works for the given height.
+        if height_depth > height {
+            return type_;
+        }
+    }
+
+    // If we reached the end of the sequence, return the last sequence element.
+    *sequence.last().unwrap()
+}
+
+/// Make a delimiter of a given height+depth, with optional centering. Here, we
+/// traverse the sequences, and create a delimiter that the sequence tells us
+/// to.
+pub fn custom_sized_delim(
+    delim: &str,
+    height: f64,
+    center: bool,
+    options: &Options,
+    mode: Mode,
+    classes: Vec<String>,
+) -> DomSpan {
+    // Special cases
+    let delim = match delim {
+        "<" | "\\lt" | "\u{27e8}" => "\\langle",
+        ">" | "\\gt" | "\u{27e9}" => "\\rangle",
+        delim => delim,
+    };
+
+    // Sequences to search through
+    let sequence = if STACK_NEVER_DELIMITERS.contains(&delim) {
+        STACK_NEVER_DELIMITER_SEQUENCE
+    } else if STACK_LARGE_DELIMITERS.contains(&delim) {
+        STACK_LARGE_DELIMITER_SEQUENCE
+    } else {
+        STACK_ALWAYS_DELIMITER_SEQUENCE
+    };
+
+    // Look through the sequence
+    let delim_type = traverse_sequence(delim, height, sequence, options);
+
+    // Get the delimiter from font glyphs.
+    // Depending on the sequence element we decided on, call the
+    // appropriate function.
+    match delim_type {
+        DelimType::Small(style) => make_small_delim(delim, style, center, options, mode, classes),
+        DelimType::Large(size) => make_large_delim(delim, size, center, options, mode, classes),
+        DelimType::Stack => make_stacked_delim(delim, height, center, options, mode, classes),
+    }
+}
+
+/// Make a delimiter for use with `\left` and `\right`, given a height and depth
+/// of an expression that the delimiters surround.
+pub fn make_left_right_delim(
+    delim: &str,
+    height: f64,
+    depth: f64,
+    options: &Options,
+    mode: Mode,
+    classes: Vec<String>,
+) -> DomSpan {
+    // We always center \left/\right delimiters, so the axis is always shifted
+    let axis_height = options.font_metrics().axis_height * options.size_multiplier;
+
+    // Taken from TeX source, tex.web, function make_left_right
+    let delimiter_factor = 901.0;
+    let delimiter_extend = 5.0 / options.font_metrics().pt_per_em;
+
+    let max_dist_from_axis = (height - axis_height).max(depth + axis_height);
+
+    let total_height = f64::max(
+        // In real TeX, calculations are done using integral values which are
+        // 65536 per pt, or 655360 per em. So, the division here truncates in
+        // TeX but doesn't here, producing different results. If we wanted to
+        // exactly match TeX's calculation, we could do
+        //   (655360 * max_dist_from_axis / 500).floor() *
+        //    delimiter_factor / 655360
+        // (To see the difference, compare
+        //    x^{x^{\left(\rule{0.1em}{0.68em}\right)}}
+        // in TeX and KaTeX)
+        max_dist_from_axis / 500.0 * delimiter_factor,
+        2.0 * max_dist_from_axis - delimiter_extend,
+    );
+
+    // Finally, we defer to `custom_sized_delim` with our calculated total
+    // height
+    custom_sized_delim(delim, total_height, true, options, mode, classes)
+}
@@ -1,13 +1,9 @@
 //! These objects store the data about the DOM nodes we create, as well as some
-//! extra data. They can then be transformed into real DOM nodes with the
-//! `to_node` function or HTML markup using `to_markup`. They are useful for both
-//! storing extra properties on the nodes, as well as providing a way to easily
-//! work with the DOM.
+//! extra data. They can then be transformed into HTML markup using
+//! `to_markup`. They are useful for both storing extra properties on the nodes,
+//! as well as providing a way to easily work with the DOM.
 //!
-//! Similar functions for working with MathML nodes exist in mathMLTree.js.
-//!
-//! TODO: refactor `span` and `anchor` into common superclass when
-//! target environments support class inheritance
+//! Similar functions for working with MathML nodes exist in mathml_tree.rs.
 use super::*;
 
 pub type DomSpan = Span;
@@ -18,70 +14,57 @@ pub fn create_class(classes: &[String]) -> String {
     classes
         .iter()
         .filter(|cls| !cls.is_empty())
-        .map(|cls| cls.clone())
+        .cloned()
         .collect::<Vec<_>>()
         .join(" ")
 }
 
-/// Makes an empty DOM node.
+/// Makes the classes and style of an empty DOM node.
 fn init_node(
-    classes: Option<Vec<String>>,
-    options: Option<Options>,
-    style: Option<CssStyle>,
-) -> HtmlDomNode {
-    let classes = classes.unwrap_or_default();
-    let style = style.unwrap_or_default();
-    let mut node = HtmlDomNode {
-        classes,
-        attributes: HashMap::new(),
-        height: 0,
-        depth: 0,
-        max_font_size: 0,
-        style,
-        children: Default::default(),
-    };
-
-    if let Some(opts) = options {
-        if opts.style.is_tight() {
-            node.classes.push("mtight".to_string());
+    mut classes: Vec<String>,
+    options: Option<&Options>,
+    mut style: CssStyle,
+) -> (Vec<String>, CssStyle) {
+    if let Some(options) = options {
+        if options.style.is_tight() {
+            classes.push("mtight".to_string());
         }
-        if let Some(color) = opts.get_color() {
-            node.style.insert("color".to_string(), color);
+        if let Some(color) = options.get_color() {
+            style.insert("color".to_string(), color);
         }
     }
-
-    node
+    (classes, style)
 }
 
-/// Convert into an HTML node
-fn to_node(node: &HtmlDomNode, tag_name: &str) -> String {
+/// Convert into an HTML markup string
+fn to_markup(
+    tag_name: &str,
+    classes: &[String],
+    style: &CssStyle,
+    attributes: &HashMap<String, String>,
+    children: &[VirtualNode],
+) -> String {
     let mut markup = format!("<{}", tag_name);
 
     // Add the class
-    if !node.classes.is_empty() {
-        markup += &format!(" class=\"{}\"", utils::escape(&create_class(&node.classes)));
-    }
-
-    let mut styles = String::new();
-
-    // Add the styles, after hyphenation
-    for (style, value) in &node.style {
-        styles += &format!("{}:{};", utils::hyphenate(style), value);
+    if !classes.is_empty() {
+        markup += &format!(" class=\"{}\"", utils::escape(&create_class(classes)));
     }
 
+    let styles = styles_markup(style);
     if !styles.is_empty() {
         markup += &format!(" style=\"{}\"", utils::escape(&styles));
     }
 
     // Add the attributes
-    for (attr, value) in &node.attributes {
+    for (attr, value) in attributes {
         markup += &format!(" {}=\"{}\"", attr, utils::escape(value));
     }
 
     markup += ">";
 
     // Add the markup of the children, also as markup
-    for child in &node.children {
+    for child in children {
         markup += &child.to_markup();
     }
 
@@ -90,202 +73,511 @@ fn to_node(node: &HtmlDomNode, tag_name: &str) -> String {
     markup
 }
 
-/// Convert into an HTML markup string
-fn to_markup(node: &HtmlDomNode, tag_name: &str) -> String {
-    let mut markup = format!("<{}", tag_name);
-
-    // Add the class
-    if !node.classes.is_empty() {
-        markup += &format!(" class=\"{}\"", utils::escape(&create_class(&node.classes)));
+/// Add the styles, after hyphenation
+fn styles_markup(style: &CssStyle) -> String {
+    let mut styles = String::new();
+    for (style, value) in style {
+        styles += &format!("{}:{};", utils::hyphenate(style), value);
     }
+    styles
+}
 
-    let mut styles = String::new();
+/// Type representing CSS styles
+pub type CssStyle = HashMap<String, String>;
 
-    // Add the styles, after hyphenation
-    for (style, value) in &node.style {
-        styles += &format!("{}:{};", utils::hyphenate(style), value);
+/// Represents a node in the HTML DOM tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VirtualNode {
+    Span(Span),
+    Anchor(Anchor),
+    Img(Img),
+    Symbol(SymbolNode),
+    Svg(SvgNode),
+    Fragment(DocumentFragment),
+    /// A `<math>` element, which `build_math_ml` wraps in a span.
+    MathMl(MathNode),
+}
+
+impl VirtualNode {
+    pub fn to_markup(&self) -> String {
+        match self {
+            VirtualNode::Span(span) => span.to_markup(),
+            VirtualNode::Anchor(anchor) => anchor.to_markup(),
+            VirtualNode::Img(img) => img.to_markup(),
+            VirtualNode::Symbol(symbol) => symbol.to_markup(),
+            VirtualNode::Svg(svg) => svg.to_markup(),
+            VirtualNode::Fragment(fragment) => fragment.to_markup(),
+            VirtualNode::MathMl(math) => math.to_markup(),
+        }
     }
 
-    if !styles.is_empty() {
-        markup += &format!(" style=\"{}\"", utils::escape(&styles));
+    pub fn height(&self) -> f64 {
+        match self {
+            VirtualNode::Span(span) => span.height,
+            VirtualNode::Anchor(anchor) => anchor.height,
+            VirtualNode::Img(img) => img.height,
+            VirtualNode::Symbol(symbol) => symbol.height,
+            VirtualNode::Fragment(fragment) => fragment.height,
+            VirtualNode::Svg(_) | VirtualNode::MathMl(_) => 0.0,
+        }
     }
 
-    // Add the attributes
-    for (attr, value) in &node.attributes {
-        markup += &format!(" {}=\"{}\"", attr, utils::escape(value));
+    pub fn depth(&self) -> f64 {
+        match self {
+            VirtualNode::Span(span) => span.depth,
+            VirtualNode::Anchor(anchor) => anchor.depth,
+            VirtualNode::Img(img) => img.depth,
+            VirtualNode::Symbol(symbol) => symbol.depth,
+            VirtualNode::Fragment(fragment) => fragment.depth,
+            VirtualNode::Svg(_) | VirtualNode::MathMl(_) => 0.0,
+        }
     }
 
-    markup += ">";
+    pub fn max_font_size(&self) -> f64 {
+        match self {
+            VirtualNode::Span(span) => span.max_font_size,
+            VirtualNode::Anchor(anchor) => anchor.max_font_size,
+            VirtualNode::Img(img) => img.max_font_size,
+            VirtualNode::Symbol(symbol) => symbol.max_font_size,
+            VirtualNode::Fragment(fragment) => fragment.max_font_size,
+            VirtualNode::Svg(_) | VirtualNode::MathMl(_) => 0.0,
+        }
+    }
 
-    // Add the markup of the children, also as markup
-    for child in &node.children {
-        markup += &child.to_markup();
+    pub fn set_height(&mut self, height: f64) {
+        match self {
+            VirtualNode::Span(span) => span.height = height,
+            VirtualNode::Anchor(anchor) => anchor.height = height,
+            VirtualNode::Img(img) => img.height = height,
+            VirtualNode::Symbol(symbol) => symbol.height = height,
+            VirtualNode::Fragment(fragment) => fragment.height = height,
+            VirtualNode::Svg(_) | VirtualNode::MathMl(_) => {}
+        }
     }
 
-    markup += &format!("</{}>", tag_name);
+    pub fn set_depth(&mut self, depth: f64) {
+        match self {
+            VirtualNode::Span(span) => span.depth = depth,
+            VirtualNode::Anchor(anchor) => anchor.depth = depth,
+            VirtualNode::Img(img) => img.depth = depth,
+            VirtualNode::Symbol(symbol) => symbol.depth = depth,
+            VirtualNode::Fragment(fragment) => fragment.depth = depth,
+            VirtualNode::Svg(_) | VirtualNode::MathMl(_) => {}
+        }
+    }
 
-    markup
-}
+    pub fn classes(&self) -> &[String] {
+        match self {
+            VirtualNode::Span(span) => &span.classes,
+            VirtualNode::Anchor(anchor) => &anchor.classes,
+            VirtualNode::Img(img) => &img.classes,
+            VirtualNode::Symbol(symbol) => &symbol.classes,
+            VirtualNode::Fragment(fragment) => &fragment.classes,
+            VirtualNode::MathMl(math) => &math.classes,
+            VirtualNode::Svg(_) => &[],
+        }
+    }
 
-/// Type representing CSS styles
-pub type CssStyle = HashMap<String, String>;
+    /// The classes of the node, or `None` for nodes without classes.
+    pub fn classes_mut(&mut self) -> Option<&mut Vec<String>> {
+        match self {
+            VirtualNode::Span(span) => Some(&mut span.classes),
+            VirtualNode::Anchor(anchor) => Some(&mut anchor.classes),
+            VirtualNode::Img(img) => Some(&mut img.classes),
+            VirtualNode::Symbol(symbol) => Some(&mut symbol.classes),
+            VirtualNode::Fragment(fragment) => Some(&mut fragment.classes),
+            VirtualNode::MathMl(math) => Some(&mut math.classes),
+            VirtualNode::Svg(_) => None,
+        }
+    }
 
-/// Represents an HTML DOM node.
-#[derive(Debug, Clone)]
-pub struct HtmlDomNode {
-    pub classes: Vec<String>,
-    pub attributes: HashMap<String, String>,
-    pub height: usize,
-    pub depth: usize,
-    pub max_font_size: usize,
-    pub style: CssStyle,
-    pub children: Vec<VirtualNode>,
-}
+    /// The CSS style of the node, or `None` for nodes without styles.
+    pub fn style_mut(&mut self) -> Option<&mut CssStyle> {
+        match self {
+            VirtualNode::Span(span) => Some(&mut span.style),
+            VirtualNode::Anchor(anchor) => Some(&mut anchor.style),
+            VirtualNode::Img(img) => Some(&mut img.style),
+            VirtualNode::Symbol(symbol) => Some(&mut symbol.style),
+            VirtualNode::Fragment(fragment) => Some(&mut fragment.style),
+            VirtualNode::Svg(_) | VirtualNode::MathMl(_) => None,
+        }
+    }
 
-impl HtmlDomNode {
     /// Checks if the node has a certain class.
     pub fn has_class(&self, class_name: &str) -> bool {
-        self.classes.contains(&class_name.to_string())
+        self.classes().iter().any(|class| class == class_name)
     }
 }
 
-impl PartialEq for HtmlDomNode {
-    fn eq(&self, other: &Self) -> bool {
-        self.classes == other.classes
-            && self.attributes == other.attributes
-            && self.height == other.height
-            && self.depth == other.depth
-            && self.max_font_size == other.max_font_size
-            && self.style == other.style
-            && self.children == other.children
+impl From<Span> for VirtualNode {
+    fn from(span: Span) -> Self {
+        VirtualNode::Span(span)
     }
 }
 
-/// Represents a virtual node in the DOM.
-#[derive(Debug, Clone, PartialEq)]
-pub enum VirtualNode {
-    Span(Span),
-    Anchor(Anchor),
-    Img(Img),
-    Symbol(SymbolNode),
-    Svg(SvgNode),
-    Path(PathNode),
-    Line(LineNode),
+impl From<Anchor> for VirtualNode {
+    fn from(anchor: Anchor) -> Self {
+        VirtualNode::Anchor(anchor)
+    }
 }
 
-impl VirtualNode {
-    pub fn to_node(&self) -> String {
-        match self {
-            VirtualNode::Span(span) => span.to_node(),
-            VirtualNode::Anchor(anchor) => anchor.to_node(),
-            VirtualNode::Img(img) => img.to_node(),
-            VirtualNode::Symbol(symbol) => symbol.to_node(),
-            VirtualNode::Svg(svg) => svg.to_node(),
-            VirtualNode::Path(path) => path.to_node(),
-            VirtualNode::Line(line) => line.to_node(),
-        }
+impl From<Img> for VirtualNode {
+    fn from(img: Img) -> Self {
+        VirtualNode::Img(img)
     }
+}
 
-    pub fn to_markup(&self) -> String {
-        match self {
-            VirtualNode::Span(span) => span.to_markup(),
-            VirtualNode::Anchor(anchor) => anchor.to_markup(),
-            VirtualNode::Img(img) => img.to_markup(),
-            VirtualNode::Symbol(symbol) => symbol.to_markup(),
-            VirtualNode::Svg(svg) => svg.to_markup(),
-            VirtualNode::Path(path) => path.to_markup(),
-            VirtualNode::Line(line) => line.to_markup(),
-        }
+impl From<SymbolNode> for VirtualNode {
+    fn from(symbol: SymbolNode) -> Self {
+        VirtualNode::Symbol(symbol)
     }
 }
 
-/// Represents a span node containing DOM nodes.
-#[derive(Debug, Clone, PartialEq)]
+impl From<SvgNode> for VirtualNode {
+    fn from(svg: SvgNode) -> Self {
+        VirtualNode::Svg(svg)
+    }
+}
+
+impl From<DocumentFragment> for VirtualNode {
+    fn from(fragment: DocumentFragment) -> Self {
+        VirtualNode::Fragment(fragment)
+    }
+}
+
+/// This node represents a span node, with a className, a list of children, and
+/// an inline style. It also contains information about its height, depth, and
+/// maxFontSize.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Span {
     pub children: Vec<VirtualNode>,
     pub attributes: HashMap<String, String>,
     pub classes: Vec<String>,
-    pub height: usize,
-    pub depth: usize,
-    pub width: Option<usize>,
-    pub max_font_size: usize,
+    pub height: f64,
+    pub depth: f64,
+    pub width: Option<f64>,
+    pub max_font_size: f64,
     pub style: CssStyle,
+    /// Set on a `\middle` delimiter, which the enclosing `\left`...`\right`
+    /// rebuilds once the height of its body is known.
+    pub is_middle: Option<Box<IsMiddle>>,
+}
+
+/// The delimiter of a `\middle` and the options in effect where it was used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsMiddle {
+    pub delim: String,
+    pub options: Options,
 }
 
-/* impl<T: VirtualNode> Span<T> {
-    /// Sets an arbitrary attribute on the span.
-    pub fn set_attribute(&mut self, attribute: String, value: String) {
-        self.attributes.insert(attribute, value);
+impl Span {
+    pub fn new(
+        classes: Vec<String>,
+        children: Vec<VirtualNode>,
+        options: Option<&Options>,
+        style: CssStyle,
+    ) -> Self {
+        let (classes, style) = init_node(classes, options, style);
+        Span {
+            children,
+            classes,
+            style,
+            ..Default::default()
+        }
     }
-} */
 
-/// Represents an anchor element with a hyperlink.
-#[derive(Debug, Clone, PartialEq)]
+    /// Sets an arbitrary attribute on the span. Warning: use this wisely. Not
+    /// all browsers support attributes the same, and having too many custom
+    /// attributes is probably bad.
+    pub fn set_attribute(&mut self, attribute: &str, value: &str) {
+        self.attributes
+            .insert(attribute.to_string(), value.to_string());
+    }
+
+    pub fn has_class(&self, class_name: &str) -> bool {
+        self.classes.iter().any(|class| class == class_name)
+    }
+
+    pub fn to_markup(&self) -> String {
+        to_markup(
+            "span",
+            &self.classes,
+            &self.style,
+            &self.attributes,
+            &self.children,
+        )
+    }
+}
+
+/// This node represents an anchor (<a>) element with a hyperlink.  See `span`
+/// for further details.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Anchor {
-    pub children: Vec<HtmlDomNode>,
+    pub children: Vec<VirtualNode>,
     pub attributes: HashMap<String, String>,
     pub classes: Vec<String>,
-    pub height: usize,
-    pub depth: usize,
-    pub max_font_size: usize,
+    pub height: f64,
+    pub depth: f64,
+    pub max_font_size: f64,
     pub style: CssStyle,
 }
 
 impl Anchor {
+    pub fn new(
+        href: &str,
+        classes: Vec<String>,
+        children: Vec<VirtualNode>,
+        options: &Options,
+    ) -> Self {
+        let (classes, style) = init_node(classes, Some(options), CssStyle::new());
+        let mut anchor = Anchor {
+            children,
+            classes,
+            style,
+            ..Default::default()
+        };
+        anchor.set_attribute("href", href);
+        anchor
+    }
+
     /// Sets an attribute for the anchor.
-    pub fn set_attribute(&mut self, attribute: String, value: String) {
-        self.attributes.insert(attribute, value);
+    pub fn set_attribute(&mut self, attribute: &str, value: &str) {
+        self.attributes
+            .insert(attribute.to_string(), value.to_string());
+    }
+
+    pub fn to_markup(&self) -> String {
+        to_markup(
+            "a",
+            &self.classes,
+            &self.style,
+            &self.attributes,
+            &self.children,
+        )
     }
 }
 
-/// Represents an image embed element.
-#[derive(Debug, Clone, PartialEq)]
+/// This node represents an image embed (<img>) element.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Img {
     pub src: String,
     pub alt: String,
     pub classes: Vec<String>,
-    pub height: usize,
-    pub depth: usize,
-    pub max_font_size: usize,
+    pub height: f64,
+    pub depth: f64,
+    pub max_font_size: f64,
     pub style: CssStyle,
 }
 
-/// Represents a symbol node.
-#[derive(Debug, Clone, PartialEq)]
+impl Img {
+    pub fn new(src: &str, alt: &str, style: CssStyle) -> Self {
+        Img {
+            src: src.to_string(),
+            alt: alt.to_string(),
+            classes: vec!["mord".to_string()],
+            style,
+            ..Default::default()
+        }
+    }
+
+    pub fn to_markup(&self) -> String {
+        let mut markup = format!(
+            "<img src=\"{}\" alt=\"{}\"",
+            utils::escape(&self.src),
+            utils::escape(&self.alt)
+        );
+
+        let styles = styles_markup(&self.style);
+        if !styles.is_empty() {
+            markup += &format!(" style=\"{}\"", utils::escape(&styles));
+        }
+
+        markup += "/>";
+        markup
+    }
+}
+
+/// A symbol node contains information about a single symbol. It either renders
+/// to a single text node, or a span with a single text node in it, depending on
+/// whether it has CSS classes, styles, or needs italic correction.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct SymbolNode {
     pub text: String,
-    pub height: usize,
-    pub depth: usize,
-    pub italic: usize,
-    pub skew: usize,
-    pub width: usize,
-    pub max_font_size: usize,
+    pub height: f64,
+    pub depth: f64,
+    pub italic: f64,
+    pub skew: f64,
+    pub width: f64,
+    pub max_font_size: f64,
     pub classes: Vec<String>,
     pub style: CssStyle,
 }
 
-/// Represents an SVG node.
-#[derive(Debug, Clone, PartialEq)]
+impl SymbolNode {
+    /// Creates markup for a symbol node.
+    pub fn to_markup(&self) -> String {
+        // TODO(alpert): More duplication than I'd like from
+        // span.prototype.toMarkup and symbolNode.prototype.toNode...
+        let mut needs_span = false;
+
+        let mut markup = "<span".to_string();
+
+        if !self.classes.is_empty() {
+            needs_span = true;
+            markup += &format!(" class=\"{}\"", utils::escape(&create_class(&self.classes)));
+        }
+
+        let mut styles = String::new();
+
+        if self.italic > 0.0 {
+            styles += &format!("margin-right:{};", make_em(self.italic));
+        }
+        styles += &styles_markup(&self.style);
+
+        if !styles.is_empty() {
+            needs_span = true;
+            markup += &format!(" style=\"{}\"", utils::escape(&styles));
+        }
+
+        let escaped = utils::escape(&self.text);
+        if needs_span {
+            markup += ">";
+            markup += &escaped;
+            markup += "</span>";
+            markup
+        } else {
+            escaped
+        }
+    }
+}
+
+/// SVG nodes are used to render stretchy wide elements.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct SvgNode {
     pub children: Vec<SvgChildNode>,
     pub attributes: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone)]
+impl SvgNode {
+    pub fn new(children: Vec<SvgChildNode>, attributes: HashMap<String, String>) -> Self {
+        SvgNode {
+            children,
+            attributes,
+        }
+    }
+
+    pub fn to_markup(&self) -> String {
+        let mut markup = "<svg xmlns=\"http://www.w3.org/2000/svg\"".to_string();
+
+        // Apply attributes
+        for (attr, value) in &self.attributes {
+            markup += &format!(" {}=\"{}\"", attr, utils::escape(value));
+        }
+
+        markup += ">";
+
+        for child in &self.children {
+            markup += &child.to_markup();
+        }
+
+        markup += "</svg>";
+
+        markup
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum SvgChildNode {
     SvgPathNode(PathNode),
     SvgLineNode(LineNode),
 }
 
-/// Represents a path node within SVG.
+impl SvgChildNode {
+    pub fn to_markup(&self) -> String {
+        match self {
+            SvgChildNode::SvgPathNode(path) => path.to_markup(),
+            SvgChildNode::SvgLineNode(line) => line.to_markup(),
+        }
+    }
+}
+
+/// A path in an SVG, either one of the named paths or an `alternate` path
+/// given directly.
 #[derive(Debug, Clone, PartialEq)]
 pub struct PathNode {
     pub path_name: String,
     pub alternate: Option<String>,
 }
 
-/// Represents a line node within SVG.
+impl PathNode {
+    pub fn new(path_name: &str, alternate: Option<String>) -> Self {
+        PathNode {
+            path_name: path_name.to_string(),
+            alternate,
+        }
+    }
+
+    pub fn to_markup(&self) -> String {
+        let path = match &self.alternate {
+            Some(alternate) => alternate.as_str(),
+            None => svg_geometry::svg_path(&self.path_name).unwrap_or_default(),
+        };
+        format!("<path d=\"{}\"/>", utils::escape(path))
+    }
+}
+
+/// A line in an SVG.
 #[derive(Debug, Clone, PartialEq)]
 pub struct LineNode {
     pub attributes: HashMap<String, String>,
 }
+
+impl LineNode {
+    pub fn new(attributes: HashMap<String, String>) -> Self {
+        LineNode { attributes }
+    }
+
+    pub fn to_markup(&self) -> String {
+        let mut markup = "<line".to_string();
+
+        for (attr, value) in &self.attributes {
+            markup += &format!(" {}=\"{}\"", attr, utils::escape(value));
+        }
+
+        markup += "/>";
+
+        markup
+    }
+}
+
+/// This node represents a document fragment, which contains elements, but when
+/// placed into the DOM doesn't have any representation itself. It only
+/// contains children and doesn't have any DOM node properties.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DocumentFragment {
+    pub children: Vec<VirtualNode>,
+    // The following are used by the HTML builders, but never rendered.
+    pub classes: Vec<String>,
+    pub height: f64,
+    pub depth: f64,
+    pub max_font_size: f64,
+    pub style: CssStyle,
+}
+
+impl DocumentFragment {
+    pub fn new(children: Vec<VirtualNode>) -> Self {
+        DocumentFragment {
+            children,
+            ..Default::default()
+        }
+    }
+
+    pub fn has_class(&self, class_name: &str) -> bool {
+        self.classes.iter().any(|class| class == class_name)
+    }
+
+    /// Convert the fragment into HTML markup.
+    pub fn to_markup(&self) -> String {
+        self.children.iter().map(VirtualNode::to_markup).collect()
+    }
+}
@@ -0,0 +1,332 @@
+//! This file contains metrics regarding fonts and individual symbols. The sigma
+//! and xi variables, as well as the METRIC_MAP map contain data extracted from
+//! TeX, TeX font metrics, and the TTF files. These data are then exposed via the
+//! `metrics` variable and the `get_character_metrics` function.
+use super::*;
+
+/// The global metrics of the fonts at one size: the sigma and xi parameters
+/// of TeX's math fonts, plus a few values from LaTeX.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontMetrics {
+    pub css_em_per_mu: f64,
+    pub slant: f64,
+    pub space: f64,
+    pub stretch: f64,
+    pub shrink: f64,
+    pub x_height: f64,
+    pub quad: f64,
+    pub extra_space: f64,
+    pub num1: f64,
+    pub num2: f64,
+    pub num3: f64,
+    pub denom1: f64,
+    pub denom2: f64,
+    pub sup1: f64,
+    pub sup2: f64,
+    pub sup3: f64,
+    pub sub1: f64,
+    pub sub2: f64,
+    pub sup_drop: f64,
+    pub sub_drop: f64,
+    pub delim1: f64,
+    pub delim2: f64,
+    pub axis_height: f64,
+    pub default_rule_thickness: f64,
+    pub big_op_spacing1: f64,
+    pub big_op_spacing2: f64,
+    pub big_op_spacing3: f64,
+    pub big_op_spacing4: f64,
+    pub big_op_spacing5: f64,
+    pub sqrt_rule_thickness: f64,
+    pub pt_per_em: f64,
+    pub double_rule_sep: f64,
+    pub array_rule_width: f64,
+    pub fboxsep: f64,
+    pub fboxrule: f64,
+}
+
+/// In TeX, there are actually three sets of dimensions, one for each of
+/// textstyle (size index 5 and higher: >=9pt), scriptstyle (size index 3 and 4:
+/// 7-8pt), and scriptscriptstyle (size index 1 and 2: 5-6pt).  These are
+/// provided in the the arrays below, in that order.
+///
+/// The font metrics are stored in fonts cmsy10, cmsy7, and cmsy5 respectively.
+/// This was determined by running the following script:
+///
+/// ```text
+/// latex -interaction=nonstopmode \
+/// '\documentclass{article}\usepackage{amsmath}\begin{document}' \
+/// '$a$ \expandafter\show\the\textfont2' \
+/// '\expandafter\show\the\scriptfont2' \
+/// '\expandafter\show\the\scriptscriptfont2' \
+/// '\stop'
+/// ```
+///
+/// The metrics themselves were retrieved using the following commands:
+///
+/// ```text
+/// tftopl cmsy10
+/// tftopl cmsy7
+/// tftopl cmsy5
+/// ```
+///
+/// The output of each of these commands is quite lengthy.  The only part we
+/// care about is the FONTDIMEN section. Each value is measured in EMs.
+fn sigmas_and_xis(index: usize) -> FontMetrics {
+    let quad = [1.000, 1.171, 1.472][index]; // sigma6
+    FontMetrics {
+        slant: [0.250, 0.250, 0.250][index],    // sigma1
+        space: [0.000, 0.000, 0.000][index],    // sigma2
+        stretch: [0.000, 0.000, 0.000][index],  // sigma3
+        shrink: [0.000, 0.000, 0.000][index],   // sigma4
+        x_height: [0.431, 0.431, 0.431][index], // sigma5
+        quad,
+        extra_space: [0.000, 0.000, 0.000][index], // sigma7
+        num1: [0.677, 0.732, 0.925][index],        // sigma8
+        num2: [0.394, 0.384, 0.387][index],        // sigma9
+        num3: [0.444, 0.471, 0.504][index],        // sigma10
+        denom1: [0.686, 0.752, 1.025][index],      // sigma11
+        denom2: [0.345, 0.344, 0.532][index],      // sigma12
+        sup1: [0.413, 0.503, 0.504][index],        // sigma13
+        sup2: [0.363, 0.431, 0.404][index],        // sigma14
+        sup3: [0.289, 0.286, 0.294][index],        // sigma15
+        sub1: [0.150, 0.143, 0.200][index],        // sigma16
+        sub2: [0.247, 0.286, 0.400][index],        // sigma17
+        sup_drop: [0.386, 0.353, 0.494][index],    // sigma18
+        sub_drop: [0.050, 0.071, 0.100][index],    // sigma19
+        delim1: [2.390, 1.700, 1.980][index],      // sigma20
+        delim2: [1.010, 1.157, 1.420][index],      // sigma21
+        axis_height: [0.250, 0.250, 0.250][index], // sigma22
+
+        // These font metrics are extracted from TeX by using tftopl on cmex10.tfm;
+        // they correspond to the font parameters of the extension fonts (family 3).
+        // See the TeXbook, page 441. In AMSTeX, the extension fonts scale; to
+        // match cmex7, we'd use cmex7.tfm values for script and scriptscript
+        // values.
+        default_rule_thickness: [0.04, 0.049, 0.049][index], // xi8; cmex7: 0.049
+        big_op_spacing1: [0.111, 0.111, 0.111][index],       // xi9
+        big_op_spacing2: [0.166, 0.166, 0.166][index],       // xi10
+        big_op_spacing3: [0.2, 0.1, 0.1][index],             // xi11; cmex7: 0.1
+        big_op_spacing4: [0.6, 0.611, 0.611][index],         // xi12; cmex7: 0.611
+        big_op_spacing5: [0.1, 0.143, 0.143][index],         // xi13; cmex7: 0.143
+
+        // The \sqrt rule width is taken from the height of the surd character.
+        // Since we use the same font at all sizes, this thickness doesn't scale.
+        sqrt_rule_thickness: [0.04, 0.04, 0.04][index],
+
+        // This value determines how large a pt is, for metrics which are defined
+        // in terms of pts.
+        // This value is also used in katex.scss; if you change it make sure the
+        // values match.
+        pt_per_em: [10.0, 10.0, 10.0][index],
+
+        // The space between adjacent `|` columns in an array definition. From
+        // `\showthe\doublerulesep` in LaTeX. Equals 2.0 / ptPerEm.
+        double_rule_sep: [0.2, 0.2, 0.2][index],
+
+        // The width of separator lines in {array} environments. From
+        // `\showthe\arrayrulewidth` in LaTeX. Equals 0.4 / ptPerEm.
+        array_rule_width: [0.04, 0.04, 0.04][index],
+
+        // Two values from LaTeX source2e:
+        fboxsep: [0.3, 0.3, 0.3][index],     //        3 pt / ptPerEm
+        fboxrule: [0.04, 0.04, 0.04][index], // 0.4 pt / ptPerEm
+
+        css_em_per_mu: quad / 18.0,
+    }
+}
+
+static FONT_METRICS_BY_SIZE_INDEX: LazyLock<[FontMetrics; 3]> =
+    LazyLock::new(|| [sigmas_and_xis(0), sigmas_and_xis(1), sigmas_and_xis(2)]);
+
+/// Get the font metrics for a given size.
+pub fn get_global_metrics(size: usize) -> &'static FontMetrics {
+    let size_index = if size >= 5 {
+        0
+    } else if size >= 3 {
+        1
+    } else {
+        2
+    };
+    &FONT_METRICS_BY_SIZE_INDEX[size_index]
+}
+
+/// The metrics of a single character, in ems.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CharacterMetrics {
+    pub depth: f64,
+    pub height: f64,
+    pub italic: f64,
+    pub skew: f64,
+    pub width: f64,
+}
+
+/// Character metrics by font name and code point, as
+/// `[depth, height, italic, skew, width]`.
+pub static METRIC_MAP: LazyLock<HashMap<&'static str, HashMap<u32, [f64; 5]>>> =
+    LazyLock::new(|| {
+        font_metrics_data::FONT_METRICS_DATA
+            .iter()
+            .map(|&(font, metrics)| (font, metrics.iter().copied().collect()))
+            .collect()
+    });
+
+/// These are very rough approximations.  We default to Times New Roman which
+/// should have Latin-1 and Cyrillic characters, but may not depending on the
+/// operating system.  The metrics do not account for extra height from the
+/// accents.  In the case of Cyrillic characters which have both ascenders and
+/// descenders we prefer approximations with ascenders, primarily to prevent
+/// the fraction bar or root line from intersecting the glyph.
+fn extra_character(ch: char) -> Option<char> {
+    let replacement = match ch {
+        // Latin-1
+        'Å' | 'À' | 'Á' | 'Â' | 'Ã' | 'Ä' => 'A',
+        'Ç' => 'C',
+        'È' | 'É' | 'Ê' | 'Ë' => 'E',
+        'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+        'Ñ' => 'N',
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' => 'O',
+        'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+        'Ý' => 'Y',
+        'å' | 'à' | 'á' | 'â' | 'ã' | 'ä' => 'a',
+        'ç' => 'c',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+
+        // Cyrillic
+        'А' => 'A',
+        'Б' => 'B',
+        'В' => 'B',
+        'Г' => 'F',
+        'Д' => 'A',
+        'Е' => 'E',
+        'Ж' => 'K',
+        'З' => '3',
+        'И' => 'N',
+        'Й' => 'N',
+        'К' => 'K',
+        'Л' => 'N',
+        'М' => 'M',
+        'Н' => 'H',
+        'О' => 'O',
+        'П' => 'N',
+        'Р' => 'P',
+        'С' => 'C',
+        'Т' => 'T',
+        'У' => 'y',
+        'Ф' => 'O',
+        'Х' => 'X',
+        'Ц' => 'U',
+        'Ч' => 'h',
+        'Ш' => 'W',
+        'Щ' => 'W',
+        'Ъ' => 'B',
+        'Ы' => 'X',
+        'Ь' => 'B',
+        'Э' => '3',
+        'Ю' => 'X',
+        'Я' => 'R',
+        'а' => 'a',
+        'б' => 'b',
+        'в' => 'a',
+        'г' => 'r',
+        'д' => 'y',
+        'е' => 'e',
+        'ж' => 'm',
+        'з' => 'e',
+        'и' => 'n',
+        'й' => 'n',
+        'к' => 'n',
+        'л' => 'n',
+        'м' => 'm',
+        'н' => 'n',
+        'о' => 'o',
+        'п' => 'n',
+        'р' => 'p',
+        'с' => 'c',
+        'т' => 'o',
+        'у' => 'y',
+        'ф' => 'b',
+        'х' => 'x',
+        'ц' => 'n',
+        'ч' => 'n',
+        'ш' => 'w',
+        'щ' => 'w',
+        'ъ' => 'a',
+        'ы' => 'm',
+        'ь' => 'a',
+        'э' => 'e',
+        'ю' => 'm',
+        'я' => 'r',
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+/// This function is a convenience function for looking up information in the
+/// METRIC_MAP table. It takes a character as a string, and a font.
+pub fn get_character_metrics(character: &str, font: &str, mode: Mode) -> Option<CharacterMetrics> {
+    let font_metrics = METRIC_MAP.get(font)?;
+    let first = character.chars().next()?;
+    let mut metrics = font_metrics.get(&(first as u32));
+
+    if metrics.is_none() {
+        if let Some(ch) = extra_character(first) {
+            metrics = font_metrics.get(&(ch as u32));
+        }
+    }
+
+    if metrics.is_none() && mode == Mode::Text {
+        // We don't typically have font metrics for Asian scripts.
+        // But since we support them in text mode, we need to return
+        // some sort of metrics.
+        // So if the character is in a script we support but we
+        // don't have metrics for it, just use the metrics for
+        // the Latin capital letter M. This is close enough because
+        // we (currently) only care about the height of the glyph
+        // not its width.
+        if !first.is_ascii() {
+            metrics = font_metrics.get(&77); // 77 is the charcode for 'M'
+        }
+    }
+
+    metrics.map(|&[depth, height, italic, skew, width]| CharacterMetrics {
+        depth,
+        height,
+        italic,
+        skew,
+        width,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn character_metrics() {
+        let x = get_character_metrics("x", "Math-Italic", Mode::Math).unwrap();
+        assert_eq!(x.height, 0.43056);
+        assert_eq!(x.width, 0.57153);
+        assert_eq!(x.skew, 0.02778);
+
+        // Accented Latin and Cyrillic letters borrow the metrics of a similar
+        // letter, and text mode uses 'M' for other scripts.
+        let a = get_character_metrics("a", "Main-Regular", Mode::Math);
+        assert_eq!(get_character_metrics("ä", "Main-Regular", Mode::Math), a);
+        let m = get_character_metrics("M", "Main-Regular", Mode::Text);
+        assert_eq!(get_character_metrics("字", "Main-Regular", Mode::Text), m);
+        assert_eq!(get_character_metrics("字", "Main-Regular", Mode::Math), None);
+        assert_eq!(get_character_metrics("x", "NoSuchFont", Mode::Math), None);
+    }
+
+    #[test]
+    fn symbols_are_measured() {
+        let markup = render_to_string("x", &Settings::default());
+        assert!(markup.contains("height:0.4306em;"), "{}", markup);
+    }
+}
@@ -0,0 +1,100 @@
+//! All the functions, keyed by name. Each module in `functions/` registers
+//! the commands it implements, together with the HTML and MathML builders
+//! for the parse node types they produce.
+use super::*;
+
+mod accent;
+mod accentunder;
+mod arrow;
+mod assemble_sup_sub;
+mod at_char;
+mod color;
+mod def;
+mod delimsizing;
+mod enclose;
+mod font;
+mod genfrac;
+mod hbox;
+mod horiz_brace;
+mod href;
+mod html;
+mod includegraphics;
+mod kern;
+mod lap;
+mod math;
+mod mclass;
+mod op;
+mod operatorname;
+mod ordgroup;
+mod overline;
+mod phantom;
+mod raisebox;
+mod relax;
+mod rule;
+mod sizing;
+mod smash;
+mod sqrt;
+mod styling;
+mod supsub;
+mod symbols_op;
+mod symbols_ord;
+mod symbols_spacing;
+mod tag;
+mod text;
+mod underline;
+mod vcenter;
+
+use assemble_sup_sub::assemble_sup_sub;
+
+static REGISTRY: LazyLock<FunctionRegistry> = LazyLock::new(|| {
+    let mut registry = FunctionRegistry::default();
+    accent::define(&mut registry);
+    accentunder::define(&mut registry);
+    arrow::define(&mut registry);
+    at_char::define(&mut registry);
+    color::define(&mut registry);
+    def::define(&mut registry);
+    delimsizing::define(&mut registry);
+    enclose::define(&mut registry);
+    font::define(&mut registry);
+    genfrac::define(&mut registry);
+    hbox::define(&mut registry);
+    horiz_brace::define(&mut registry);
+    href::define(&mut registry);
+    html::define(&mut registry);
+    includegraphics::define(&mut registry);
+    kern::define(&mut registry);
+    lap::define(&mut registry);
+    math::define(&mut registry);
+    mclass::define(&mut registry);
+    op::define(&mut registry);
+    operatorname::define(&mut registry);
+    ordgroup::define(&mut registry);
+    overline::define(&mut registry);
+    phantom::define(&mut registry);
+    raisebox::define(&mut registry);
+    relax::define(&mut registry);
+    rule::define(&mut registry);
+    sizing::define(&mut registry);
+    smash::define(&mut registry);
+    sqrt::define(&mut registry);
+    styling::define(&mut registry);
+    supsub::define(&mut registry);
+    symbols_op::define(&mut registry);
+    symbols_ord::define(&mut registry);
+    symbols_spacing::define(&mut registry);
+    tag::define(&mut registry);
+    text::define(&mut registry);
+    underline::define(&mut registry);
+    vcenter::define(&mut registry);
+    registry
+});
+
+pub static FUNCTIONS: LazyLock<&'static HashMap<&'static str, FunctionSpec>> =
+    LazyLock::new(|| &REGISTRY.functions);
+
+pub static HTML_GROUP_BUILDERS: LazyLock<&'static HashMap<&'static str, HtmlBuilder>> =
+    LazyLock::new(|| &REGISTRY.html_group_builders);
+
+pub static MATHML_GROUP_BUILDERS: LazyLock<&'static HashMap<&'static str, MathMlBuilder>> =
+    LazyLock::new(|| &REGISTRY.mathml_group_builders);
@@ -0,0 +1,457 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // Accents
+    registry.define_function(FunctionDefSpec {
+        node_type: "accent",
+        names: &[
+            "\\acute",
+            "\\grave",
+            "\\ddot",
+            "\\tilde",
+            "\\bar",
+            "\\breve",
+            "\\check",
+            "\\hat",
+            "\\vec",
+            "\\dot",
+            "\\mathring",
+            "\\widecheck",
+            "\\widehat",
+            "\\widetilde",
+            "\\overrightarrow",
+            "\\overleftarrow",
+            "\\Overrightarrow",
+            "\\overleftrightarrow",
+            "\\overleftharpoon",
+            "\\overrightharpoon",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(math_accent_handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    // Text-mode accents
+    registry.define_function(FunctionDefSpec {
+        node_type: "accent",
+        names: &[
+            "\\'",
+            "\\`",
+            "\\^",
+            "\\~",
+            "\\=",
+            "\\u",
+            "\\.",
+            "\\\"",
+            "\\c",
+            "\\r",
+            "\\H",
+            "\\v",
+            "\\textcircled",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_text: true,
+            allowed_in_math: true, // unless in strict mode
+            arg_types: Some(vec![ArgType::Primitive]),
+            ..Default::default()
+        },
+        handler: Some(text_accent_handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+/// NOTE: Unlike most `html_builder`s, this one handles not only "accent", but
+/// also "supsub" since an accent can affect super/subscripting.
+pub fn html_builder(grp: &AnyParseNode, options: &Options) -> VirtualNode {
+    // Accents are handled in the TeXbook pg. 443, rule 12.
+    let (group, base, sup_sub_group) = match grp {
+        AnyParseNode::SupSub(supsub) => {
+            // If our base is a character box, and we have superscripts and
+            // subscripts, the supsub will defer to us. In particular, we want
+            // to attach the superscripts and subscripts to the inner body (so
+            // that the position of the superscripts and subscripts won't be
+            // affected by the height of the accent). We accomplish this by
+            // sticking the base of the accent into the base of the supsub, and
+            // rendering that, while keeping track of where the accent is.
+
+            // The real accent group is the base of the supsub group
+            let Some(AnyParseNode::Accent(group)) = supsub.base.as_deref() else {
+                panic!("Expected node of type accent as the base of a supsub");
+            };
+            // The character box is the base of the accent group
+            let base = &*group.base;
+            // Stick the character box into the base of the supsub group
+            let mut supsub = supsub.clone();
+            supsub.base = Some(Box::new(base.clone()));
+
+            // Rerender the supsub group with its new base, and store that
+            // result.
+            let VirtualNode::Span(sup_sub_group) =
+                build_html::build_group(Some(&AnyParseNode::SupSub(supsub)), options, None)
+            else {
+                panic!("Expected span<HtmlDomNode> for a supsub");
+            };
+            (group, base, Some(sup_sub_group))
+        }
+        AnyParseNode::Accent(group) => (group, &*group.base, None),
+        _ => unreachable!(),
+    };
+
+    // Build the base group
+    let body = build_html::build_group(Some(base), &options.having_cramped_style(), None);
+
+    // Does the accent need to shift for the skew of a character?
+    let must_shift = group.is_shifty == Some(true) && utils::is_character_box(base);
+
+    // Calculate the skew of the accent. This is based on the line "If the
+    // nucleus is not a single character, let s = 0; otherwise set s to the
+    // kern amount for the nucleus followed by the \skewchar of its font."
+    // Note that our skew metrics are just the kern between each character
+    // and the skewchar.
+    let mut skew = 0.0;
+    if must_shift {
+        // If the base is a character box, then we want the skew of the
+        // innermost character. To do that, we find the innermost character:
+        let base_char = utils::get_base_elem(base);
+        // Then, we render its group to get the symbol inside it
+        let base_group =
+            build_html::build_group(Some(base_char), &options.having_cramped_style(), None);
+        // Finally, we pull the skew off of the symbol.
+        let VirtualNode::Symbol(base_group) = base_group else {
+            panic!("Expected symbolNode for the base of a shifty accent");
+        };
+        skew = base_group.skew;
+        // Note that we now throw away base_group, because the layers we
+        // removed with get_base_elem might contain things like \color which
+        // we can't get rid of.
+        // TODO(emily): Find a better way to get the skew
+    }
+
+    let accent_below = group.label == "\\c";
+
+    // calculate the amount of space between the body and the accent
+    let mut clearance = if accent_below {
+        body.height() + body.depth()
+    } else {
+        body.height().min(options.font_metrics().x_height)
+    };
+
+    // Build the accent
+    let accent_body = if group.is_stretchy != Some(true) {
+        let accent: VirtualNode;
+        let width;
+        if group.label == "\\vec" {
+            // Before version 0.9, \vec used the combining font glyph U+20D7.
+            // But browsers, especially Safari, are not consistent in how they
+            // render combining characters when not preceded by a character.
+            // So now we use an SVG.
+            // If Safari reforms, we should consider reverting to the glyph.
+            accent = static_svg("vec", options).into();
+            width = svg_data("vec").unwrap().1;
+        } else {
+            let VirtualNode::Symbol(mut symbol) = make_ord(
+                &AnyParseNode::TextOrd(TextOrdParseNode {
+                    type_: "textord".into(),
+                    mode: group.mode.clone(),
+                    loc: None,
+                    text: group.label.clone(),
+                }),
+                options,
+                "textord",
+            ) else {
+                panic!("Expected symbolNode for the accent {}", group.label);
+            };
+            // Remove the italic correction of the accent, because it only serves to
+            // shift the accent over to a place we don't want.
+            symbol.italic = 0.0;
+            width = symbol.width;
+            if accent_below {
+                clearance += symbol.depth;
+            }
+            accent = symbol.into();
+        }
+
+        let mut accent_body = make_span(vec!["accent-body".to_string()], vec![accent], None, None);
+
+        // "Full" accents expand the width of the resulting symbol to be
+        // at least the width of the accent, and overlap directly onto the
+        // character without any vertical offset.
+        let accent_full = group.label == "\\textcircled";
+        if accent_full {
+            accent_body.classes.push("accent-full".to_string());
+            clearance = body.height();
+        }
+
+        // Shift the accent over by the skew.
+        let mut left = skew;
+
+        // CSS defines `.katex .accent .accent-body:not(.accent-full) { width: 0 }`
+        // so that the accent doesn't contribute to the bounding box.
+        // We need to shift the character by its width (effectively half
+        // its width) to compensate.
+        if !accent_full {
+            left -= width / 2.0;
+        }
+
+        accent_body.style.insert("left".to_string(), make_em(left));
+
+        // \textcircled uses the \bigcirc glyph, so it needs some
+        // vertical adjustment to match LaTeX.
+        if group.label == "\\textcircled" {
+            accent_body
+                .style
+                .insert("top".to_string(), ".2em".to_string());
+        }
+
+        make_v_list(VListParam::FirstBaseline(vec![
+            VListElem::new(body).into(),
+            VListChild::Kern(-clearance),
+            VListElem::new(accent_body).into(),
+        ]))
+    } else {
+        let accent_body = stretchy::svg_span(&AnyParseNode::Accent(group.clone()), options);
+
+        let mut accent_body = VListElem::new(accent_body);
+        accent_body.wrapper_classes = vec!["svg-align".to_string()];
+        if skew > 0.0 {
+            accent_body.wrapper_style = HashMap::from([
+                (
+                    "width".to_string(),
+                    format!("calc(100% - {})", make_em(2.0 * skew)),
+                ),
+                ("marginLeft".to_string(), make_em(2.0 * skew)),
+            ]);
+        }
+
+        make_v_list(VListParam::FirstBaseline(vec![
+            VListElem::new(body).into(),
+            accent_body.into(),
+        ]))
+    };
+
+    let accent_wrap = make_span(
+        vec!["mord".to_string(), "accent".to_string()],
+        vec![accent_body.into()],
+        Some(options),
+        None,
+    );
+
+    match sup_sub_group {
+        Some(mut sup_sub_group) => {
+            // Here, we replace the "base" child of the supsub with our newly
+            // generated accent.
+            let accent_height = accent_wrap.height;
+            sup_sub_group.children[0] = accent_wrap.into();
+
+            // Since we don't rerun the height calculation after replacing the
+            // accent, we manually recalculate height.
+            sup_sub_group.height = accent_height.max(sup_sub_group.height);
+
+            // Accents should always be ords, even when their innards are not.
+            sup_sub_group.classes[0] = "mord".to_string();
+
+            sup_sub_group.into()
+        }
+        None => accent_wrap.into(),
+    }
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Accent(group) = group else {
+        unreachable!()
+    };
+    let accent_node = if group.is_stretchy == Some(true) {
+        stretchy::math_ml_node(&group.label)
+    } else {
+        MathNode::new(
+            MathNodeType::Mo,
+            vec![
+                build_mathml::make_text(&group.label, Mode::from(group.mode.as_str()), None).into(),
+            ],
+            vec![],
+        )
+    };
+
+    let mut node = MathNode::new(
+        MathNodeType::Mover,
+        vec![
+            build_mathml::build_group(Some(&group.base), options),
+            accent_node.into(),
+        ],
+        vec![],
+    );
+
+    node.set_attribute("accent", "true");
+
+    node.into()
+}
+
+const NON_STRETCHY_ACCENTS: &[&str] = &[
+    "\\acute",
+    "\\grave",
+    "\\ddot",
+    "\\tilde",
+    "\\bar",
+    "\\breve",
+    "\\check",
+    "\\hat",
+    "\\vec",
+    "\\dot",
+    "\\mathring",
+];
+
+fn math_accent_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let base = normalize_argument(args.remove(0));
+
+    let is_stretchy = !NON_STRETCHY_ACCENTS.contains(&func_name.as_str());
+    let is_shifty = !is_stretchy
+        || func_name == "\\widehat"
+        || func_name == "\\widetilde"
+        || func_name == "\\widecheck";
+
+    Ok(AnyParseNode::Accent(AccentParseNode {
+        type_: "accent".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        label: func_name,
+        is_stretchy: Some(is_stretchy),
+        is_shifty: Some(is_shifty),
+        base: Box::new(base),
+    }))
+}
+
+fn text_accent_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser,
+        func_name,
+        token,
+        ..
+    } = context;
+    let base = args.remove(0);
+    let mut mode = parser.mode;
+
+    if mode == Mode::Math {
+        parser.settings.report_nonstrict(
+            "mathVsTextAccents",
+            &format!("LaTeX's accent {} works only in text mode", func_name),
+            token.as_ref(),
+        )?;
+        mode = Mode::Text;
+    }
+
+    Ok(AnyParseNode::Accent(AccentParseNode {
+        type_: "accent".into(),
+        mode: mode.to_string(),
+        loc: None,
+        label: func_name,
+        is_stretchy: Some(false),
+        is_shifty: Some(true),
+        base: Box::new(base),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accent_kinds() {
+        let expression = "\\hat x\\widehat{xyz}\\overrightarrow{AB}";
+        let tree = parse_tree(expression, &Settings::default()).unwrap();
+        let [
+            AnyParseNode::Accent(hat),
+            AnyParseNode::Accent(widehat),
+            AnyParseNode::Accent(arrow),
+        ] = &tree[..]
+        else {
+            panic!("expected three accent nodes");
+        };
+        assert_eq!(hat.label, "\\hat");
+        assert_eq!((hat.is_stretchy, hat.is_shifty), (Some(false), Some(true)));
+        assert_eq!((widehat.is_stretchy, widehat.is_shifty), (Some(true), Some(true)));
+        assert_eq!((arrow.is_stretchy, arrow.is_shifty), (Some(true), Some(false)));
+
+        let tree = parse_tree("\\utilde{ab}", &Settings::default()).unwrap();
+        let [AnyParseNode::AccentUnder(utilde)] = &tree[..] else {
+            panic!("expected an accentunder node");
+        };
+        assert_eq!(utilde.label, "\\utilde");
+    }
+
+    #[test]
+    fn layout() {
+        // An accent sits on top of its base, lifted by the base's height.
+        let base = build_one("x");
+        let hat = build_one("\\hat x");
+        assert!(hat.height() > base.height());
+        assert_eq!(hat.depth(), base.depth());
+        assert!(build_one("\\hat X").height() > hat.height());
+
+        // An under-accent hangs below it instead.
+        let under = build_one("\\underleftarrow{x}");
+        assert!(under.depth() > base.depth());
+    }
+
+    #[test]
+    fn markup() {
+        let hat = render("\\hat x");
+        assert!(hat.contains("<mover accent=\"true\"><mi>x</mi><mo>^</mo></mover>"));
+        assert!(hat.contains("class=\"accent-body\""));
+
+        // Wide accents pick a taller image for more characters.
+        let widehat = render("\\widehat{xyz}");
+        assert!(widehat.contains("<mo stretchy=\"true\">^</mo>"));
+        assert!(widehat.contains("viewBox=\"0 0 2364 300\""));
+        let widehat = render("\\widehat{x}");
+        assert!(widehat.contains("viewBox=\"0 0 1062 239\""));
+
+        let arrow = render("\\overrightarrow{AB}");
+        assert!(arrow.contains("class=\"hide-tail\""));
+        assert!(arrow.contains("preserveAspectRatio=\"xMaxYMin slice\""));
+
+        let under = render("\\underleftarrow{AB}");
+        assert!(under.contains("<munder accentunder=\"true\">"));
+        assert!(under.contains("class=\"mord accentunder\""));
+    }
+
+    #[test]
+    fn svg_images_have_paths() {
+        for expression in [
+            "\\vec v",
+            "\\widehat{x}",
+            "\\widecheck{xyz}",
+            "\\widetilde{abcdef}",
+            "\\overrightarrow{x}",
+            "\\overleftrightarrow{x}",
+            "\\Overrightarrow{x}",
+            "\\overgroup{x}",
+            "\\overlinesegment{x}",
+            "\\overleftharpoon{x}",
+            "\\overrightharpoon{x}",
+            "\\utilde{x}",
+            "\\undergroup{x}",
+            "\\underlinesegment{x}",
+        ] {
+            let markup = render(expression);
+            assert!(markup.contains("<path d=\"M"), "{}", expression);
+            assert!(!markup.contains("<path d=\"\"/>"), "{}", expression);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Horizontal overlap functions
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "accentUnder",
+        names: &[
+            "\\underleftarrow",
+            "\\underrightarrow",
+            "\\underleftrightarrow",
+            "\\utilde",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let base = args.remove(0);
+    Ok(AnyParseNode::AccentUnder(AccentUnderParseNode {
+        type_: "accentUnder".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        label: func_name,
+        is_stretchy: None,
+        is_shifty: None,
+        base: Box::new(base),
+    }))
+}
+
+fn html_builder(grp: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::AccentUnder(group) = grp else {
+        unreachable!()
+    };
+    // Treat under accents much like underlines.
+    let inner_group = build_html::build_group(Some(&group.base), options, None);
+
+    let accent_body = stretchy::svg_span(grp, options);
+    let kern = if group.label == "\\utilde" { 0.12 } else { 0.0 };
+
+    // Generate the vlist, with the appropriate kerns
+    let inner_height = inner_group.height();
+    let mut accent_body = VListElem::new(accent_body);
+    accent_body.wrapper_classes = vec!["svg-align".to_string()];
+    let vlist = make_v_list(VListParam::Top(
+        inner_height,
+        vec![
+            accent_body.into(),
+            VListChild::Kern(kern),
+            VListElem::new(inner_group).into(),
+        ],
+    ));
+
+    make_span(
+        vec!["mord".to_string(), "accentunder".to_string()],
+        vec![vlist.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::AccentUnder(group) = group else {
+        unreachable!()
+    };
+    let accent_node = stretchy::math_ml_node(&group.label);
+    let mut node = MathNode::new(
+        MathNodeType::Munder,
+        vec![
+            build_mathml::build_group(Some(&group.base), options),
+            accent_node.into(),
+        ],
+        vec![],
+    );
+    node.set_attribute("accentunder", "true");
+    node.into()
+}
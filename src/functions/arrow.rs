@@ -0,0 +1,256 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // Stretchy arrows with an optional argument
+    registry.define_function(FunctionDefSpec {
+        node_type: "xArrow",
+        names: &[
+            "\\xleftarrow",
+            "\\xrightarrow",
+            "\\xLeftarrow",
+            "\\xRightarrow",
+            "\\xleftrightarrow",
+            "\\xLeftrightarrow",
+            "\\xhookleftarrow",
+            "\\xhookrightarrow",
+            "\\xmapsto",
+            "\\xrightharpoondown",
+            "\\xrightharpoonup",
+            "\\xleftharpoondown",
+            "\\xleftharpoonup",
+            "\\xrightleftharpoons",
+            "\\xleftrightharpoons",
+            "\\xlongequal",
+            "\\xtwoheadrightarrow",
+            "\\xtwoheadleftarrow",
+            "\\xtofrom",
+            // The next 3 functions are here to support the mhchem extension.
+            // Direct use of these functions is discouraged and may break someday.
+            "\\xrightleftarrows",
+            "\\xrightequilibrium",
+            "\\xleftequilibrium",
+            // The next 3 functions are here only to support the {CD} environment.
+            "\\\\cdrightarrow",
+            "\\\\cdleftarrow",
+            "\\\\cdlongequal",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            num_optional_args: 1,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+// Helper function
+fn padded_node(group: MathDomNode) -> MathNode {
+    let mut node = MathNode::new(MathNodeType::Mpadded, vec![group], vec![]);
+    node.set_attribute("width", "+0.6em");
+    node.set_attribute("lspace", "0.3em");
+    node
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    mut opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    Ok(AnyParseNode::XArrow(XArrowParseNode {
+        type_: "xArrow".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        label: func_name,
+        body: Box::new(args.remove(0)),
+        below: opt_args.remove(0).map(Box::new),
+    }))
+}
+
+fn html_builder(grp: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::XArrow(group) = grp else {
+        unreachable!()
+    };
+    let style = options.style;
+
+    // Build the argument groups in the appropriate style.
+    // Ref: amsmath.dtx:   \hbox{$\scriptstyle\mkern#3mu{#6}\mkern#4mu$}%
+
+    // Some groups can return document fragments.  Handle those by wrapping
+    // them in a span.
+    let new_options = options.having_style(style.sup());
+    let mut upper_group = wrap_fragment(
+        build_html::build_group(Some(&group.body), &new_options, Some(options)),
+        options,
+    );
+    let arrow_prefix = if group.label.starts_with("\\x") {
+        "x"
+    } else {
+        "cd"
+    };
+    if let Some(classes) = upper_group.classes_mut() {
+        classes.push(format!("{}-arrow-pad", arrow_prefix));
+    }
+
+    let lower_group = group.below.as_deref().map(|below| {
+        // Build the lower group
+        let new_options = options.having_style(style.sub());
+        let mut lower_group = wrap_fragment(
+            build_html::build_group(Some(below), &new_options, Some(options)),
+            options,
+        );
+        if let Some(classes) = lower_group.classes_mut() {
+            classes.push(format!("{}-arrow-pad", arrow_prefix));
+        }
+        lower_group
+    });
+
+    let arrow_body = stretchy::svg_span(grp, options);
+
+    // Re shift: Note that stretchy::svg_span returned arrow_body.depth = 0.
+    // The point we want on the math axis is at 0.5 * arrow_body.height.
+    let axis_height = options.font_metrics().axis_height;
+    let arrow_height = arrow_body.height;
+    let arrow_shift = -axis_height + 0.5 * arrow_height;
+    // 2 mu kern. Ref: amsmath.dtx: #7\if0#2\else\mkern#2mu\fi
+    let mut upper_shift = -axis_height - 0.5 * arrow_height - 0.111; // 0.111 em = 2 mu
+    if upper_group.depth() > 0.25 || group.label == "\\xleftequilibrium" {
+        upper_shift -= upper_group.depth(); // shift up if depth encroaches
+    }
+
+    let mut arrow_body = VListElem::shifted(arrow_body, arrow_shift);
+    arrow_body.wrapper_classes = vec!["svg-align".to_string()];
+
+    // Generate the vlist
+    let mut children = vec![VListElem::shifted(upper_group, upper_shift), arrow_body];
+    if let Some(lower_group) = lower_group {
+        let lower_shift = -axis_height + lower_group.height() + 0.5 * arrow_height + 0.111;
+        children.push(VListElem::shifted(lower_group, lower_shift));
+    }
+    let vlist = make_v_list(VListParam::IndividualShift(children));
+
+    make_span(
+        vec!["mrel".to_string(), "x-arrow".to_string()],
+        vec![vlist.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::XArrow(group) = group else {
+        unreachable!()
+    };
+    let mut arrow_node = stretchy::math_ml_node(&group.label);
+    arrow_node.set_attribute(
+        "minsize",
+        if group.label.starts_with("\\x") {
+            "1.75em"
+        } else {
+            "3.0em"
+        },
+    );
+
+    let upper_node = padded_node(build_mathml::build_group(Some(&group.body), options));
+    let node = match group.below.as_deref() {
+        Some(below) => {
+            let lower_node = padded_node(build_mathml::build_group(Some(below), options));
+            MathNode::new(
+                MathNodeType::Munderover,
+                vec![arrow_node.into(), lower_node.into(), upper_node.into()],
+                vec![],
+            )
+        }
+        None => MathNode::new(
+            MathNodeType::Mover,
+            vec![arrow_node.into(), upper_node.into()],
+            vec![],
+        ),
+    };
+    node.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optional_below_label() {
+        let tree = parse_tree("\\xrightarrow[b]{a}\\xleftarrow{a}", &Settings::default()).unwrap();
+        let [AnyParseNode::XArrow(right), AnyParseNode::XArrow(left)] = &tree[..] else {
+            panic!("expected two xArrow nodes");
+        };
+        assert_eq!(right.label, "\\xrightarrow");
+        assert!(right.below.is_some());
+        assert_eq!(left.label, "\\xleftarrow");
+        assert!(left.below.is_none());
+    }
+
+    #[test]
+    fn layout() {
+        // Labels are stacked above and below the arrow, which sits on the
+        // axis.
+        let bare = build_one("\\xrightarrow{}");
+        let above = build_one("\\xrightarrow{a}");
+        let both = build_one("\\xrightarrow[b]{a}");
+        assert!(above.height() > bare.height() + 0.2);
+        assert_eq!(above.depth(), bare.depth());
+        assert!(both.depth() > above.depth() + 0.2);
+        assert_eq!(both.height(), above.height());
+    }
+
+    #[test]
+    fn markup() {
+        let right = render("\\xrightarrow[b]{a}");
+        assert!(right.contains("<munderover><mo "));
+        assert!(right.contains("<mi>b</mi></mpadded>"));
+        assert!(right.contains("class=\"mrel x-arrow\""));
+        assert!(right.contains("class=\"hide-tail\""));
+
+        let left = render("\\xleftarrow{a}");
+        assert!(left.contains("<mover><mo "));
+        assert!(left.contains("min-width:1.469em"));
+
+        // The arrow stretches to the wider label.
+        let long = render("\\xrightarrow{aaaaaaaaaa}");
+        assert!(long.contains("class=\"mrel x-arrow\""));
+        assert!(long.contains("class=\"sizing reset-size6 size3 mtight x-arrow-pad\""));
+    }
+
+    #[test]
+    fn svg_images_have_paths() {
+        for name in [
+            "\\xleftarrow",
+            "\\xrightarrow",
+            "\\xLeftarrow",
+            "\\xRightarrow",
+            "\\xleftrightarrow",
+            "\\xLeftrightarrow",
+            "\\xhookleftarrow",
+            "\\xhookrightarrow",
+            "\\xmapsto",
+            "\\xrightharpoondown",
+            "\\xrightharpoonup",
+            "\\xleftharpoondown",
+            "\\xleftharpoonup",
+            "\\xrightleftharpoons",
+            "\\xleftrightharpoons",
+            "\\xlongequal",
+            "\\xtwoheadrightarrow",
+            "\\xtwoheadleftarrow",
+            "\\xtofrom",
+            "\\xrightleftarrows",
+            "\\xrightequilibrium",
+            "\\xleftequilibrium",
+        ] {
+            let markup = render(&format!("{}{{x}}", name));
+            assert!(markup.contains("<path d=\"M"), "{}", name);
+            assert!(!markup.contains("<path d=\"\"/>"), "{}", name);
+        }
+    }
+}
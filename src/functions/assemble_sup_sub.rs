@@ -0,0 +1,135 @@
+use super::*;
+
+struct Limit {
+    elem: VirtualNode,
+    kern: f64,
+}
+
+/// For an operator with limits, assemble the base, sup, and sub into a span.
+pub fn assemble_sup_sub(
+    base: VirtualNode,
+    sup_group: Option<&AnyParseNode>,
+    sub_group: Option<&AnyParseNode>,
+    options: &Options,
+    style: Style,
+    slant: f64,
+    base_shift: f64,
+) -> VirtualNode {
+    let base = make_span(vec![], vec![base], None, None);
+    let sub_is_single_character = sub_group.is_some_and(utils::is_character_box);
+    let metrics = options.font_metrics();
+    // We manually have to handle the superscripts and subscripts. This,
+    // aside from the kern calculations, is copied from supsub.
+    let sup = sup_group.map(|sup_group| {
+        let elem = build_html::build_group(
+            Some(sup_group),
+            &options.having_style(style.sup()),
+            Some(options),
+        );
+        let kern = metrics
+            .big_op_spacing1
+            .max(metrics.big_op_spacing3 - elem.depth());
+        Limit { elem, kern }
+    });
+
+    let sub = sub_group.map(|sub_group| {
+        let elem = build_html::build_group(
+            Some(sub_group),
+            &options.having_style(style.sub()),
+            Some(options),
+        );
+        let kern = metrics
+            .big_op_spacing2
+            .max(metrics.big_op_spacing4 - elem.height());
+        Limit { elem, kern }
+    });
+
+    // Build the final group as a vlist of the possible subscript, base,
+    // and possible superscript.
+    let has_sub = sub.is_some();
+    let final_group = match (sup, sub) {
+        (Some(sup), Some(sub)) => {
+            let bottom = metrics.big_op_spacing5
+                + sub.elem.height()
+                + sub.elem.depth()
+                + sub.kern
+                + base.depth
+                + base_shift;
+
+            let mut sub_elem = VListElem::new(sub.elem);
+            sub_elem.margin_left = Some(make_em(-slant));
+            let mut sup_elem = VListElem::new(sup.elem);
+            sup_elem.margin_left = Some(make_em(slant));
+            make_v_list(VListParam::Bottom(
+                bottom,
+                vec![
+                    VListChild::Kern(metrics.big_op_spacing5),
+                    sub_elem.into(),
+                    VListChild::Kern(sub.kern),
+                    VListElem::new(base).into(),
+                    VListChild::Kern(sup.kern),
+                    sup_elem.into(),
+                    VListChild::Kern(metrics.big_op_spacing5),
+                ],
+            ))
+        }
+        (None, Some(sub)) => {
+            let top = base.height - base_shift;
+
+            // Shift the limits by the slant of the symbol. Note
+            // that we are supposed to shift the limits by 1/2 of the slant,
+            // but since we are centering the limits adding a full slant of
+            // margin will shift by 1/2 that.
+            let mut sub_elem = VListElem::new(sub.elem);
+            sub_elem.margin_left = Some(make_em(-slant));
+            make_v_list(VListParam::Top(
+                top,
+                vec![
+                    VListChild::Kern(metrics.big_op_spacing5),
+                    sub_elem.into(),
+                    VListChild::Kern(sub.kern),
+                    VListElem::new(base).into(),
+                ],
+            ))
+        }
+        (Some(sup), None) => {
+            let bottom = base.depth + base_shift;
+
+            let mut sup_elem = VListElem::new(sup.elem);
+            sup_elem.margin_left = Some(make_em(slant));
+            make_v_list(VListParam::Bottom(
+                bottom,
+                vec![
+                    VListElem::new(base).into(),
+                    VListChild::Kern(sup.kern),
+                    sup_elem.into(),
+                    VListChild::Kern(metrics.big_op_spacing5),
+                ],
+            ))
+        }
+        (None, None) => {
+            // This case probably shouldn't occur (this would mean the
+            // supsub was sending us a group with no superscript or
+            // subscript) but be safe.
+            return base.into();
+        }
+    };
+
+    let mut parts = vec![final_group.into()];
+    if has_sub && slant != 0.0 && !sub_is_single_character {
+        // A negative margin-left was applied to the lower limit.
+        // Avoid an overlap by placing a spacer on the left on the group.
+        let mut spacer = make_span(vec!["mspace".to_string()], vec![], Some(options), None);
+        spacer
+            .style
+            .insert("marginRight".to_string(), make_em(slant));
+        parts.insert(0, spacer.into());
+    }
+    make_span(
+        vec!["mop".to_string(), "op-limits".to_string()],
+        parts,
+        Some(options),
+        None,
+    )
+    .into()
+}
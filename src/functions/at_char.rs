@@ -0,0 +1,189 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // \@char is an internal function that takes a grouped decimal argument like
+    // {123} and converts into symbol with code 123.  It is used by the *macro*
+    // \char defined in macros.rs.
+    registry.define_function(FunctionDefSpec {
+        node_type: "textord",
+        names: &["\\@char"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // \@mathchar is the same for the *macro* \mathchar: it takes the math
+    // code as a grouped decimal argument and makes the symbol at that
+    // position of the family's font, in the encoded math class.
+    registry.define_function(FunctionDefSpec {
+        node_type: "atom",
+        names: &["\\@mathchar"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(mathchar_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+/// Read the decimal number in the grouped argument of `func_name`.
+fn number_argument(arg: &AnyParseNode, func_name: &str) -> Result<(u32, String), ParseError> {
+    let AnyParseNode::OrdGroup(arg) = arg else {
+        return Err(ParseError::new("Expected node of type ordgroup", None));
+    };
+    let mut number = String::new();
+    for node in &arg.body {
+        let AnyParseNode::TextOrd(node) = node else {
+            return Err(ParseError::new("Expected node of type textord", None));
+        };
+        number += &node.text;
+    }
+    let code = number.parse::<u32>().map_err(|_| {
+        ParseError::new(
+            &format!("{} has non-numeric argument {}", func_name, number),
+            None,
+        )
+    })?;
+    Ok((code, number))
+}
+
+fn handler(
+    context: FunctionContext,
+    args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let (code, number) = number_argument(&args[0], "\\@char")?;
+    let text = char::from_u32(code).ok_or_else(|| {
+        ParseError::new(&format!("\\@char with invalid code point {}", number), None)
+    })?;
+    Ok(AnyParseNode::TextOrd(TextOrdParseNode {
+        type_: "textord".into(),
+        mode: context.parser.mode.to_string(),
+        loc: None,
+        text: text.to_string(),
+    }))
+}
+
+// The fonts of plain TeX's math families 0 to 3 (cmr, cmmi, cmsy and cmex),
+// by position. Positions holding only a piece of a symbol, like the hooks of
+// \hookrightarrow, are '\0'. The calligraphic capitals of cmsy are given as
+// their Latin letters.
+const CMR: [char; 128] = [
+    'Γ', 'Δ', 'Θ', 'Λ', 'Ξ', 'Π', 'Σ', 'Υ', 'Φ', 'Ψ', 'Ω', 'ﬀ', 'ﬁ', 'ﬂ', 'ﬃ', 'ﬄ',
+    'ı', 'ȷ', '`', '´', 'ˇ', '˘', '¯', '˚', '¸', 'ß', 'æ', 'œ', 'ø', 'Æ', 'Œ', 'Ø',
+    '\0', '!', '”', '#', '$', '%', '&', '’', '(', ')', '*', '+', ',', '-', '.', '/',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '¡', '=', '¿', '?',
+    '@', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '[', '“', ']', 'ˆ', '˙',
+    '‘', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '–', '—', '˝', '˜', '¨',
+];
+
+const CMMI: [char; 128] = [
+    'Γ', 'Δ', 'Θ', 'Λ', 'Ξ', 'Π', 'Σ', 'Υ', 'Φ', 'Ψ', 'Ω', 'α', 'β', 'γ', 'δ', 'ϵ',
+    'ζ', 'η', 'θ', 'ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'π', 'ρ', 'σ', 'τ', 'υ', 'ϕ', 'χ',
+    'ψ', 'ω', 'ε', 'ϑ', 'ϖ', 'ϱ', 'ς', 'φ', '↼', '↽', '⇀', '⇁', '\0', '\0', '▹', '◃',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '.', ',', '<', '/', '>', '⋆',
+    '∂', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '♭', '♮', '♯', '⌣', '⌢',
+    'ℓ', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'ı', 'ȷ', '℘', '\0', '\0',
+];
+
+const CMSY: [char; 128] = [
+    '−', '⋅', '×', '∗', '÷', '⋄', '±', '∓', '⊕', '⊖', '⊗', '⊘', '⊙', '◯', '∘', '∙',
+    '≍', '≡', '⊆', '⊇', '≤', '≥', '⪯', '⪰', '∼', '≈', '⊂', '⊃', '≪', '≫', '≺', '≻',
+    '←', '→', '↑', '↓', '↔', '↗', '↘', '≃', '⇐', '⇒', '⇑', '⇓', '⇔', '↖', '↙', '∝',
+    '′', '∞', '∈', '∋', '△', '▽', '\0', '\0', '∀', '∃', '¬', '∅', 'ℜ', 'ℑ', '⊤', '⊥',
+    'ℵ', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '∪', '∩', '⊎', '∧', '∨',
+    '⊢', '⊣', '⌊', '⌋', '⌈', '⌉', '{', '}', '⟨', '⟩', '|', '∥', '↕', '⇕', '∖', '≀',
+    '√', '⨿', '∇', '∫', '⊔', '⊓', '⊑', '⊒', '§', '†', '‡', '¶', '♣', '♢', '♡', '♠',
+];
+
+// cmex holds the delimiters in growing sizes and the large operators in text
+// and display sizes; each maps to its Unicode character.
+const CMEX: [char; 128] = [
+    '(', ')', '[', ']', '⌊', '⌋', '⌈', '⌉', '{', '}', '⟨', '⟩', '|', '∥', '/', '∖',
+    '(', ')', '(', ')', '[', ']', '⌊', '⌋', '⌈', '⌉', '{', '}', '⟨', '⟩', '/', '∖',
+    '(', ')', '[', ']', '⌊', '⌋', '⌈', '⌉', '{', '}', '⟨', '⟩', '/', '∖', '/', '∖',
+    '⎛', '⎞', '⎡', '⎤', '⎣', '⎦', '⎢', '⎥', '⎧', '⎫', '⎩', '⎭', '⎨', '⎬', '⎪', '⏐',
+    '⎝', '⎠', '⎜', '⎟', '⟨', '⟩', '⨆', '⨆', '∮', '∮', '⨀', '⨀', '⨁', '⨁', '⨂', '⨂',
+    '∑', '∏', '∫', '⋃', '⋂', '⨄', '⋀', '⋁', '∑', '∏', '∫', '⋃', '⋂', '⨄', '⋀', '⋁',
+    '∐', '∐', '^', '^', '^', '~', '~', '~', '[', ']', '⌊', '⌋', '⌈', '⌉', '{', '}',
+    '√', '√', '√', '√', '√', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+];
+
+fn mathchar_handler(
+    context: FunctionContext,
+    args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let (code, _) = number_argument(&args[0], "\\@mathchar")?;
+    let (class, family, position) = (code >> 12, (code >> 8) & 0xf, code & 0xff);
+    let font = match family {
+        0 => &CMR,
+        1 => &CMMI,
+        2 => &CMSY,
+        3 => &CMEX,
+        _ => {
+            return Err(ParseError::new(
+                &format!("\\mathchar family {} has no font", family),
+                None,
+            ))
+        }
+    };
+    let text = match font.get(position as usize) {
+        Some(&ch) if ch != '\0' => ch.to_string(),
+        _ => {
+            return Err(ParseError::new(
+                &format!("\\mathchar\"{:04X} has no character", code),
+                None,
+            ))
+        }
+    };
+    let mode = context.parser.mode.to_string();
+    let family = match class {
+        // Class 7 is an ordinary character whose family varies with \fam.
+        0 | 7 => {
+            // Letters of the math italic family are variables; the other
+            // families have upright characters.
+            return Ok(if family == 1 {
+                AnyParseNode::MathOrd(MathOrdParseNode {
+                    type_: "mathord".into(),
+                    mode,
+                    loc: None,
+                    text,
+                })
+            } else {
+                AnyParseNode::TextOrd(TextOrdParseNode {
+                    type_: "textord".into(),
+                    mode,
+                    loc: None,
+                    text,
+                })
+            });
+        }
+        2 => Group::Bin,
+        3 => Group::Rel,
+        4 => Group::Open,
+        5 => Group::Close,
+        6 => Group::Punct,
+        // Class 1 is a large operator, with limits in display style.
+        _ => return Ok(op::mathchar_op(context.parser, &text)),
+    };
+    Ok(AnyParseNode::Atom(AtomParseNode {
+        type_: "atom".into(),
+        mode,
+        loc: None,
+        family,
+        text,
+    }))
+}
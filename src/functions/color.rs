@@ -0,0 +1,170 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "color",
+        names: &["\\textcolor"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            allowed_in_text: true,
+            arg_types: Some(vec![ArgType::Color, ArgType::Original]),
+            ..Default::default()
+        },
+        handler: Some(textcolor_handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "color",
+        names: &["\\color"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_text: true,
+            arg_types: Some(vec![ArgType::Color]),
+            ..Default::default()
+        },
+        handler: Some(color_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Color(group) = group else {
+        unreachable!()
+    };
+    // \color is a mode switch scoped to the current group.
+    let elements = build_html::build_expression(
+        &group.body,
+        &options.with_color(&group.color),
+        false,
+        false,
+        [None, None],
+    );
+
+    // \color isn't supposed to affect the type of the elements it contains.
+    // To accomplish this, we wrap the results in a fragment, so the inner
+    // elements will be able to directly interact with their neighbors. For
+    // example, `\color{red}{2 +} 3` has the same spacing as `2 + 3`
+    make_fragment(elements).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Color(group) = group else {
+        unreachable!()
+    };
+    let inner =
+        build_mathml::build_expression(&group.body, &options.with_color(&group.color), false);
+
+    let mut node = MathNode::new(MathNodeType::Mstyle, inner, vec![]);
+
+    node.set_attribute("mathcolor", &group.color);
+
+    node.into()
+}
+
+fn textcolor_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(1);
+    let AnyParseNode::ColorToken(color) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type color-token", None));
+    };
+    Ok(AnyParseNode::Color(ColorParseNode {
+        type_: "color".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        color: color.color,
+        body: ordargument(body),
+    }))
+}
+
+fn color_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser,
+        break_on_token_text,
+        ..
+    } = context;
+    let AnyParseNode::ColorToken(color) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type color-token", None));
+    };
+
+    // Set macro \current@color in current namespace to store the current
+    // color, mimicking the behavior of color.sty.
+    // This is currently used just to correctly color a \right
+    // that follows a \color command.
+    parser
+        .gullet
+        .macros
+        .set("\\current@color", Some(color.color.as_str().into()), false);
+
+    // Parse out the implicit body that should be colored.
+    let body = parser.parse_expression(true, break_on_token_text.as_deref())?;
+
+    Ok(AnyParseNode::Color(ColorParseNode {
+        type_: "color".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        color: color.color,
+        body,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_color(input: &str) -> ColorParseNode {
+        let AnyParseNode::Color(node) = parse_one(input) else {
+            panic!("expected a color node for {}", input);
+        };
+        node
+    }
+
+    #[test]
+    fn color_takes_the_rest_of_the_group() {
+        let color = parse_color("\\color{red} a + b");
+        assert_eq!(color.color, "red");
+        assert_eq!(color.body.len(), 3);
+
+        let tree = parse_tree("{\\color{blue} a} b", &Settings::default()).unwrap();
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn textcolor_takes_an_argument() {
+        let color = parse_color("\\textcolor{#00f}{x}");
+        assert_eq!(color.color, "#00f");
+        assert_eq!(color.body.len(), 1);
+
+        assert!(parse_tree("\\textcolor{bad color}{x}", &Settings::default()).is_err());
+
+        // Names must be colors CSS knows, in any case.
+        assert_eq!(parse_color("\\color{RoyalBlue} x").color, "RoyalBlue");
+        let error = parse_tree("\\color{notacolor} x", &Settings::default()).unwrap_err();
+        assert_eq!(error.raw_message(), "Invalid color: 'notacolor'");
+    }
+
+    #[test]
+    fn layout() {
+        // A color change leaves the size of its body alone.
+        let colored = build_one("\\textcolor{red}{\\frac{a}{b}}");
+        let plain = build_one("\\frac{a}{b}");
+        assert_eq!((colored.height(), colored.depth()), (plain.height(), plain.depth()));
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\textcolor{red}{x}");
+        assert!(markup.contains("color:red;"));
+        assert!(markup.contains("mathcolor=\"red\""));
+    }
+}
@@ -0,0 +1,271 @@
+//! Macro definitions and assignments: \def, \gdef, \edef, \xdef, \let,
+//! \futurelet and the \global and \long prefixes.
+use super::*;
+
+/// The global version of each assignment, used after a \global prefix.
+fn global_map(name: &str) -> Option<&'static str> {
+    match name {
+        "\\global" => Some("\\global"),
+        "\\long" | "\\\\globallong" => Some("\\\\globallong"),
+        "\\def" | "\\gdef" => Some("\\gdef"),
+        "\\edef" | "\\xdef" => Some("\\xdef"),
+        "\\let" => Some("\\\\globallet"),
+        "\\futurelet" => Some("\\\\globalfuture"),
+        _ => None,
+    }
+}
+
+fn check_control_sequence(tok: &Token) -> Result<String, ParseError> {
+    let name = &tok.text;
+    if regex!(r"^(?:[\\{}$&#^_]|EOF)$").is_match(name) {
+        return Err(ParseError::new("Expected a control sequence", Some(tok)));
+    }
+    Ok(name.clone())
+}
+
+fn get_rhs(parser: &mut Parser) -> Result<Token, ParseError> {
+    let mut tok = parser.gullet.pop_token()?;
+    if tok.text == "=" {
+        // consume optional equals
+        tok = parser.gullet.pop_token()?;
+        if tok.text == " " {
+            // consume one optional space
+            tok = parser.gullet.pop_token()?;
+        }
+    }
+    Ok(tok)
+}
+
+fn let_command(parser: &mut Parser, name: &str, mut tok: Token, global: bool) {
+    let definition = match parser.gullet.macros.get(&tok.text) {
+        Some(definition) => definition.clone(),
+        None => {
+            // don't expand it later even if a macro with the same name is defined
+            // e.g., \let\foo=\frac \def\frac{\relax} \frac12
+            tok.noexpand = Some(true);
+            let unexpandable = !parser.gullet.is_expandable(&tok.text);
+            MacroDefinition::Expansion(MacroExpansion {
+                tokens: vec![tok],
+                num_args: 0,
+                delimiters: None,
+                unexpandable,
+            })
+        }
+    };
+    parser.gullet.macros.set(name, Some(definition), global);
+}
+
+fn internal(parser: &Parser) -> AnyParseNode {
+    AnyParseNode::Internal(InternalParseNode {
+        type_: "internal".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+    })
+}
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // <assignment> -> <non-macro assignment>|<macro assignment>
+    // <non-macro assignment> -> <simple assignment>|\global<non-macro assignment>
+    // <macro assignment> -> <definition>|<prefix><macro assignment>
+    // <prefix> -> \global|\long|\outer
+    registry.define_function(FunctionDefSpec {
+        node_type: "internal",
+        names: &["\\global", "\\long", "\\\\globallong"], // can’t be entered directly
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(prefix_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // Basic support for macro definitions: \def, \gdef, \edef, \xdef
+    // <definition> -> <def><control sequence><definition text>
+    // <def> -> \def|\gdef|\edef|\xdef
+    // <definition text> -> <parameter text><left brace><balanced text><right brace>
+    registry.define_function(FunctionDefSpec {
+        node_type: "internal",
+        names: &["\\def", "\\gdef", "\\edef", "\\xdef"],
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(def_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // <simple assignment> -> <let assignment>
+    // <let assignment> -> \futurelet<control sequence><token><token>
+    //     | \let<control sequence><equals><one optional space><token>
+    // <equals> -> <optional spaces>|<optional spaces>=
+    registry.define_function(FunctionDefSpec {
+        node_type: "internal",
+        names: &["\\let", "\\\\globallet"], // can’t be entered directly
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(let_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // ref: https://www.tug.org/TUGboat/tb09-3/tb22bechtolsheim.pdf
+    registry.define_function(FunctionDefSpec {
+        node_type: "internal",
+        names: &["\\futurelet", "\\\\globalfuture"], // can’t be entered directly
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(futurelet_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn prefix_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    parser.consume_spaces()?;
+    let token = parser.fetch()?.clone();
+    let Some(global) = global_map(&token.text) else {
+        return Err(ParseError::new(
+            "Invalid token after macro prefix",
+            Some(&token),
+        ));
+    };
+    // KaTeX doesn't have \par, so ignore \long
+    if func_name == "\\global" || func_name == "\\\\globallong" {
+        if let Some(next_token) = parser.next_token.as_mut() {
+            next_token.text = global.to_string();
+        }
+    }
+    match parser.parse_function(None, None)? {
+        Some(node @ AnyParseNode::Internal(_)) => Ok(node),
+        _ => Err(ParseError::new("Expected node of type internal", Some(&token))),
+    }
+}
+
+fn def_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let mut tok = parser.gullet.pop_token()?;
+    let name = check_control_sequence(&tok)?;
+    let mut num_args = 0;
+    let mut insert = None;
+    let mut delimiters: Vec<Vec<String>> = vec![vec![]];
+    // <parameter text> contains no braces
+    while parser.gullet.future()?.text != "{" {
+        tok = parser.gullet.pop_token()?;
+        if tok.text == "#" {
+            // If the very last character of the <parameter text> is #, so that
+            // this # is immediately followed by {, TeX will behave as if the {
+            // had been inserted at the right end of both the parameter text
+            // and the replacement text.
+            if parser.gullet.future()?.text == "{" {
+                insert = Some(parser.gullet.future()?);
+                delimiters[num_args].push("{".into());
+                break;
+            }
+
+            // A parameter, the first appearance of # must be followed by 1,
+            // the next by 2, and so on; up to nine #’s are allowed
+            tok = parser.gullet.pop_token()?;
+            if !regex!(r"^[1-9]$").is_match(&tok.text) {
+                return Err(ParseError::new(
+                    &format!("Invalid argument number \"{}\"", tok.text),
+                    Some(&tok),
+                ));
+            }
+            if tok.text.parse::<usize>().ok() != Some(num_args + 1) {
+                return Err(ParseError::new(
+                    &format!("Argument number \"{}\" out of order", tok.text),
+                    Some(&tok),
+                ));
+            }
+            num_args += 1;
+            delimiters.push(vec![]);
+        } else if tok.text == "EOF" {
+            return Err(ParseError::new("Expected a macro definition", Some(&tok)));
+        } else {
+            delimiters[num_args].push(tok.text.clone());
+        }
+    }
+    // replacement text, enclosed in '{' and '}' and properly nested
+    let (_, mut tokens, _) = parser.gullet.consume_arg(None)?;
+    if let Some(insert) = insert {
+        // tokens are in reverse order, so the inserted { goes at the end
+        tokens.push(insert);
+    }
+
+    if func_name == "\\edef" || func_name == "\\xdef" {
+        tokens = parser.gullet.expand_tokens(tokens)?;
+        tokens.reverse(); // to fit in with stack order
+    }
+    // Final arg is the expansion of the macro
+    let global = global_map(&func_name) == Some(func_name.as_str());
+    parser.gullet.macros.set(
+        &name,
+        Some(MacroDefinition::Expansion(MacroExpansion {
+            tokens,
+            num_args,
+            delimiters: Some(delimiters),
+            unexpandable: false,
+        })),
+        global,
+    );
+
+    Ok(internal(parser))
+}
+
+fn let_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let name = check_control_sequence(&parser.gullet.pop_token()?)?;
+    parser.gullet.consume_spaces()?;
+    let tok = get_rhs(parser)?;
+    let_command(parser, &name, tok, func_name == "\\\\globallet");
+    Ok(internal(parser))
+}
+
+fn futurelet_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let name = check_control_sequence(&parser.gullet.pop_token()?)?;
+    let middle = parser.gullet.pop_token()?;
+    let tok = parser.gullet.pop_token()?;
+    let_command(parser, &name, tok.clone(), func_name == "\\\\globalfuture");
+    parser.gullet.push_token(tok);
+    parser.gullet.push_token(middle);
+    Ok(internal(parser))
+}
@@ -0,0 +1,590 @@
+use super::*;
+
+/// Extra data needed for the delimiter handler down below: the math class and
+/// size each sizing command produces.
+fn delimiter_sizes(func_name: &str) -> (&'static str, i64) {
+    match func_name {
+        "\\bigl" => ("mopen", 1),
+        "\\Bigl" => ("mopen", 2),
+        "\\biggl" => ("mopen", 3),
+        "\\Biggl" => ("mopen", 4),
+        "\\bigr" => ("mclose", 1),
+        "\\Bigr" => ("mclose", 2),
+        "\\biggr" => ("mclose", 3),
+        "\\Biggr" => ("mclose", 4),
+        "\\bigm" => ("mrel", 1),
+        "\\Bigm" => ("mrel", 2),
+        "\\biggm" => ("mrel", 3),
+        "\\Biggm" => ("mrel", 4),
+        "\\big" => ("mord", 1),
+        "\\Big" => ("mord", 2),
+        "\\bigg" => ("mord", 3),
+        "\\Bigg" => ("mord", 4),
+        _ => unreachable!(),
+    }
+}
+
+const DELIMITERS: &[&str] = &[
+    "(",
+    "\\lparen",
+    ")",
+    "\\rparen",
+    "[",
+    "\\lbrack",
+    "]",
+    "\\rbrack",
+    "\\{",
+    "\\lbrace",
+    "\\}",
+    "\\rbrace",
+    "\\lfloor",
+    "\\rfloor",
+    "\u{230a}",
+    "\u{230b}",
+    "\\lceil",
+    "\\rceil",
+    "\u{2308}",
+    "\u{2309}",
+    "<",
+    ">",
+    "\\langle",
+    "\u{27e8}",
+    "\\rangle",
+    "\u{27e9}",
+    "\\lt",
+    "\\gt",
+    "\\lvert",
+    "\\rvert",
+    "\\lVert",
+    "\\rVert",
+    "\\lgroup",
+    "\\rgroup",
+    "\u{27ee}",
+    "\u{27ef}",
+    "\\lmoustache",
+    "\\rmoustache",
+    "\u{23b0}",
+    "\u{23b1}",
+    "/",
+    "\\backslash",
+    "|",
+    "\\vert",
+    "\\|",
+    "\\Vert",
+    "\\uparrow",
+    "\\Uparrow",
+    "\\downarrow",
+    "\\Downarrow",
+    "\\updownarrow",
+    "\\Updownarrow",
+    ".",
+];
+
+/// Delimiter functions
+fn check_delimiter(delim: &AnyParseNode, func_name: &str) -> Result<String, ParseError> {
+    match delim.symbol_text() {
+        Some(text) if DELIMITERS.contains(&text) => Ok(text.to_string()),
+        Some(text) => Err(ParseError::new(
+            &format!("Invalid delimiter '{}' after '{}'", text, func_name),
+            None,
+        )),
+        None => Err(ParseError::new(
+            &format!("Invalid delimiter type '{}'", delim.type_()),
+            None,
+        )),
+    }
+}
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "delimsizing",
+        names: &[
+            "\\bigl", "\\Bigl", "\\biggl", "\\Biggl", "\\bigr", "\\Bigr", "\\biggr", "\\Biggr",
+            "\\bigm", "\\Bigm", "\\biggm", "\\Biggm", "\\big", "\\Big", "\\bigg", "\\Bigg",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Primitive]),
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "leftright-right",
+        names: &["\\right"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(right_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "leftright",
+        names: &["\\left"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(left_handler),
+        html_builder: Some(left_right_html_builder),
+        mathml_builder: Some(left_right_mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "middle",
+        names: &["\\middle"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(middle_handler),
+        html_builder: Some(middle_html_builder),
+        mathml_builder: Some(middle_mathml_builder),
+    });
+}
+
+fn handler(
+    context: FunctionContext,
+    args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let delim = check_delimiter(&args[0], &func_name)?;
+    let (mclass, size) = delimiter_sizes(&func_name);
+
+    Ok(AnyParseNode::DelimSizing(DelimSizingParseNode {
+        type_: "delimsizing".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        size,
+        mclass: mclass.into(),
+        delim,
+    }))
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::DelimSizing(group) = group else {
+        unreachable!()
+    };
+    if group.delim == "." {
+        // Empty delimiters still count as elements, even though they don't
+        // show anything.
+        return make_span(vec![group.mclass.clone()], vec![], None, None).into();
+    }
+
+    // Use delimiter::make_sized_delim to generate the delimiter.
+    delimiter::make_sized_delim(
+        &group.delim,
+        group.size as usize,
+        options,
+        Mode::from(group.mode.as_str()),
+        vec![group.mclass.clone()],
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, _options: &Options) -> MathDomNode {
+    let AnyParseNode::DelimSizing(group) = group else {
+        unreachable!()
+    };
+    let mut children = vec![];
+    if group.delim != "." {
+        children.push(
+            build_mathml::make_text(&group.delim, Mode::from(group.mode.as_str()), None).into(),
+        );
+    }
+
+    let mut node = MathNode::new(MathNodeType::Mo, children, vec![]);
+
+    if group.mclass == "mopen" || group.mclass == "mclose" {
+        // Only some of the delimsizing functions act as fences, and they
+        // return "mopen" or "mclose" mclass.
+        node.set_attribute("fence", "true");
+    } else {
+        // Explicitly disable fencing if it's not a fence, to override the
+        // defaults.
+        node.set_attribute("fence", "false");
+    }
+
+    node.set_attribute("stretchy", "true");
+    let size = make_em(delimiter::SIZE_TO_MAX_HEIGHT[group.size as usize]);
+    node.set_attribute("minsize", &size);
+    node.set_attribute("maxsize", &size);
+
+    node.into()
+}
+
+fn right_handler(
+    context: FunctionContext,
+    args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    // \left case below triggers parsing of \right in
+    //   `let right = parser.parse_function(None, None)?;`
+    // uses this return value.
+    let color = match parser.gullet.macros.get("\\current@color") {
+        None => None,
+        Some(MacroDefinition::Text(color)) => Some(color.clone()),
+        Some(_) => {
+            return Err(ParseError::new(
+                "\\current@color set to non-string in \\right",
+                None,
+            ));
+        }
+    };
+
+    Ok(AnyParseNode::LeftRightRight(LeftRightRightParseNode {
+        type_: "leftright-right".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        delim: check_delimiter(&args[0], &func_name)?,
+        color, // None if not set via \color
+    }))
+}
+
+fn left_handler(
+    context: FunctionContext,
+    args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let delim = check_delimiter(&args[0], &func_name)?;
+    // Parse out the implicit body
+    parser.leftright_depth += 1;
+    // parse_expression stops before '\\right'
+    let body = parser.parse_expression(false, None);
+    parser.leftright_depth -= 1;
+    let body = body?;
+    // Check the next token
+    parser.expect("\\right", false)?;
+    let Some(AnyParseNode::LeftRightRight(right)) = parser.parse_function(None, None)? else {
+        return Err(ParseError::new(
+            "Expected node of type leftright-right",
+            None,
+        ));
+    };
+
+    Ok(AnyParseNode::LeftRight(LeftRightParseNode {
+        type_: "leftright".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body,
+        left: delim,
+        right: right.delim,
+        right_color: right.color,
+    }))
+}
+
+fn left_right_html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::LeftRight(group) = group else {
+        unreachable!()
+    };
+    let mode = Mode::from(group.mode.as_str());
+    // Build the inner expression
+    let mut inner = build_html::build_expression(
+        &group.body,
+        options,
+        true,
+        false,
+        [Some("mopen"), Some("mclose")],
+    );
+
+    let mut inner_height: f64 = 0.0;
+    let mut inner_depth: f64 = 0.0;
+    let mut had_middle = false;
+
+    // Calculate its height and depth
+    for node in &inner {
+        if matches!(node, VirtualNode::Span(span) if span.is_middle.is_some()) {
+            had_middle = true;
+        } else {
+            inner_height = inner_height.max(node.height());
+            inner_depth = inner_depth.max(node.depth());
+        }
+    }
+
+    // The size of delimiters is the same, regardless of what style we are
+    // in. Thus, to correctly calculate the size of delimiter we need around
+    // a group, we scale down the inner size based on the size.
+    inner_height *= options.size_multiplier;
+    inner_depth *= options.size_multiplier;
+
+    let left_delim = if group.left == "." {
+        // Empty delimiters in \left and \right make null delimiter spaces.
+        build_html::make_null_delimiter(options, vec!["mopen".to_string()])
+    } else {
+        // Otherwise, use make_left_right_delim to generate the correct sized
+        // delimiter.
+        delimiter::make_left_right_delim(
+            &group.left,
+            inner_height,
+            inner_depth,
+            options,
+            mode,
+            vec!["mopen".to_string()],
+        )
+    };
+
+    // Handle middle delimiters
+    if had_middle {
+        for node in inner.iter_mut() {
+            let VirtualNode::Span(span) = node else {
+                continue;
+            };
+            if let Some(is_middle) = &span.is_middle {
+                // Apply the options that were active when \middle was called
+                *node = delimiter::make_left_right_delim(
+                    &is_middle.delim,
+                    inner_height,
+                    inner_depth,
+                    &is_middle.options,
+                    mode,
+                    vec![],
+                )
+                .into();
+            }
+        }
+    }
+
+    // Add the left delimiter to the beginning of the expression
+    inner.insert(0, left_delim.into());
+
+    // Same for the right delimiter, but using color specified by \color
+    let right_delim = if group.right == "." {
+        build_html::make_null_delimiter(options, vec!["mclose".to_string()])
+    } else {
+        let color_options = match &group.right_color {
+            Some(color) => options.with_color(color),
+            None => options.clone(),
+        };
+        delimiter::make_left_right_delim(
+            &group.right,
+            inner_height,
+            inner_depth,
+            &color_options,
+            mode,
+            vec!["mclose".to_string()],
+        )
+    };
+    // Add it to the end of the expression.
+    inner.push(right_delim.into());
+
+    make_span(vec!["minner".to_string()], inner, Some(options), None).into()
+}
+
+fn left_right_mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::LeftRight(group) = group else {
+        unreachable!()
+    };
+    let mode = Mode::from(group.mode.as_str());
+    let mut inner = build_mathml::build_expression(&group.body, options, false);
+
+    if group.left != "." {
+        let mut left_node = MathNode::new(
+            MathNodeType::Mo,
+            vec![build_mathml::make_text(&group.left, mode, None).into()],
+            vec![],
+        );
+        left_node.set_attribute("fence", "true");
+        inner.insert(0, left_node.into());
+    }
+
+    if group.right != "." {
+        let mut right_node = MathNode::new(
+            MathNodeType::Mo,
+            vec![build_mathml::make_text(&group.right, mode, None).into()],
+            vec![],
+        );
+        right_node.set_attribute("fence", "true");
+        if let Some(right_color) = &group.right_color {
+            right_node.set_attribute("mathcolor", right_color);
+        }
+        inner.push(right_node.into());
+    }
+
+    build_mathml::make_row(inner)
+}
+
+fn middle_handler(
+    context: FunctionContext,
+    args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let delim = check_delimiter(&args[0], &func_name)?;
+    if parser.leftright_depth == 0 {
+        return Err(ParseError::new("\\middle without preceding \\left", None));
+    }
+
+    Ok(AnyParseNode::Middle(MiddleParseNode {
+        type_: "middle".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        delim,
+    }))
+}
+
+fn middle_html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Middle(group) = group else {
+        unreachable!()
+    };
+    if group.delim == "." {
+        return build_html::make_null_delimiter(options, vec![]).into();
+    }
+
+    let mut middle_delim = delimiter::make_sized_delim(
+        &group.delim,
+        1,
+        options,
+        Mode::from(group.mode.as_str()),
+        vec![],
+    );
+    // The enclosing \left...\right replaces this delimiter with one sized to
+    // its body.
+    middle_delim.is_middle = Some(Box::new(IsMiddle {
+        delim: group.delim.clone(),
+        options: options.clone(),
+    }));
+    middle_delim.into()
+}
+
+fn middle_mathml_builder(group: &AnyParseNode, _options: &Options) -> MathDomNode {
+    let AnyParseNode::Middle(group) = group else {
+        unreachable!()
+    };
+    // A Firefox \middle will stretch a character vertically only if it
+    // is in the fence part of the operator dictionary at:
+    // https://www.w3.org/TR/MathML3/appendixc.html.
+    // So we need to avoid U+2223 and use plain "|" instead.
+    let text_node = if group.delim == "\\vert" || group.delim == "|" {
+        build_mathml::make_text("|", Mode::Text, None)
+    } else {
+        build_mathml::make_text(&group.delim, Mode::from(group.mode.as_str()), None)
+    };
+    let mut middle_node = MathNode::new(MathNodeType::Mo, vec![text_node.into()], vec![]);
+    middle_node.set_attribute("fence", "true");
+    // MathML gives 5/18em spacing to each <mo> element.
+    // \middle should get delimiter spacing instead.
+    middle_node.set_attribute("lspace", "0.05em");
+    middle_node.set_attribute("rspace", "0.05em");
+    middle_node.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sized_delimiters() {
+        let AnyParseNode::DelimSizing(big) = parse_one("\\Bigl(") else {
+            panic!("expected a delimsizing node");
+        };
+        assert_eq!((big.size, big.mclass.as_str(), big.delim.as_str()), (2, "mopen", "("));
+
+        let AnyParseNode::DelimSizing(big) = parse_one("\\biggm|") else {
+            panic!("expected a delimsizing node");
+        };
+        assert_eq!((big.size, big.mclass.as_str()), (3, "mrel"));
+
+        let error = parse_tree("\\big x", &Settings::default()).unwrap_err();
+        assert_eq!(error.raw_message(), "Invalid delimiter 'x' after '\\big'");
+    }
+
+    #[test]
+    fn left_right_and_middle() {
+        let AnyParseNode::LeftRight(node) = parse_one("\\left( a \\middle| b \\right.") else {
+            panic!("expected a leftright node");
+        };
+        assert_eq!((node.left.as_str(), node.right.as_str()), ("(", "."));
+        assert!(matches!(&node.body[..], [_, AnyParseNode::Middle(_), _]));
+
+        let AnyParseNode::LeftRight(node) = parse_one("\\left[x\\color{red}\\right]") else {
+            panic!("expected a leftright node");
+        };
+        assert_eq!(node.right_color.as_deref(), Some("red"));
+
+        for (input, message) in [
+            ("\\left( x", "Expected '\\right', got 'EOF'"),
+            ("x \\middle|", "\\middle without preceding \\left"),
+        ] {
+            let error = parse_tree(input, &Settings::default()).unwrap_err();
+            assert_eq!(error.raw_message(), message);
+        }
+        assert!(parse_tree("x \\right)", &Settings::default()).is_err());
+    }
+
+    #[test]
+    fn layout() {
+        let total = |expr| {
+            let node = build_one(expr);
+            node.height() + node.depth()
+        };
+        let sizes = ["\\bigl(", "\\Bigl(", "\\biggl(", "\\Biggl("].map(total);
+        assert!(sizes.windows(2).all(|pair| pair[1] > pair[0] + 0.5));
+        assert!(sizes[0] > total("(") + 0.1);
+
+        // A delimiter is centered on the axis, so it reaches below the baseline
+        // to cover a tall body.
+        assert_eq!(build_one("\\rule{1em}{4em}").depth(), 0.0);
+        let node = build_one("\\left(\\rule{1em}{4em}\\right)");
+        assert!(node.depth() > 3.0);
+        assert!(node.height() >= 4.0);
+    }
+
+    #[test]
+    fn every_delimiter_stretches() {
+        for delim in DELIMITERS {
+            for body in ["x", "\\rule{1em}{2em}", "\\rule{1em}{20em}"] {
+                let expr = format!("\\left{d} {b} \\middle{d} {b} \\right{d}", d = delim, b = body);
+                let markup = render(&expr);
+                assert!(!markup.contains("NaN"), "{}", expr);
+            }
+        }
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\big(");
+        assert!(markup.contains("delimsizing size1"));
+        assert!(markup.contains("minsize=\"1.2em\""));
+
+        let markup = render("\\left(\\dfrac{a}{b}\\right)");
+        assert!(markup.contains("mopen"));
+        assert!(markup.contains("<mo fence=\"true\">(</mo>"));
+        assert!(markup.contains("delimsizing size2"));
+
+        let markup = render("\\left\\{\\rule{1em}{5em}\\right.");
+        assert!(markup.contains("delimsizinginner"));
+        assert!(markup.contains("<svg"));
+        assert!(markup.contains("nulldelimiter"));
+
+        for expr in [
+            "\\left(x\\middle|y\\right)",
+            "\\left[\\rule{1em}{5em}\\right]",
+            "\\bigm|",
+            "\\binom{n}{k}",
+            "{n \\brace k}",
+            "\\genfrac(]{0pt}{}{a}{b}",
+        ] {
+            assert!(!render(expr).contains("NaN"), "{}", expr);
+        }
+    }
+}
@@ -0,0 +1,508 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "enclose",
+        names: &["\\colorbox"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            allowed_in_text: true,
+            arg_types: Some(vec![ArgType::Color, ArgType::Mode(Mode::Text)]),
+            ..Default::default()
+        },
+        handler: Some(colorbox_handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "enclose",
+        names: &["\\fcolorbox"],
+        props: FunctionPropSpec {
+            num_args: 3,
+            allowed_in_text: true,
+            arg_types: Some(vec![
+                ArgType::Color,
+                ArgType::Color,
+                ArgType::Mode(Mode::Text),
+            ]),
+            ..Default::default()
+        },
+        handler: Some(fcolorbox_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "enclose",
+        names: &["\\fbox"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Hbox]),
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // LaTeX defines \boxed as \fbox{$\m@th\displaystyle#1$}: a box around
+    // its body, set as display math.
+    registry.define_function(FunctionDefSpec {
+        node_type: "enclose",
+        names: &["\\boxed"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "enclose",
+        names: &["\\cancel", "\\bcancel", "\\xcancel", "\\sout", "\\phase"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "enclose",
+        names: &["\\angl"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Hbox]),
+            allowed_in_text: false,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Enclose(group) = group else {
+        unreachable!()
+    };
+    // \cancel, \bcancel, \xcancel, \sout, \fbox, \colorbox, \fcolorbox, \phase
+    // Some groups can return document fragments.  Handle those by wrapping
+    // them in a span.
+    let mut inner = if group.label == "\\boxed" {
+        let display_options = options.having_style(Style::DISPLAY);
+        let inner = build_html::build_group(Some(&group.body), &display_options, None);
+        wrap_fragment(inner, &display_options)
+    } else {
+        wrap_fragment(
+            build_html::build_group(Some(&group.body), options, None),
+            options,
+        )
+    };
+
+    // Apart from its display style body, \boxed is an \fbox.
+    let label = match &group.label[1..] {
+        "boxed" => "fbox",
+        label => label,
+    };
+    let mut scale = options.size_multiplier;
+    let mut img;
+    let img_shift;
+
+    // In the LaTeX cancel package, line geometry is slightly different
+    // depending on whether the subject is wider than it is tall, or vice versa.
+    // We don't know the width of a group, so as a proxy, we test if
+    // the subject is a single character. This captures most of the
+    // subjects that should get the "tall" treatment.
+    let is_single_char = utils::is_character_box(&group.body);
+
+    if label == "sout" {
+        img = make_span(
+            vec!["stretchy".to_string(), "sout".to_string()],
+            vec![],
+            None,
+            None,
+        );
+        img.height = options.font_metrics().default_rule_thickness / scale;
+        img_shift = -0.5 * options.font_metrics().x_height;
+    } else if label == "phase" {
+        // Set a couple of dimensions from the steinmetz package.
+        let line_weight = calculate_size(
+            &Measurement {
+                number: 0.6,
+                unit: "pt".to_string(),
+            },
+            options,
+        );
+        let clearance = calculate_size(
+            &Measurement {
+                number: 0.35,
+                unit: "ex".to_string(),
+            },
+            options,
+        );
+
+        // Prevent size changes like \Huge from affecting line thickness
+        let new_options = options.having_base_sizing();
+        scale /= new_options.size_multiplier;
+
+        let angle_height = inner.height() + inner.depth() + line_weight + clearance;
+        // Reserve a left pad for the angle.
+        if let Some(style) = inner.style_mut() {
+            style.insert(
+                "paddingLeft".to_string(),
+                make_em(angle_height / 2.0 + line_weight),
+            );
+        }
+
+        // Create an SVG
+        let view_box_height = (1000.0 * angle_height * scale).floor();
+        let path = svg_geometry::phase_path(view_box_height);
+        let svg_node = SvgNode::new(
+            vec![SvgChildNode::SvgPathNode(PathNode::new(
+                "phase",
+                Some(path),
+            ))],
+            HashMap::from([
+                ("width".to_string(), "400em".to_string()),
+                ("height".to_string(), make_em(view_box_height / 1000.0)),
+                (
+                    "viewBox".to_string(),
+                    format!("0 0 400000 {}", view_box_height),
+                ),
+                (
+                    "preserveAspectRatio".to_string(),
+                    "xMinYMin slice".to_string(),
+                ),
+            ]),
+        );
+        // Wrap it in a span with overflow: hidden.
+        img = make_svg_span(
+            vec!["hide-tail".to_string()],
+            vec![svg_node.into()],
+            Some(options),
+            None,
+        );
+        img.style
+            .insert("height".to_string(), make_em(angle_height));
+        img_shift = inner.depth() + line_weight + clearance;
+    } else {
+        // Add horizontal padding
+        if let Some(classes) = inner.classes_mut() {
+            if label.contains("cancel") {
+                if !is_single_char {
+                    classes.push("cancel-pad".to_string());
+                }
+            } else if label == "angl" {
+                classes.push("anglpad".to_string());
+            } else {
+                classes.push("boxpad".to_string());
+            }
+        }
+
+        // Add vertical padding
+        let top_pad;
+        let bottom_pad;
+        let mut rule_thickness = 0.0;
+        // ref: cancel package: \advance\totalheight2\p@ % "+2"
+        if label.contains("box") {
+            rule_thickness = options
+                .font_metrics()
+                .fboxrule // default
+                .max(options.min_rule_thickness); // User override.
+            top_pad = options.font_metrics().fboxsep
+                + if label == "colorbox" {
+                    0.0
+                } else {
+                    rule_thickness
+                };
+            bottom_pad = top_pad;
+        } else if label == "angl" {
+            rule_thickness = options
+                .font_metrics()
+                .default_rule_thickness
+                .max(options.min_rule_thickness);
+            top_pad = 4.0 * rule_thickness; // gap = 3 × line, plus the line itself.
+            bottom_pad = (0.25 - inner.depth()).max(0.0);
+        } else {
+            top_pad = if is_single_char { 0.2 } else { 0.0 };
+            bottom_pad = top_pad;
+        }
+
+        img = stretchy::enclose_span(&inner, label, top_pad, bottom_pad, options);
+        if label.contains("fbox") || label.contains("boxed") || label.contains("fcolorbox") {
+            img.style
+                .insert("borderStyle".to_string(), "solid".to_string());
+            img.style
+                .insert("borderWidth".to_string(), make_em(rule_thickness));
+        } else if label == "angl" && rule_thickness != 0.049 {
+            img.style
+                .insert("borderTopWidth".to_string(), make_em(rule_thickness));
+            img.style
+                .insert("borderRightWidth".to_string(), make_em(rule_thickness));
+        }
+        img_shift = inner.depth() + bottom_pad;
+
+        if let Some(background_color) = &group.background_color {
+            img.style
+                .insert("backgroundColor".to_string(), background_color.clone());
+            if let Some(border_color) = &group.border_color {
+                img.style
+                    .insert("borderColor".to_string(), border_color.clone());
+            }
+        }
+    }
+
+    let inner_height = inner.height();
+    let inner_depth = inner.depth();
+    let mut vlist = if group.background_color.is_some() {
+        make_v_list(VListParam::IndividualShift(vec![
+            // Put the color background behind inner;
+            VListElem::shifted(img, img_shift),
+            VListElem::shifted(inner, 0.0),
+        ]))
+    } else {
+        let mut img = VListElem::shifted(img, img_shift);
+        if label.contains("cancel") || label.contains("phase") {
+            img.wrapper_classes = vec!["svg-align".to_string()];
+        }
+        make_v_list(VListParam::IndividualShift(vec![
+            // Write the \cancel stroke on top of inner.
+            VListElem::shifted(inner, 0.0),
+            img,
+        ]))
+    };
+
+    if label.contains("cancel") {
+        // The cancel package documentation says that cancel lines add their height
+        // to the expression, but tests show that isn't how it actually works.
+        vlist.height = inner_height;
+        vlist.depth = inner_depth;
+    }
+
+    let classes = if label.contains("cancel") && !is_single_char {
+        // cancel does not create horiz space for its line extension.
+        vec!["mord".to_string(), "cancel-lap".to_string()]
+    } else {
+        vec!["mord".to_string()]
+    };
+    make_span(classes, vec![vlist.into()], Some(options), None).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Enclose(group) = group else {
+        unreachable!()
+    };
+    let mut body = build_mathml::build_group(Some(&group.body), options);
+    if group.label == "\\boxed" {
+        let mut style = MathNode::new(MathNodeType::Mstyle, vec![body], vec![]);
+        style.set_attribute("displaystyle", "true");
+        style.set_attribute("scriptlevel", "0");
+        body = style.into();
+    }
+    let mut node = MathNode::new(
+        if group.label.contains("colorbox") {
+            MathNodeType::Mpadded
+        } else {
+            MathNodeType::Menclose
+        },
+        vec![body],
+        vec![],
+    );
+    match group.label.as_str() {
+        "\\cancel" => node.set_attribute("notation", "updiagonalstrike"),
+        "\\bcancel" => node.set_attribute("notation", "downdiagonalstrike"),
+        "\\phase" => node.set_attribute("notation", "phasorangle"),
+        "\\sout" => node.set_attribute("notation", "horizontalstrike"),
+        "\\fbox" | "\\boxed" => node.set_attribute("notation", "box"),
+        "\\angl" => node.set_attribute("notation", "actuarial"),
+        "\\fcolorbox" | "\\colorbox" => {
+            // <menclose> doesn't have a good notation option. So use <mpadded>
+            // instead. Set some attributes that come included with <menclose>.
+            let fboxsep = options.font_metrics().fboxsep * options.font_metrics().pt_per_em;
+            node.set_attribute("width", &format!("+{}pt", 2.0 * fboxsep));
+            node.set_attribute("height", &format!("+{}pt", 2.0 * fboxsep));
+            node.set_attribute("lspace", &format!("{}pt", fboxsep));
+            node.set_attribute("voffset", &format!("{}pt", fboxsep));
+            if group.label == "\\fcolorbox" {
+                let thk = options
+                    .font_metrics()
+                    .fboxrule // default
+                    .max(options.min_rule_thickness); // user override
+                node.set_attribute(
+                    "style",
+                    &format!(
+                        "border: {}em solid {}",
+                        thk,
+                        group.border_color.as_deref().unwrap_or_default()
+                    ),
+                );
+            }
+        }
+        "\\xcancel" => node.set_attribute("notation", "updiagonalstrike downdiagonalstrike"),
+        _ => {}
+    }
+    if let Some(background_color) = &group.background_color {
+        node.set_attribute("mathbackground", background_color);
+    }
+    node.into()
+}
+
+fn colorbox_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let body = args.remove(1);
+    let AnyParseNode::ColorToken(color) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type color-token", None));
+    };
+    Ok(AnyParseNode::Enclose(EncloseParseNode {
+        type_: "enclose".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        label: func_name,
+        background_color: Some(color.color),
+        border_color: None,
+        body: Box::new(body),
+    }))
+}
+
+fn fcolorbox_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let body = args.remove(2);
+    let AnyParseNode::ColorToken(background_color) = args.remove(1) else {
+        return Err(ParseError::new("Expected node of type color-token", None));
+    };
+    let AnyParseNode::ColorToken(border_color) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type color-token", None));
+    };
+    Ok(AnyParseNode::Enclose(EncloseParseNode {
+        type_: "enclose".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        label: func_name,
+        background_color: Some(background_color.color),
+        border_color: Some(border_color.color),
+        body: Box::new(body),
+    }))
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    Ok(AnyParseNode::Enclose(EncloseParseNode {
+        type_: "enclose".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        label: func_name,
+        background_color: None,
+        border_color: None,
+        body: Box::new(args.remove(0)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_and_labels() {
+        let input = "\\fcolorbox{red}{00ff00}{a}\\colorbox{#abc}{b}";
+        let tree = parse_tree(input, &Settings::default()).unwrap();
+        let [AnyParseNode::Enclose(fcolorbox), AnyParseNode::Enclose(colorbox)] = &tree[..] else {
+            panic!("expected two enclose nodes");
+        };
+        assert_eq!(fcolorbox.label, "\\fcolorbox");
+        assert_eq!(fcolorbox.border_color.as_deref(), Some("red"));
+        assert_eq!(fcolorbox.background_color.as_deref(), Some("#00ff00"));
+        assert_eq!(colorbox.background_color.as_deref(), Some("#abc"));
+        assert_eq!(colorbox.border_color, None);
+
+        let error = parse_tree("\\colorbox{#12}{a}", &Settings::default()).unwrap_err();
+        assert_eq!(error.raw_message(), "Invalid color: '#12'");
+    }
+
+    #[test]
+    fn markup() {
+        let cancel = render("\\xcancel{x}");
+        assert!(cancel.contains("<menclose notation=\"updiagonalstrike downdiagonalstrike\">"));
+        assert_eq!(cancel.matches("<line ").count(), 2);
+
+        let phase = render("\\phase{x}");
+        assert!(phase.contains("<menclose notation=\"phasorangle\">"));
+        assert!(phase.contains("<path d=\"M400000 641\nH0 L320.5 0 l65 45 L145 561 H400000z\"/>"));
+
+        let sout = render("\\sout{x}");
+        assert!(sout.contains("<menclose notation=\"horizontalstrike\">"));
+        assert!(sout.contains("class=\"stretchy sout\""));
+    }
+
+    #[test]
+    fn boxes() {
+        let fbox = render("\\fbox{ab}");
+        assert!(fbox.contains("<menclose notation=\"box\"><mstyle "));
+        assert!(fbox.contains("<mtext>ab</mtext></mstyle></menclose>"));
+        assert!(fbox.contains("class=\"stretchy fbox\""));
+
+        let colorbox = render("\\colorbox{red}{ab}");
+        assert!(colorbox.contains("mathbackground=\"red\""));
+        assert!(colorbox.contains("background-color:red;"));
+
+        let fcolorbox = render("\\fcolorbox{red}{#00f}{ab}");
+        assert!(fcolorbox.contains("style=\"border: 0.04em solid red\""));
+        assert!(fcolorbox.contains("border-color:red;"));
+
+        let angl = render("\\angl{n}");
+        assert!(angl.contains("<menclose notation=\"actuarial\">"));
+        assert!(angl.contains("class=\"stretchy angl\""));
+
+        // \boxed frames math, set in display style.
+        let boxed = render("\\boxed{x^2}");
+        assert!(boxed.contains("<menclose notation=\"box\"><mstyle "));
+        assert!(boxed.contains("displaystyle=\"true\"") && boxed.contains("scriptlevel=\"0\""));
+        assert!(boxed.contains("class=\"stretchy fbox\""));
+    }
+
+    #[test]
+    fn layout() {
+        // A box pads its body by \fboxsep and \fboxrule on each side.
+        let body = build_one("{x}");
+        let boxed = build_one("\\boxed{x}");
+        assert!((boxed.height() - body.height() - 0.34).abs() < 1e-9);
+        assert!((boxed.depth() - body.depth() - 0.34).abs() < 1e-9);
+
+        // A \colorbox has no rule, so only \fboxsep.
+        let colorbox = build_one("\\colorbox{red}{x}");
+        assert!((colorbox.depth() - 0.3).abs() < 1e-9);
+
+        // The body of \boxed is in display style, so its fractions are large.
+        assert!(
+            build_one("\\boxed{\\frac{a}{b}}").height()
+                > build_one("\\boxed{\\tfrac{a}{b}}").height() + 0.2
+        );
+
+        // Cancel strokes add no height.
+        assert_eq!(build_one("\\cancel{x}").height(), build_one("x").height());
+    }
+}
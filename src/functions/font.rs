@@ -0,0 +1,248 @@
+// TODO(kevinb): implement \sl and \sc
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "font",
+        names: &[
+            // styles, except \boldsymbol defined below
+            "\\mathrm",
+            "\\mathit",
+            "\\mathbf",
+            "\\mathnormal",
+            "\\mathsfit",
+            // families
+            "\\mathbb",
+            "\\mathcal",
+            "\\mathfrak",
+            "\\mathscr",
+            "\\mathsf",
+            "\\mathtt",
+            // aliases, except \bm defined below
+            "\\Bbb",
+            "\\bold",
+            "\\frak",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_argument: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "mclass",
+        names: &["\\boldsymbol", "\\bm"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(boldsymbol_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // Old font changing functions
+    registry.define_function(FunctionDefSpec {
+        node_type: "font",
+        names: &["\\rm", "\\sf", "\\tt", "\\bf", "\\it", "\\cal"],
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(old_font_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Font(group) = group else {
+        unreachable!()
+    };
+    let new_options = options.with_font(&group.font);
+    build_html::build_group(Some(&group.body), &new_options, None)
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Font(group) = group else {
+        unreachable!()
+    };
+    let new_options = options.with_font(&group.font);
+    build_mathml::build_group(Some(&group.body), &new_options)
+}
+
+fn font_alias(func_name: &str) -> &str {
+    match func_name {
+        "\\Bbb" => "\\mathbb",
+        "\\bold" => "\\mathbf",
+        "\\frak" => "\\mathfrak",
+        "\\bm" => "\\boldsymbol",
+        func_name => func_name,
+    }
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let body = normalize_argument(args.remove(0));
+    let func = font_alias(&func_name);
+    Ok(AnyParseNode::Font(FontParseNode {
+        type_: "font".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        font: func[1..].to_string(),
+        body: Box::new(body),
+    }))
+}
+
+fn boldsymbol_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(0);
+    let is_character_box = utils::is_character_box(&body);
+    // amsbsy.sty's \boldsymbol uses \binrel spacing to inherit the
+    // argument's bin|rel|ord status
+    Ok(AnyParseNode::Mclass(MclassParseNode {
+        type_: "mclass".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        mclass: mclass::binrel_class(&body),
+        body: vec![AnyParseNode::Font(FontParseNode {
+            type_: "font".into(),
+            mode: parser.mode.to_string(),
+            loc: None,
+            font: "boldsymbol".into(),
+            body: Box::new(body),
+        })],
+        is_character_box,
+    }))
+}
+
+fn old_font_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser,
+        func_name,
+        break_on_token_text,
+        ..
+    } = context;
+    let mode = parser.mode;
+    let body = parser.parse_expression(true, break_on_token_text.as_deref())?;
+    let style = format!("math{}", &func_name[1..]);
+
+    Ok(AnyParseNode::Font(FontParseNode {
+        type_: "font".into(),
+        mode: mode.to_string(),
+        loc: None,
+        font: style,
+        body: Box::new(AnyParseNode::OrdGroup(OrdGroupParseNode {
+            type_: "ordgroup".into(),
+            mode: parser.mode.to_string(),
+            loc: None,
+            body,
+            semisimple: None,
+        })),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn math_fonts_and_aliases() {
+        let AnyParseNode::Font(font) = parse_one("\\mathbf{x}") else {
+            panic!("expected a font node");
+        };
+        assert_eq!(font.font, "mathbf");
+
+        let AnyParseNode::Font(font) = parse_one("\\Bbb{R}") else {
+            panic!("expected a font node");
+        };
+        assert_eq!(font.font, "mathbb");
+
+        let AnyParseNode::Font(font) = parse_one("\\rm ab") else {
+            panic!("expected a font node");
+        };
+        assert_eq!(font.font, "mathrm");
+        let AnyParseNode::OrdGroup(body) = *font.body else {
+            panic!("expected an ordgroup body");
+        };
+        assert_eq!(body.body.len(), 2);
+    }
+
+    #[test]
+    fn boldsymbol_keeps_the_atom_class() {
+        let AnyParseNode::Mclass(node) = parse_one("\\boldsymbol{+}") else {
+            panic!("expected an mclass node");
+        };
+        assert_eq!(node.mclass, "mbin");
+        let AnyParseNode::Mclass(node) = parse_one("\\bm{x}") else {
+            panic!("expected an mclass node");
+        };
+        assert_eq!(node.mclass, "mord");
+    }
+
+    #[test]
+    fn text_fonts() {
+        let AnyParseNode::Text(text) = parse_one("\\textbf{ab}") else {
+            panic!("expected a text node");
+        };
+        assert_eq!(text.font.as_deref(), Some("\\textbf"));
+        assert_eq!(text.body.len(), 2);
+    }
+
+    #[test]
+    fn layout() {
+        // Each font brings its own metrics: bold letters are taller.
+        let bold = build_one("\\mathbf{x}");
+        let italic = build_one("x");
+        assert!(bold.height() > italic.height());
+        assert!(build_one("\\mathfrak{g}").depth() > 0.15);
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\mathbf{x}");
+        assert!(markup.contains("<span class=\"mord mathbf\">x</span>"));
+        assert!(markup.contains("<mi mathvariant=\"bold\">x</mi>"));
+
+        // Each math font sets its class on the symbols it has.
+        for (expression, class) in [
+            ("\\mathrm{x}", "mathrm"),
+            ("\\mathit{x}", "mathit"),
+            ("\\mathbb{R}", "mathbb"),
+            ("\\mathcal{A}", "mathcal"),
+            ("\\mathfrak{g}", "mathfrak"),
+            ("\\mathsf{x}", "mathsf"),
+            ("\\mathtt{x}", "mathtt"),
+            ("\\boldsymbol{x}", "boldsymbol"),
+        ] {
+            let markup = render(expression);
+            assert!(markup.contains(&format!("class=\"mord {}\"", class)), "{}", expression);
+        }
+
+        // Symbols missing from a font fall back to the default font.
+        assert!(render("\\mathbf{\\alpha}").contains("class=\"mord mathnormal\""));
+
+        let markup = render("\\textit{a}");
+        assert!(markup.contains("<span class=\"mord textit\">a</span>"));
+        assert!(markup.contains("<mtext mathvariant=\"italic\">a</mtext>"));
+    }
+}
@@ -0,0 +1,704 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "genfrac",
+        names: &[
+            "\\dfrac",
+            "\\frac",
+            "\\tfrac",
+            "\\dbinom",
+            "\\binom",
+            "\\tbinom",
+            "\\\\atopfrac", // can’t be entered directly
+            "\\\\bracefrac",
+            "\\\\brackfrac", // ditto
+        ],
+        props: FunctionPropSpec {
+            num_args: 2,
+            allowed_in_argument: true,
+            ..Default::default()
+        },
+        handler: Some(frac_handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "genfrac",
+        names: &["\\cfrac"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            num_optional_args: 1,
+            arg_types: Some(vec![ArgType::Raw, ArgType::Original, ArgType::Original]),
+            ..Default::default()
+        },
+        handler: Some(cfrac_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // Infix generalized fractions -- these are not rendered directly, but replaced
+    // immediately by one of the variants above.
+    registry.define_function(FunctionDefSpec {
+        node_type: "infix",
+        names: &["\\over", "\\choose", "\\atop", "\\brace", "\\brack"],
+        props: FunctionPropSpec {
+            num_args: 0,
+            infix: true,
+            ..Default::default()
+        },
+        handler: Some(infix_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "genfrac",
+        names: &["\\genfrac"],
+        props: FunctionPropSpec {
+            num_args: 6,
+            allowed_in_argument: true,
+            arg_types: Some(vec![
+                ArgType::Mode(Mode::Math),
+                ArgType::Mode(Mode::Math),
+                ArgType::Size,
+                ArgType::Mode(Mode::Text),
+                ArgType::Mode(Mode::Math),
+                ArgType::Mode(Mode::Math),
+            ]),
+            ..Default::default()
+        },
+        handler: Some(genfrac_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // \above is an infix fraction that also defines a fraction bar size.
+    registry.define_function(FunctionDefSpec {
+        node_type: "infix",
+        names: &["\\above"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Size]),
+            infix: true,
+            ..Default::default()
+        },
+        handler: Some(above_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "genfrac",
+        names: &["\\\\abovefrac"],
+        props: FunctionPropSpec {
+            num_args: 3,
+            arg_types: Some(vec![
+                ArgType::Mode(Mode::Math),
+                ArgType::Size,
+                ArgType::Mode(Mode::Math),
+            ]),
+            ..Default::default()
+        },
+        handler: Some(abovefrac_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn adjust_style(size: &str, original_style: Style) -> Style {
+    // Figure out what style this fraction should be in based on the
+    // function used
+    match size {
+        // Get display style as a default.
+        // If incoming style is sub/sup, use style.text() to get correct size.
+        "display" if original_style.id >= Style::SCRIPT.id => original_style.text(),
+        "display" => Style::DISPLAY,
+        // We're in a \tfrac but incoming style is displaystyle, so:
+        "text" if original_style.size == Style::DISPLAY.size => Style::TEXT,
+        "script" => Style::SCRIPT,
+        "scriptscript" => Style::SCRIPTSCRIPT,
+        _ => original_style,
+    }
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Genfrac(group) = group else {
+        unreachable!()
+    };
+    // Fractions are handled in the TeXbook on pages 444-445, rules 15(a-e).
+    let style = adjust_style(&group.size, options.style);
+
+    let nstyle = style.frac_num();
+    let dstyle = style.frac_den();
+
+    let new_options = options.having_style(nstyle);
+    let mut numerm = build_html::build_group(Some(&group.numer), &new_options, Some(options));
+
+    if group.continued {
+        // \cfrac inserts a \strut into the numerator.
+        // Get \strut dimensions from TeXbook page 353.
+        let h_strut = 8.5 / options.font_metrics().pt_per_em;
+        let d_strut = 3.5 / options.font_metrics().pt_per_em;
+        numerm.set_height(numerm.height().max(h_strut));
+        numerm.set_depth(numerm.depth().max(d_strut));
+    }
+
+    let new_options = options.having_style(dstyle);
+    let denomm = build_html::build_group(Some(&group.denom), &new_options, Some(options));
+
+    let rule;
+    let rule_width;
+    let rule_spacing;
+    if group.has_bar_line {
+        let line = match &group.bar_size {
+            Some(bar_size) => {
+                let rule_width = calculate_size(bar_size, options);
+                make_line_span("frac-line", options, Some(rule_width))
+            }
+            None => make_line_span("frac-line", options, None),
+        };
+        rule_width = line.height;
+        rule_spacing = line.height;
+        rule = Some(line);
+    } else {
+        rule = None;
+        rule_width = 0.0;
+        rule_spacing = options.font_metrics().default_rule_thickness;
+    }
+
+    // Rule 15b
+    let mut num_shift;
+    let clearance;
+    let mut denom_shift;
+    if style.size == Style::DISPLAY.size || group.size == "display" {
+        num_shift = options.font_metrics().num1;
+        clearance = if rule_width > 0.0 {
+            3.0 * rule_spacing
+        } else {
+            7.0 * rule_spacing
+        };
+        denom_shift = options.font_metrics().denom1;
+    } else {
+        if rule_width > 0.0 {
+            num_shift = options.font_metrics().num2;
+            clearance = rule_spacing;
+        } else {
+            num_shift = options.font_metrics().num3;
+            clearance = 3.0 * rule_spacing;
+        }
+        denom_shift = options.font_metrics().denom2;
+    }
+
+    let (numerm_depth, denomm_height) = (numerm.depth(), denomm.height());
+    let mut numer_elem = VListElem::new(numerm);
+    if let Some(align) = &group.numer_align {
+        numer_elem
+            .wrapper_style
+            .insert("textAlign".to_string(), align.clone());
+    }
+
+    let mut frac = match rule {
+        None => {
+            // Rule 15c
+            let candidate_clearance = (num_shift - numerm_depth) - (denomm_height - denom_shift);
+            if candidate_clearance < clearance {
+                num_shift += 0.5 * (clearance - candidate_clearance);
+                denom_shift += 0.5 * (clearance - candidate_clearance);
+            }
+
+            numer_elem.shift = -num_shift;
+            make_v_list(VListParam::IndividualShift(vec![
+                VListElem::shifted(denomm, denom_shift),
+                numer_elem,
+            ]))
+        }
+        Some(rule) => {
+            // Rule 15d
+            let axis_height = options.font_metrics().axis_height;
+
+            if (num_shift - numerm_depth) - (axis_height + 0.5 * rule_width) < clearance {
+                num_shift +=
+                    clearance - ((num_shift - numerm_depth) - (axis_height + 0.5 * rule_width));
+            }
+
+            if (axis_height - 0.5 * rule_width) - (denomm_height - denom_shift) < clearance {
+                denom_shift +=
+                    clearance - ((axis_height - 0.5 * rule_width) - (denomm_height - denom_shift));
+            }
+
+            let mid_shift = -(axis_height - 0.5 * rule_width);
+
+            numer_elem.shift = -num_shift;
+            make_v_list(VListParam::IndividualShift(vec![
+                VListElem::shifted(denomm, denom_shift),
+                VListElem::shifted(rule, mid_shift),
+                numer_elem,
+            ]))
+        }
+    };
+
+    // Since we manually change the style sometimes (with \dfrac or \tfrac),
+    // account for the possible size change here.
+    let new_options = options.having_style(style);
+    frac.height *= new_options.size_multiplier / options.size_multiplier;
+    frac.depth *= new_options.size_multiplier / options.size_multiplier;
+
+    // Rule 15e
+    let delim_size = if style.size == Style::DISPLAY.size {
+        options.font_metrics().delim1
+    } else if style.size == Style::SCRIPTSCRIPT.size {
+        options.having_style(Style::SCRIPT).font_metrics().delim2
+    } else {
+        options.font_metrics().delim2
+    };
+
+    let left_delim = match &group.left_delim {
+        None => build_html::make_null_delimiter(options, vec!["mopen".to_string()]),
+        Some(left_delim) => delimiter::custom_sized_delim(
+            left_delim,
+            delim_size,
+            true,
+            &options.having_style(style),
+            Mode::from(group.mode.as_str()),
+            vec!["mopen".to_string()],
+        ),
+    };
+
+    let right_delim = if group.continued {
+        make_span(vec![], vec![], None, None) // zero width for \cfrac
+    } else {
+        match &group.right_delim {
+            None => build_html::make_null_delimiter(options, vec!["mclose".to_string()]),
+            Some(right_delim) => delimiter::custom_sized_delim(
+                right_delim,
+                delim_size,
+                true,
+                &options.having_style(style),
+                Mode::from(group.mode.as_str()),
+                vec!["mclose".to_string()],
+            ),
+        }
+    };
+
+    make_span(
+        [
+            vec!["mord".to_string()],
+            new_options.sizing_classes(options),
+        ]
+        .concat(),
+        vec![
+            left_delim.into(),
+            make_span(vec!["mfrac".to_string()], vec![frac.into()], None, None).into(),
+            right_delim.into(),
+        ],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Genfrac(group) = group else {
+        unreachable!()
+    };
+    let mut frac = MathNode::new(
+        MathNodeType::Mfrac,
+        vec![
+            build_mathml::build_group(Some(&group.numer), options),
+            build_mathml::build_group(Some(&group.denom), options),
+        ],
+        vec![],
+    );
+
+    if !group.has_bar_line {
+        frac.set_attribute("linethickness", "0px");
+    } else if let Some(bar_size) = &group.bar_size {
+        let rule_width = calculate_size(bar_size, options);
+        frac.set_attribute("linethickness", &make_em(rule_width));
+    }
+
+    if let Some(align) = &group.numer_align {
+        frac.set_attribute("numalign", align);
+    }
+
+    let mut node = frac;
+    let style = adjust_style(&group.size, options.style);
+    if style.size != options.style.size {
+        node = MathNode::new(MathNodeType::Mstyle, vec![node.into()], vec![]);
+        let is_display = if style.size == Style::DISPLAY.size {
+            "true"
+        } else {
+            "false"
+        };
+        node.set_attribute("displaystyle", is_display);
+        node.set_attribute("scriptlevel", "0");
+    }
+
+    if group.left_delim.is_some() || group.right_delim.is_some() {
+        let fence = |delim: &str| {
+            let mut op = MathNode::new(
+                MathNodeType::Mo,
+                vec![TextNode::new(&delim.replacen('\\', "", 1)).into()],
+                vec![],
+            );
+            op.set_attribute("fence", "true");
+            op.into()
+        };
+
+        let mut with_delims = vec![];
+
+        if let Some(left_delim) = &group.left_delim {
+            with_delims.push(fence(left_delim));
+        }
+
+        with_delims.push(node.into());
+
+        if let Some(right_delim) = &group.right_delim {
+            with_delims.push(fence(right_delim));
+        }
+
+        return build_mathml::make_row(with_delims);
+    }
+
+    node.into()
+}
+
+fn genfrac_node(
+    parser: &Parser,
+    numer: AnyParseNode,
+    denom: AnyParseNode,
+    has_bar_line: bool,
+    left_delim: Option<&str>,
+    right_delim: Option<&str>,
+    size: &str,
+) -> GenfracParseNode {
+    GenfracParseNode {
+        type_: "genfrac".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        continued: false,
+        numer: Box::new(numer),
+        denom: Box::new(denom),
+        has_bar_line,
+        left_delim: left_delim.map(str::to_string),
+        right_delim: right_delim.map(str::to_string),
+        size: size.to_string(),
+        bar_size: None,
+        numer_align: None,
+    }
+}
+
+fn frac_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let denom = args.pop().unwrap();
+    let numer = args.pop().unwrap();
+
+    let (has_bar_line, left_delim, right_delim) = match func_name.as_str() {
+        "\\dfrac" | "\\frac" | "\\tfrac" => (true, None, None),
+        "\\\\atopfrac" => (false, None, None),
+        "\\dbinom" | "\\binom" | "\\tbinom" => (false, Some("("), Some(")")),
+        "\\\\bracefrac" => (false, Some("\\{"), Some("\\}")),
+        "\\\\brackfrac" => (false, Some("["), Some("]")),
+        _ => panic!("Unrecognized genfrac command"),
+    };
+
+    let size = match func_name.as_str() {
+        "\\dfrac" | "\\dbinom" => "display",
+        "\\tfrac" | "\\tbinom" => "text",
+        _ => "auto",
+    };
+
+    Ok(AnyParseNode::Genfrac(genfrac_node(
+        parser,
+        numer,
+        denom,
+        has_bar_line,
+        left_delim,
+        right_delim,
+        size,
+    )))
+}
+
+/// `\cfrac[l]` and `\cfrac[r]` flush the numerator left or right, as in
+/// amsmath.
+fn cfrac_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, token, .. } = context;
+    let denom = args.pop().unwrap();
+    let numer = args.pop().unwrap();
+
+    let numer_align = match opt_args.into_iter().next().flatten() {
+        Some(AnyParseNode::Raw(align)) => match align.string.trim() {
+            "l" => Some("left".to_string()),
+            "r" => Some("right".to_string()),
+            "c" | "" => None,
+            align => {
+                return Err(ParseError::new(
+                    &format!("Invalid alignment '{}' for \\cfrac", align),
+                    token.as_ref(),
+                ))
+            }
+        },
+        _ => None,
+    };
+
+    Ok(AnyParseNode::Genfrac(GenfracParseNode {
+        continued: true,
+        numer_align,
+        ..genfrac_node(parser, numer, denom, true, None, None, "display")
+    }))
+}
+
+fn infix_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser,
+        func_name,
+        token,
+        ..
+    } = context;
+    let replace_with = match func_name.as_str() {
+        "\\over" => "\\frac",
+        "\\choose" => "\\binom",
+        "\\atop" => "\\\\atopfrac",
+        "\\brace" => "\\\\bracefrac",
+        "\\brack" => "\\\\brackfrac",
+        _ => panic!("Unrecognized infix genfrac command"),
+    };
+    Ok(AnyParseNode::Infix(InfixParseNode {
+        type_: "infix".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        replace_with: replace_with.to_string(),
+        size: None,
+        token,
+    }))
+}
+
+const STYL_ARRAY: [&str; 4] = ["display", "text", "script", "scriptscript"];
+
+fn delim_from_value(delim_string: &str) -> Option<String> {
+    if delim_string.is_empty() || delim_string == "." {
+        None
+    } else {
+        Some(delim_string.to_string())
+    }
+}
+
+/// The style given by the digit in the fourth argument of \genfrac.
+fn styl_from_textord(node: &AnyParseNode) -> Result<&'static str, ParseError> {
+    let AnyParseNode::TextOrd(textord) = node else {
+        return Err(ParseError::new("Expected node of type textord", None));
+    };
+    Ok(textord
+        .text
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| STYL_ARRAY.get(index).copied())
+        .unwrap_or("auto"))
+}
+
+fn genfrac_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let denom = args.pop().unwrap();
+    let numer = args.pop().unwrap();
+    let styl = args.pop().unwrap();
+    let bar_node = args.pop().unwrap();
+    let right_node = normalize_argument(args.pop().unwrap());
+    let left_node = normalize_argument(args.pop().unwrap());
+
+    // Look into the parse nodes to get the desired delimiters.
+    let left_delim = match &left_node {
+        AnyParseNode::Atom(atom) if atom.family == Group::Open => delim_from_value(&atom.text),
+        _ => None,
+    };
+    let right_delim = match &right_node {
+        AnyParseNode::Atom(atom) if atom.family == Group::Close => delim_from_value(&atom.text),
+        _ => None,
+    };
+
+    let AnyParseNode::Size(bar_node) = bar_node else {
+        return Err(ParseError::new("Expected node of type size", None));
+    };
+    let has_bar_line;
+    let mut bar_size = None;
+    if bar_node.is_blank {
+        // \genfrac acts differently than \above.
+        // \genfrac treats an empty size group as a signal to use a
+        // standard bar size. \above would see size = 0 and omit the bar.
+        has_bar_line = true;
+    } else {
+        has_bar_line = bar_node.value.number > 0.0;
+        bar_size = Some(bar_node.value);
+    }
+
+    // Find out if we want displaystyle, textstyle, etc.
+    let size = match &styl {
+        AnyParseNode::OrdGroup(styl) => match styl.body.first() {
+            Some(node) => styl_from_textord(node)?,
+            None => "auto",
+        },
+        styl => styl_from_textord(styl)?,
+    };
+
+    Ok(AnyParseNode::Genfrac(GenfracParseNode {
+        left_delim,
+        right_delim,
+        bar_size,
+        ..genfrac_node(parser, numer, denom, has_bar_line, None, None, size)
+    }))
+}
+
+fn above_handler(
+    context: FunctionContext,
+    args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, token, .. } = context;
+    let Some(AnyParseNode::Size(size)) = args.into_iter().next() else {
+        return Err(ParseError::new("Expected node of type size", None));
+    };
+    Ok(AnyParseNode::Infix(InfixParseNode {
+        type_: "infix".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        replace_with: "\\\\abovefrac".to_string(),
+        size: Some(size.value),
+        token,
+    }))
+}
+
+fn abovefrac_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let denom = args.pop().unwrap();
+    let infix = args.pop().unwrap();
+    let numer = args.pop().unwrap();
+    let AnyParseNode::Infix(InfixParseNode {
+        size: Some(bar_size),
+        ..
+    }) = infix
+    else {
+        return Err(ParseError::new("Expected node of type infix", None));
+    };
+
+    let has_bar_line = bar_size.number > 0.0;
+    Ok(AnyParseNode::Genfrac(GenfracParseNode {
+        bar_size: Some(bar_size),
+        ..genfrac_node(parser, numer, denom, has_bar_line, None, None, "auto")
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_genfrac(input: &str) -> GenfracParseNode {
+        let AnyParseNode::Genfrac(node) = parse_one(input) else {
+            panic!("expected a genfrac node for {}", input);
+        };
+        node
+    }
+
+    #[test]
+    fn frac_and_binom() {
+        let frac = parse_genfrac("\\dfrac{a}{b}");
+        assert!(frac.has_bar_line);
+        assert_eq!(frac.size, "display");
+        assert_eq!((frac.left_delim, frac.right_delim), (None, None));
+
+        let binom = parse_genfrac("\\binom{n}{k}");
+        assert!(!binom.has_bar_line);
+        assert_eq!(binom.size, "auto");
+        assert_eq!(binom.left_delim.as_deref(), Some("("));
+        assert_eq!(binom.right_delim.as_deref(), Some(")"));
+    }
+
+    #[test]
+    fn infix_operators_take_the_whole_group() {
+        let frac = parse_genfrac("a+b \\over c");
+        let AnyParseNode::OrdGroup(numer) = *frac.numer else {
+            panic!("expected an ordgroup numerator");
+        };
+        assert_eq!(numer.body.len(), 3);
+        assert!(frac.has_bar_line);
+
+        let choose = parse_genfrac("n \\choose k");
+        assert!(!choose.has_bar_line);
+        assert_eq!(choose.left_delim.as_deref(), Some("("));
+
+        let above = parse_genfrac("a \\above 2pt b");
+        assert_eq!(above.bar_size.map(|size| size.number), Some(2.0));
+
+        let error = parse_tree("a \\over b \\over c", &Settings::default()).unwrap_err();
+        assert_eq!(error.raw_message(), "only one infix operator per group");
+    }
+
+    #[test]
+    fn genfrac_arguments() {
+        let frac = parse_genfrac("\\genfrac[]{0pt}{2}{a}{b}");
+        assert!(!frac.has_bar_line);
+        assert_eq!(frac.size, "script");
+        assert_eq!(frac.left_delim.as_deref(), Some("["));
+        assert_eq!(frac.right_delim.as_deref(), Some("]"));
+
+        let frac = parse_genfrac("\\genfrac{}{}{}{}{a}{b}");
+        assert!(frac.has_bar_line);
+        assert_eq!(frac.size, "auto");
+        assert_eq!(frac.bar_size, None);
+    }
+
+    #[test]
+    fn layout() {
+        // A display fraction sets its parts in text style and shifts them
+        // further from the axis than a text fraction does.
+        let display = build_one("\\dfrac{a}{b}");
+        let text = build_one("\\tfrac{a}{b}");
+        assert!(display.height() > text.height() + 0.2);
+        assert!(display.depth() > text.depth() + 0.2);
+
+        // The numerator sits above the bar and the denominator below the
+        // baseline, whatever their own sizes.
+        let frac = build_one("\\frac{x}{y}");
+        assert!(frac.height() > build_one("x").height());
+        assert!(frac.depth() > build_one("y").depth());
+
+        // A thicker bar pushes both parts further out.
+        let thick = build_one("{x \\above 5pt y}");
+        let over = build_one("{x \\over y}");
+        assert!(thick.height() > over.height() && thick.depth() > over.depth());
+    }
+
+    #[test]
+    fn markup() {
+        let frac = render("\\frac{1}{2}");
+        assert!(frac.contains("mfrac") && frac.contains("frac-line"));
+        assert!(frac.contains("<mfrac><mn>1</mn><mn>2</mn></mfrac>"));
+
+        let binom = render("\\binom{n}{k}");
+        assert!(!binom.contains("frac-line"));
+        assert!(binom.contains("linethickness=\"0px\""));
+        assert!(binom.contains("delimsizing"));
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+/// \hbox is provided for compatibility with LaTeX \vcenter.
+/// In LaTeX, \vcenter can act only on a box, as in
+/// \vcenter{\hbox{$\frac{a+b}{\dfrac{c}{d}}$}}
+/// This function by itself doesn't do anything but prevent a soft line break.
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "hbox",
+        names: &["\\hbox"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Mode(Mode::Text)]),
+            allowed_in_text: true,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Hbox(group) = group else {
+        unreachable!()
+    };
+    let elements = build_html::build_expression(&group.body, options, false, false, [None, None]);
+    make_fragment(elements).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Hbox(group) = group else {
+        unreachable!()
+    };
+    MathNode::new(
+        MathNodeType::Mrow,
+        build_mathml::build_expression(&group.body, options, false),
+        vec![],
+    )
+    .into()
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    Ok(AnyParseNode::Hbox(HboxParseNode {
+        type_: "hbox".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: ordargument(args.remove(0)),
+    }))
+}
@@ -0,0 +1,251 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // Horizontal stretchy braces
+    registry.define_function(FunctionDefSpec {
+        node_type: "horizBrace",
+        names: &[
+            "\\overbrace",
+            "\\underbrace",
+            "\\overbracket",
+            "\\underbracket",
+            "\\overgroup",
+            "\\undergroup",
+            "\\overlinesegment",
+            "\\underlinesegment",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    Ok(AnyParseNode::HorizBrace(HorizBraceParseNode {
+        type_: "horizBrace".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        is_over: func_name.starts_with("\\over"),
+        label: func_name,
+        base: Box::new(args.remove(0)),
+    }))
+}
+
+/// NOTE: Unlike most html_builders, this one handles not only "horizBrace", but
+/// also "supsub" since an over/underbrace can affect super/subscripting.
+pub fn html_builder(grp: &AnyParseNode, options: &Options) -> VirtualNode {
+    let style = options.style;
+
+    // Pull out the `HorizBraceParseNode` if `grp` is a "supsub" node.
+    let (group, sup_sub_group) = match grp {
+        AnyParseNode::SupSub(supsub) => {
+            // Ref: LaTeX source2e: }}}}\limits}
+            // i.e. LaTeX treats the brace similar to an op and passes it
+            // with \limits, so we need to assign supsub style.
+            let sup_sub_group = match (&supsub.sup, &supsub.sub) {
+                (Some(sup), _) => build_html::build_group(
+                    Some(sup),
+                    &options.having_style(style.sup()),
+                    Some(options),
+                ),
+                (None, sub) => build_html::build_group(
+                    sub.as_deref(),
+                    &options.having_style(style.sub()),
+                    Some(options),
+                ),
+            };
+            let Some(AnyParseNode::HorizBrace(group)) = supsub.base.as_deref() else {
+                panic!("Expected node of type horizBrace as the base of a supsub");
+            };
+            (group, Some(sup_sub_group))
+        }
+        AnyParseNode::HorizBrace(group) => (group, None),
+        _ => unreachable!(),
+    };
+
+    // Build the base group
+    let body = build_html::build_group(
+        Some(&group.base),
+        &options.having_base_style(Some(Style::DISPLAY)),
+        None,
+    );
+
+    // Create the stretchy element
+    let brace_body = stretchy::svg_span(&AnyParseNode::HorizBrace(group.clone()), options);
+
+    // Generate the vlist, with the appropriate kerns        ┏━━━━━━━━┓
+    // This first vlist contains the content and the brace:   equation
+    let mut vlist;
+    if group.is_over {
+        let mut brace_body = VListElem::new(brace_body);
+        brace_body.wrapper_classes = vec!["svg-align".to_string()];
+        vlist = make_v_list(VListParam::FirstBaseline(vec![
+            VListElem::new(body).into(),
+            VListChild::Kern(0.1),
+            brace_body.into(),
+        ]));
+    } else {
+        let position_data = body.depth() + 0.1 + brace_body.height;
+        let mut brace_body = VListElem::new(brace_body);
+        brace_body.wrapper_classes = vec!["svg-align".to_string()];
+        vlist = make_v_list(VListParam::Bottom(
+            position_data,
+            vec![
+                brace_body.into(),
+                VListChild::Kern(0.1),
+                VListElem::new(body).into(),
+            ],
+        ));
+    }
+
+    if let Some(sup_sub_group) = sup_sub_group {
+        // To write the supsub, wrap the first vlist in another vlist:
+        // They can't all go in the same vlist, because the note might be
+        // wider than the equation. We want the equation to control the
+        // brace width.
+
+        //      note          long note           long note
+        //   ┏━━━━━━━━┓   or    ┏━━━┓     not    ┏━━━━━━━━━┓
+        //    equation           eqn                 eqn
+
+        let v_span = make_span(
+            vec![
+                "mord".to_string(),
+                if group.is_over { "mover" } else { "munder" }.to_string(),
+            ],
+            vec![vlist.into()],
+            Some(options),
+            None,
+        );
+
+        if group.is_over {
+            vlist = make_v_list(VListParam::FirstBaseline(vec![
+                VListElem::new(v_span).into(),
+                VListChild::Kern(0.2),
+                VListElem::new(sup_sub_group).into(),
+            ]));
+        } else {
+            let position_data = v_span.depth + 0.2 + sup_sub_group.height() + sup_sub_group.depth();
+            vlist = make_v_list(VListParam::Bottom(
+                position_data,
+                vec![
+                    VListElem::new(sup_sub_group).into(),
+                    VListChild::Kern(0.2),
+                    VListElem::new(v_span).into(),
+                ],
+            ));
+        }
+    }
+
+    make_span(
+        vec![
+            "mord".to_string(),
+            if group.is_over { "mover" } else { "munder" }.to_string(),
+        ],
+        vec![vlist.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::HorizBrace(group) = group else {
+        unreachable!()
+    };
+    let accent_node = stretchy::math_ml_node(&group.label);
+    MathNode::new(
+        if group.is_over {
+            MathNodeType::Mover
+        } else {
+            MathNodeType::Munder
+        },
+        vec![
+            build_mathml::build_group(Some(&group.base), options),
+            accent_node.into(),
+        ],
+        vec![],
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn braces_and_labels() {
+        let tree = parse_tree("\\underbrace{a+b}", &Settings::default()).unwrap();
+        let [AnyParseNode::HorizBrace(brace)] = &tree[..] else {
+            panic!("expected a horizBrace node");
+        };
+        assert_eq!(brace.label, "\\underbrace");
+        assert!(!brace.is_over);
+
+        let tree = parse_tree("\\overbrace{a+b}^{n}", &Settings::default()).unwrap();
+        let [AnyParseNode::SupSub(supsub)] = &tree[..] else {
+            panic!("expected a supsub node");
+        };
+        let Some(AnyParseNode::HorizBrace(brace)) = supsub.base.as_deref() else {
+            panic!("expected a horizBrace base");
+        };
+        assert!(brace.is_over);
+    }
+
+    #[test]
+    fn layout() {
+        let base = build_one("{a+b}");
+        let over = build_one("\\overbrace{a+b}");
+        assert!(over.height() > base.height() + 0.3);
+        assert_eq!(over.depth(), base.depth());
+        let under = build_one("\\underbrace{a+b}");
+        assert!(under.depth() > base.depth() + 0.3);
+
+        // A label is set beyond the brace in script style.
+        let labelled = build_one("\\overbrace{a+b}^{n}");
+        assert!(labelled.height() > over.height() + 0.2);
+    }
+
+    #[test]
+    fn markup() {
+        // The label goes above an \overbrace, like a limit.
+        let over = render("\\overbrace{a+b}^{n}");
+        assert!(over.contains("<mover><mover><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow>"));
+        assert!(over.contains("<mo stretchy=\"true\">\u{23de}</mo></mover><mi>n</mi></mover>"));
+        assert!(over.contains("class=\"brace-left\""));
+        assert!(over.contains("class=\"brace-center\""));
+
+        let under = render("\\underbrace{a+b}_{n}");
+        assert!(under.contains("</munder><mi>n</mi></munder>"));
+        assert!(under.contains("class=\"mord munder\""));
+
+        let bracket = render("\\overbracket{ab}");
+        assert!(bracket.contains("<mo stretchy=\"true\">\u{23b4}</mo>"));
+        assert!(bracket.contains("class=\"halfarrow-left\""));
+    }
+
+    #[test]
+    fn svg_images_have_paths() {
+        for expression in [
+            "\\overbrace{x}",
+            "\\underbrace{x}",
+            "\\overbracket{x}",
+            "\\underbracket{x}",
+        ] {
+            let markup = render(expression);
+            assert!(markup.contains("<path d=\"M"), "{}", expression);
+            assert!(!markup.contains("<path d=\"\"/>"), "{}", expression);
+        }
+    }
+}
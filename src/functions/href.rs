@@ -0,0 +1,203 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "href",
+        names: &["\\href"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            arg_types: Some(vec![ArgType::Url, ArgType::Original]),
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(href_handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "href",
+        names: &["\\url"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Url]),
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(url_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Href(group) = group else {
+        unreachable!()
+    };
+    let elements = build_html::build_expression(&group.body, options, false, false, [None, None]);
+    make_anchor(&group.href, vec![], elements, options).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Href(group) = group else {
+        unreachable!()
+    };
+    let mut math = match build_mathml::build_expression_row(&group.body, options, false) {
+        MathDomNode::Math(math) => math,
+        math => MathNode::new(MathNodeType::Mrow, vec![math], vec![]),
+    };
+    math.set_attribute("href", &group.href);
+    math.into()
+}
+
+fn href_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(1);
+    let AnyParseNode::Url(url) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type url", None));
+    };
+    let href = url.url;
+
+    if !parser.settings.is_trusted(&mut TrustContext {
+        command: "\\href",
+        url: Some(&href),
+        ..Default::default()
+    }) {
+        // Keep the link text, but mark it as an unsupported command.
+        return Ok(AnyParseNode::Color(ColorParseNode {
+            type_: "color".into(),
+            mode: parser.mode.to_string(),
+            loc: None,
+            color: parser.settings.error_color.clone(),
+            body: ordargument(body),
+        }));
+    }
+
+    Ok(AnyParseNode::Href(HrefParseNode {
+        type_: "href".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        href,
+        body: ordargument(body),
+    }))
+}
+
+fn url_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let AnyParseNode::Url(url) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type url", None));
+    };
+    let href = url.url;
+
+    if !parser.settings.is_trusted(&mut TrustContext {
+        command: "\\url",
+        url: Some(&href),
+        ..Default::default()
+    }) {
+        return Ok(parser.format_unsupported_cmd("\\url"));
+    }
+
+    let chars = href
+        .chars()
+        .map(|c| {
+            let text = if c == '~' {
+                "\\textasciitilde".to_string()
+            } else {
+                c.to_string()
+            };
+            AnyParseNode::TextOrd(TextOrdParseNode {
+                type_: "textord".into(),
+                mode: "text".into(),
+                loc: None,
+                text,
+            })
+        })
+        .collect();
+    let body = AnyParseNode::Text(TextParseNode {
+        type_: "text".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: chars,
+        font: Some("\\texttt".into()),
+    });
+    Ok(AnyParseNode::Href(HrefParseNode {
+        type_: "href".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        href,
+        body: ordargument(body),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trusted() -> Settings {
+        Settings {
+            trust: true.into(),
+            output: OutputFormat::HtmlAndMathml,
+            ..Default::default()
+        }
+    }
+
+    fn parse_href(input: &str) -> HrefParseNode {
+        let mut tree = parse_tree(input, &trusted()).unwrap();
+        assert_eq!(tree.len(), 1);
+        let AnyParseNode::Href(node) = tree.remove(0) else {
+            panic!("expected an href node for {}", input);
+        };
+        node
+    }
+
+    #[test]
+    fn urls_are_read_verbatim() {
+        let href = parse_href("\\href{https://example.com/a%20b~c}{x}");
+        assert_eq!(href.href, "https://example.com/a%20b~c");
+        assert_eq!(href.body.len(), 1);
+
+        let href = parse_href("\\href{\\#top\\_1}{x}");
+        assert_eq!(href.href, "#top_1");
+
+        let url = parse_href("\\url{http://a.b/~c}");
+        assert_eq!(url.href, "http://a.b/~c");
+        let [AnyParseNode::Text(text)] = &url.body[..] else {
+            panic!("expected a text body");
+        };
+        assert_eq!(text.font.as_deref(), Some("\\texttt"));
+        assert_eq!(text.body.len(), 13);
+    }
+
+    #[test]
+    fn untrusted_links_are_not_made() {
+        let untrusted = Settings::default();
+        let tree = parse_tree("\\href{https://example.com}{xy}", &untrusted).unwrap();
+        let [AnyParseNode::Color(color)] = &tree[..] else {
+            panic!("expected a color node");
+        };
+        assert_eq!(color.color, untrusted.error_color);
+        assert!(matches!(&color.body[..], [AnyParseNode::MathOrd(_), AnyParseNode::MathOrd(_)]));
+
+        let tree = parse_tree("\\url{javascript&colon;alert(1)}", &trusted()).unwrap();
+        assert!(matches!(&tree[..], [AnyParseNode::Color(_)]));
+
+        let markup = render_to_string("\\href{javascript:alert(1)}{x}", &untrusted);
+        assert!(!markup.contains("<a "));
+        assert!(!markup.contains("href="));
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render_to_string("\\href{https://example.com}{x}", &trusted());
+        assert!(markup.contains("<a href=\"https://example.com\">"));
+        assert!(markup.contains("<mi href=\"https://example.com\">x</mi>"));
+    }
+}
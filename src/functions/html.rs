@@ -0,0 +1,257 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "html",
+        names: &["\\htmlClass", "\\htmlId", "\\htmlStyle", "\\htmlData"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            arg_types: Some(vec![ArgType::Raw, ArgType::Original]),
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Html(group) = group else {
+        unreachable!()
+    };
+    let elements = build_html::build_expression(&group.body, options, false, false, [None, None]);
+
+    let mut classes = vec!["enclosing".to_string()];
+    if let Some(class) = group.attributes.get("class") {
+        classes.extend(class.split_whitespace().map(str::to_string));
+    }
+
+    let mut span = make_span(classes, elements, Some(options), None);
+    for (attr, value) in &group.attributes {
+        if attr == "style" {
+            // Merge into the span's own style, which may already hold a color,
+            // so the markup gets a single style attribute.
+            for declaration in value.split(';') {
+                if let Some((property, value)) = declaration.split_once(':') {
+                    span.style
+                        .insert(property.trim().to_string(), value.trim().to_string());
+                }
+            }
+        } else if attr != "class" {
+            span.set_attribute(attr, value);
+        }
+    }
+    span.into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Html(group) = group else {
+        unreachable!()
+    };
+    build_mathml::build_expression_row(&group.body, options, false)
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser,
+        func_name,
+        token,
+        ..
+    } = context;
+    let body = args.remove(1);
+    let AnyParseNode::Raw(raw) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type raw", None));
+    };
+    let value = raw.string;
+
+    parser.settings.report_nonstrict(
+        "htmlExtension",
+        "HTML extension is disabled on strict mode",
+        token.as_ref(),
+    )?;
+
+    let mut attributes = HashMap::new();
+    let trusted = match func_name.as_str() {
+        "\\htmlClass" => {
+            attributes.insert("class".to_string(), value.clone());
+            parser.settings.is_trusted(&mut TrustContext {
+                command: "\\htmlClass",
+                class: Some(&value),
+                ..Default::default()
+            })
+        }
+        "\\htmlId" => {
+            attributes.insert("id".to_string(), value.clone());
+            parser.settings.is_trusted(&mut TrustContext {
+                command: "\\htmlId",
+                id: Some(&value),
+                ..Default::default()
+            })
+        }
+        "\\htmlStyle" => {
+            attributes.insert("style".to_string(), value.clone());
+            parser.settings.is_trusted(&mut TrustContext {
+                command: "\\htmlStyle",
+                style: Some(&value),
+                ..Default::default()
+            })
+        }
+        "\\htmlData" => {
+            for item in value.split(',') {
+                let Some((key, value)) = item.split_once('=') else {
+                    return Err(ParseError::new(
+                        &format!("\\htmlData key/value '{}' missing equals sign", item),
+                        None,
+                    ));
+                };
+                if value.contains('=') {
+                    return Err(ParseError::new(
+                        &format!("\\htmlData key/value '{}' has more than one equals sign", item),
+                        None,
+                    ));
+                }
+                let (key, value) = (key.trim(), value.trim());
+                // Attribute names are written out unescaped, so reject any
+                // that could break out of the tag.
+                if key.is_empty() || regex!(r#"[\s"'>/=\x00-\x1f]"#).is_match(key) {
+                    return Err(ParseError::new(
+                        &format!("Invalid attribute name 'data-{}'", key),
+                        None,
+                    ));
+                }
+                attributes.insert(format!("data-{}", key), value.to_string());
+            }
+            parser.settings.is_trusted(&mut TrustContext {
+                command: "\\htmlData",
+                attributes: Some(&attributes),
+                ..Default::default()
+            })
+        }
+        _ => unreachable!("Unrecognized html command"),
+    };
+
+    if !trusted {
+        return Ok(parser.format_unsupported_cmd(&func_name));
+    }
+    Ok(AnyParseNode::Html(HtmlParseNode {
+        type_: "html".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        attributes,
+        body: ordargument(body),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trusted() -> Settings {
+        Settings {
+            trust: true.into(),
+            output: OutputFormat::HtmlAndMathml,
+            ..Default::default()
+        }
+    }
+
+    fn parse_html(input: &str) -> HtmlParseNode {
+        let mut tree = parse_tree(input, &trusted()).unwrap();
+        assert_eq!(tree.len(), 1);
+        let AnyParseNode::Html(node) = tree.remove(0) else {
+            panic!("expected an html node for {}", input);
+        };
+        node
+    }
+
+    #[test]
+    fn attributes() {
+        let html = parse_html("\\htmlClass{a b}{x}");
+        assert_eq!(html.attributes["class"], "a b");
+        let html = parse_html("\\htmlId{eq1}{x}");
+        assert_eq!(html.attributes["id"], "eq1");
+        let html = parse_html("\\htmlStyle{color: red}{x}");
+        assert_eq!(html.attributes["style"], "color: red");
+        let html = parse_html("\\htmlData{foo=a, bar=b}{x}");
+        assert_eq!(html.attributes["data-foo"], "a");
+        assert_eq!(html.attributes.len(), 2);
+    }
+
+    #[test]
+    fn rejected_input() {
+        let error = parse_tree("\\htmlData{foo}{x}", &trusted()).unwrap_err();
+        assert_eq!(error.raw_message(), "\\htmlData key/value 'foo' missing equals sign");
+        assert!(parse_tree("\\htmlData{a\"b=c}{x}", &trusted()).is_err());
+
+        let strict = Settings {
+            strict: true,
+            ..trusted()
+        };
+        assert!(parse_tree("\\htmlId{a}{x}", &strict).is_err());
+
+        let untrusted = Settings::default();
+        let tree = parse_tree("\\htmlId{a}{x}", &untrusted).unwrap();
+        assert!(matches!(&tree[..], [AnyParseNode::Color(_)]));
+        let tree = parse_tree("\\htmlStyle{background:url(javascript:x)}{x}", &untrusted);
+        assert!(matches!(&tree.unwrap()[..], [AnyParseNode::Color(_)]));
+        let markup = render("\\htmlStyle{background:url(javascript:x)}{x}");
+        assert!(!markup.contains("style=\"background"));
+    }
+
+    #[test]
+    fn html_data_is_trimmed_and_validated() {
+        let html = parse_html("\\htmlData{ foo = a , bar=b }{x}");
+        assert_eq!(html.attributes["data-foo"], "a");
+        assert_eq!(html.attributes["data-bar"], "b");
+
+        let error = parse_tree("\\htmlData{a=b=c}{x}", &trusted()).unwrap_err();
+        assert_eq!(
+            error.raw_message(),
+            "\\htmlData key/value 'a=b=c' has more than one equals sign"
+        );
+        let error = parse_tree("\\htmlData{a b=c}{x}", &trusted()).unwrap_err();
+        assert_eq!(error.raw_message(), "Invalid attribute name 'data-a b'");
+        assert!(parse_tree("\\htmlData{=c}{x}", &trusted()).is_err());
+    }
+
+    #[test]
+    fn trust_policy_sees_the_whole_context() {
+        let settings = Settings {
+            trust: TrustPolicy::Fn(Arc::new(|context: &TrustContext| {
+                match context.command {
+                    "\\htmlClass" => context.class == Some("safe"),
+                    "\\href" => context.protocol.as_deref() == Some("https"),
+                    _ => false,
+                }
+            })),
+            ..Default::default()
+        };
+        let trusted = |input| {
+            let tree = parse_tree(input, &settings).unwrap();
+            !matches!(&tree[..], [AnyParseNode::Color(_)])
+        };
+        assert!(trusted("\\htmlClass{safe}{x}"));
+        assert!(!trusted("\\htmlClass{other}{x}"));
+        assert!(!trusted("\\htmlId{safe}{x}"));
+        assert!(trusted("\\href{https://example.com}{x}"));
+        assert!(!trusted("\\href{http://example.com}{x}"));
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render_to_string("\\htmlClass{foo bar}{x}", &trusted());
+        assert!(markup.contains("class=\"enclosing foo bar\""));
+        let markup = render_to_string("\\htmlId{eq1}{x}", &trusted());
+        assert!(markup.contains("id=\"eq1\""));
+        assert!(markup.contains("<mi>x</mi>"));
+
+        let markup = render_to_string("\\color{red}\\htmlStyle{font-weight: bold}{x}", &trusted());
+        assert!(markup.split('<').all(|tag| tag.matches(" style=").count() <= 1));
+        assert!(markup.contains("font-weight:bold;"));
+        assert!(markup.contains("color:red;"));
+    }
+}
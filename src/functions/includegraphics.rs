@@ -0,0 +1,244 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "includegraphics",
+        names: &["\\includegraphics"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            num_optional_args: 1,
+            arg_types: Some(vec![ArgType::Raw, ArgType::Url]),
+            allowed_in_text: false,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn size_data(str: &str) -> Result<Measurement, ParseError> {
+    if regex!(r"^[-+]? *(\d+(\.\d*)?|\.\d+)$").is_match(str) {
+        // str is a number with no unit specified.
+        // default unit is bp, per graphix package.
+        return Ok(Measurement {
+            number: str.replace(' ', "").parse().unwrap_or(0.0),
+            unit: "bp".into(),
+        });
+    }
+    let Some(captures) = regex!(r"([-+]?) *(\d+(?:\.\d*)?|\.\d+) *([a-z]{2})").captures(str) else {
+        return Err(ParseError::new(
+            &format!("Invalid size: '{}' in \\includegraphics", str),
+            None,
+        ));
+    };
+    let data = Measurement {
+        // sign + magnitude, cast to number
+        number: format!("{}{}", &captures[1], &captures[2])
+            .parse()
+            .unwrap_or(0.0),
+        unit: captures[3].to_string(),
+    };
+    if !valid_unit(&data.unit) {
+        return Err(ParseError::new(
+            &format!("Invalid unit: '{}' in \\includegraphics.", data.unit),
+            None,
+        ));
+    }
+    Ok(data)
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Includegraphics(group) = group else {
+        unreachable!()
+    };
+    let height = calculate_size(&group.height, options);
+    let mut depth = 0.0;
+
+    if group.totalheight.number > 0.0 {
+        depth = calculate_size(&group.totalheight, options) - height;
+    }
+
+    let mut width = 0.0;
+    if group.width.number > 0.0 {
+        width = calculate_size(&group.width, options);
+    }
+
+    let mut style = CssStyle::from([("height".to_string(), make_em(height + depth))]);
+    if width > 0.0 {
+        style.insert("width".to_string(), make_em(width));
+    }
+    if depth > 0.0 {
+        style.insert("verticalAlign".to_string(), make_em(-depth));
+    }
+
+    let mut node = Img::new(&group.src, &group.alt, style);
+    node.height = height;
+    node.depth = depth;
+
+    VirtualNode::Img(node)
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Includegraphics(group) = group else {
+        unreachable!()
+    };
+    let mut node = MathNode::new(MathNodeType::Mglyph, vec![], vec![]);
+    node.set_attribute("alt", &group.alt);
+
+    let height = calculate_size(&group.height, options);
+    let mut depth = 0.0;
+    if group.totalheight.number > 0.0 {
+        depth = calculate_size(&group.totalheight, options) - height;
+        node.set_attribute("valign", &make_em(-depth));
+    }
+    node.set_attribute("height", &make_em(height + depth));
+
+    if group.width.number > 0.0 {
+        let width = calculate_size(&group.width, options);
+        node.set_attribute("width", &make_em(width));
+    }
+    node.set_attribute("src", &group.src);
+    node.into()
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    mut opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let mut width = Measurement {
+        number: 0.0,
+        unit: "em".into(),
+    };
+    let mut height = Measurement {
+        number: 0.9,
+        unit: "em".into(),
+    }; // sorta character sized.
+    let mut totalheight = Measurement {
+        number: 0.0,
+        unit: "em".into(),
+    };
+    let mut alt = String::new();
+
+    match opt_args.remove(0) {
+        Some(AnyParseNode::Raw(raw)) => {
+            // The parser does not parse key/value pairs. We get a string.
+            for attribute in raw.string.split(',') {
+                let key_val: Vec<&str> = attribute.split('=').collect();
+                if let [key, val] = key_val[..] {
+                    let str = val.trim();
+                    match key.trim() {
+                        "alt" => alt = str.to_string(),
+                        "width" => width = size_data(str)?,
+                        "height" => height = size_data(str)?,
+                        "totalheight" => totalheight = size_data(str)?,
+                        _ => {
+                            return Err(ParseError::new(
+                                &format!("Invalid key: '{}' in \\includegraphics.", key),
+                                None,
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+        Some(_) => return Err(ParseError::new("Expected node of type raw", None)),
+        None => {}
+    }
+
+    let AnyParseNode::Url(url) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type url", None));
+    };
+    let src = url.url;
+
+    if alt.is_empty() {
+        // No alt given. Use the file name. Strip away the path.
+        let file_name = regex!(r"^.*[\\/]").replace(&src, "");
+        alt = match file_name.rfind('.') {
+            Some(index) => file_name[..index].to_string(),
+            None => file_name.into_owned(),
+        };
+    }
+
+    if !parser.settings.is_trusted(&mut TrustContext {
+        command: "\\includegraphics",
+        url: Some(&src),
+        ..Default::default()
+    }) {
+        return Ok(parser.format_unsupported_cmd("\\includegraphics"));
+    }
+
+    Ok(AnyParseNode::Includegraphics(IncludegraphicsParseNode {
+        type_: "includegraphics".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        alt,
+        width,
+        height,
+        totalheight,
+        src,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trusted() -> Settings {
+        Settings {
+            trust: true.into(),
+            output: OutputFormat::HtmlAndMathml,
+            ..Default::default()
+        }
+    }
+
+    fn parse_graphics(input: &str) -> IncludegraphicsParseNode {
+        let mut tree = parse_tree(input, &trusted()).unwrap();
+        assert_eq!(tree.len(), 1);
+        let AnyParseNode::Includegraphics(node) = tree.remove(0) else {
+            panic!("expected an includegraphics node for {}", input);
+        };
+        node
+    }
+
+    #[test]
+    fn options() {
+        let graphics = parse_graphics("\\includegraphics{img/plot.png}");
+        assert_eq!(graphics.src, "img/plot.png");
+        assert_eq!(graphics.alt, "plot");
+        assert_eq!(graphics.height.number, 0.9);
+        assert_eq!(parse_graphics("\\includegraphics{img/plot}").alt, "plot");
+
+        let graphics = parse_graphics(
+            "\\includegraphics[height=2em, width=3em, totalheight=2.5em, alt=A plot]{plot.png}",
+        );
+        assert_eq!(graphics.alt, "A plot");
+        assert_eq!(graphics.width.number, 3.0);
+        assert_eq!(graphics.height.number, 2.0);
+        assert_eq!(graphics.totalheight.number, 2.5);
+
+        let error = parse_tree("\\includegraphics[size=1em]{a.png}", &trusted());
+        assert_eq!(
+            error.unwrap_err().raw_message(),
+            "Invalid key: 'size' in \\includegraphics."
+        );
+    }
+
+    #[test]
+    fn untrusted_images_are_not_loaded() {
+        let tree = parse_tree("\\includegraphics{javascript:alert(1)}", &Settings::default());
+        assert!(matches!(&tree.unwrap()[..], [AnyParseNode::Color(_)]));
+        assert!(!render("\\includegraphics{plot.png}").contains("<img"));
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render_to_string("\\includegraphics[height=2em]{plot.png}", &trusted());
+        assert!(markup.contains("<img"));
+        assert!(markup.contains("src=\"plot.png\""));
+        assert!(markup.contains("alt=\"plot\""));
+        assert!(markup.contains("<mglyph"));
+    }
+}
@@ -0,0 +1,135 @@
+//! Horizontal spacing commands
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // TODO: \hskip and \mskip should support plus and minus in lengths
+    registry.define_function(FunctionDefSpec {
+        node_type: "kern",
+        names: &["\\kern", "\\mkern", "\\hskip", "\\mskip"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Size]),
+            primitive: true,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Kern(group) = group else {
+        unreachable!()
+    };
+    make_glue(&group.dimension, options).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Kern(group) = group else {
+        unreachable!()
+    };
+    let dimension = calculate_size(&group.dimension, options);
+    SpaceNode::new(dimension).into()
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser,
+        func_name,
+        token,
+        ..
+    } = context;
+    let AnyParseNode::Size(size) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type size", None));
+    };
+    let math_function = func_name.as_bytes()[1] == b'm'; // \mkern, \mskip
+    let mu_unit = size.value.unit == "mu";
+    if math_function {
+        if !mu_unit {
+            parser.settings.report_nonstrict(
+                "mathVsTextUnits",
+                &format!(
+                    "LaTeX's {} supports only mu units, not {} units",
+                    func_name, size.value.unit
+                ),
+                token.as_ref(),
+            )?;
+        }
+        if parser.mode != Mode::Math {
+            parser.settings.report_nonstrict(
+                "mathVsTextUnits",
+                &format!("LaTeX's {} works only in math mode", func_name),
+                token.as_ref(),
+            )?;
+        }
+    } else if mu_unit {
+        parser.settings.report_nonstrict(
+            "mathVsTextUnits",
+            &format!("LaTeX's {} doesn't support mu units", func_name),
+            token.as_ref(),
+        )?;
+    }
+    Ok(AnyParseNode::Kern(KernParseNode {
+        type_: "kern".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        dimension: size.value,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kerns(input: &str) -> Vec<Measurement> {
+        let tree = parse_tree(input, &Settings::default()).unwrap();
+        tree.into_iter()
+            .filter_map(|node| match node {
+                AnyParseNode::Kern(kern) => Some(kern.dimension),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn spacing_macros_expand_to_kerns() {
+        let units = |input| {
+            kerns(input)
+                .into_iter()
+                .map(|size| (size.number, size.unit))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(units("\\kern1em"), [(1.0, "em".to_string())]);
+        assert_eq!(units("a\\,b"), [(3.0, "mu".to_string())]);
+        assert_eq!(units("a\\!b"), [(-3.0, "mu".to_string())]);
+        assert_eq!(units("\\qquad"), [(2.0, "em".to_string())]);
+        assert_eq!(units("\\textrm{a\\,b}"), []);
+    }
+
+    #[test]
+    fn math_kerns_need_mu_units_in_strict_mode() {
+        let strict = Settings {
+            strict: true,
+            ..Default::default()
+        };
+        assert!(parse_tree("\\mkern3mu", &strict).is_ok());
+        let error = parse_tree("\\mkern1em", &strict).unwrap_err();
+        assert!(error.raw_message().contains("supports only mu units"));
+        let error = parse_tree("\\kern3mu", &strict).unwrap_err();
+        assert!(error.raw_message().contains("doesn't support mu units"));
+        assert!(parse_tree("\\mkern1em", &Settings::default()).is_ok());
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("a\\quad b");
+        assert!(markup.contains("margin-right:1em;"));
+        assert!(markup.contains("<mspace width=\"1em\"/>"));
+    }
+}
@@ -0,0 +1,119 @@
+//! Horizontal overlap functions
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "lap",
+        names: &["\\mathllap", "\\mathrlap", "\\mathclap"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Lap(group) = group else {
+        unreachable!()
+    };
+    // mathllap, mathrlap, mathclap
+    let body = build_html::build_group(Some(&group.body), options, None);
+    let inner = if group.alignment == "clap" {
+        // ref: https://www.math.lsu.edu/~aperlis/publications/mathclap/
+        let inner = make_span(vec![], vec![body], None, None);
+        // wrap, since CSS will center a .clap > .inner > span
+        make_span(
+            vec!["inner".to_string()],
+            vec![inner.into()],
+            Some(options),
+            None,
+        )
+    } else {
+        make_span(vec!["inner".to_string()], vec![body], None, None)
+    };
+    let fix = make_span(vec!["fix".to_string()], vec![], None, None);
+    let mut node = make_span(
+        vec![group.alignment.clone()],
+        vec![inner.into(), fix.into()],
+        Some(options),
+        None,
+    );
+
+    // At this point, we have correctly set horizontal alignment of the
+    // two items involved in the lap.
+    // Next, use a strut to set the height of the HTML bounding box.
+    // Otherwise, a tall argument may be misplaced.
+    // This code resolved issue #1153
+    let mut strut = make_span(vec!["strut".to_string()], vec![], None, None);
+    strut
+        .style
+        .insert("height".to_string(), make_em(node.height + node.depth));
+    if node.depth != 0.0 {
+        strut
+            .style
+            .insert("verticalAlign".to_string(), make_em(-node.depth));
+    }
+    node.children.insert(0, strut.into());
+
+    // Next, prevent vertical misplacement when next to something tall.
+    // This code resolves issue #1234
+    let node = make_span(
+        vec!["thinbox".to_string()],
+        vec![node.into()],
+        Some(options),
+        None,
+    );
+    make_span(
+        vec!["mord".to_string(), "vbox".to_string()],
+        vec![node.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Lap(group) = group else {
+        unreachable!()
+    };
+    // mathllap, mathrlap, mathclap
+    let mut node = MathNode::new(
+        MathNodeType::Mpadded,
+        vec![build_mathml::build_group(Some(&group.body), options)],
+        vec![],
+    );
+
+    if group.alignment != "rlap" {
+        let offset = if group.alignment == "llap" {
+            "-1"
+        } else {
+            "-0.5"
+        };
+        node.set_attribute("lspace", &format!("{}width", offset));
+    }
+    node.set_attribute("width", "0px");
+
+    node.into()
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let body = args.remove(0);
+    Ok(AnyParseNode::Lap(LapParseNode {
+        type_: "lap".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        alignment: func_name[5..].to_string(),
+        body: Box::new(body),
+    }))
+}
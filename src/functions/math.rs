@@ -0,0 +1,131 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // Switching from text mode back to math mode
+    registry.define_function(FunctionDefSpec {
+        node_type: "styling",
+        names: &["\\(", "$"],
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            allowed_in_math: false,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // Check for extra closing math delimiters
+    registry.define_function(FunctionDefSpec {
+        node_type: "text", // Doesn't matter what this is.
+        names: &["\\)", "\\]"],
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            allowed_in_math: false,
+            ..Default::default()
+        },
+        handler: Some(mismatched_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let outer_mode = parser.mode;
+    parser.switch_mode(Mode::Math);
+    let close = if func_name == "\\(" { "\\)" } else { "$" };
+    let body = parser.parse_expression(false, Some(close))?;
+    parser.expect(close, true)?;
+    parser.switch_mode(outer_mode);
+    Ok(AnyParseNode::Styling(StylingParseNode {
+        type_: "styling".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        style: "text".into(),
+        body,
+    }))
+}
+
+fn mismatched_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    Err(ParseError::new(
+        &format!("Mismatched {}", context.func_name),
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_text(input: &str) -> TextParseNode {
+        let AnyParseNode::Text(node) = parse_one(input) else {
+            panic!("expected a text node for {}", input);
+        };
+        node
+    }
+
+    #[test]
+    fn math_inside_text() {
+        let text = parse_text("\\text{a $x^2$ b}");
+        let [_, _, AnyParseNode::Styling(math), _, _] = &text.body[..] else {
+            panic!("expected math between the words, got {:?}", text.body);
+        };
+        assert_eq!(math.style, "text");
+        assert_eq!(math.mode, "text");
+        assert!(matches!(&math.body[..], [AnyParseNode::SupSub(_)]));
+
+        let text = parse_text("\\mbox{\\(x\\)}");
+        assert!(matches!(&text.body[..], [AnyParseNode::Styling(_)]));
+    }
+
+    #[test]
+    fn mismatched_delimiters() {
+        let error = parse_tree("\\text{a \\) b}", &Settings::default()).unwrap_err();
+        assert_eq!(error.raw_message(), "Mismatched \\)");
+        let error = parse_tree("\\text{\\(x$}", &Settings::default()).unwrap_err();
+        assert_eq!(error.raw_message(), "Can't use function '$' in math mode");
+        assert!(parse_tree("x$", &Settings::default()).is_err());
+    }
+
+    #[test]
+    fn hbox_holds_text() {
+        let AnyParseNode::Hbox(hbox) = parse_one("\\hbox{a b}") else {
+            panic!("expected an hbox node");
+        };
+        assert_eq!(hbox.body.len(), 3);
+        assert!(hbox.body.iter().all(|node| node.mode() == "text"));
+    }
+
+    #[test]
+    fn layout() {
+        // Math inside text starts in text style, like $...$ in a paragraph.
+        let inline = build_one("\\text{$\\frac{1}{2}$}");
+        let display = build_one("\\text{$\\displaystyle\\frac{1}{2}$}");
+        assert!(display.height() > inline.height() + 0.2);
+        assert!(inline.height() > build_one("\\text{1}").height());
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\text{if $x$}");
+        assert!(markup.contains("<mtext>if\u{a0}</mtext>"));
+        assert!(markup.contains("<mi>x</mi>"));
+
+        let markup = render("\\fbox{$\\frac{1}{2}$}");
+        assert!(markup.contains("fbox"));
+        assert!(markup.contains("<mfrac>"));
+    }
+}
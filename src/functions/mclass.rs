@@ -0,0 +1,289 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // Math class commands except \mathop
+    registry.define_function(FunctionDefSpec {
+        node_type: "mclass",
+        names: &[
+            "\\mathord",
+            "\\mathbin",
+            "\\mathrel",
+            "\\mathopen",
+            "\\mathclose",
+            "\\mathpunct",
+            "\\mathinner",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    // \@binrel{x}{y} renders like y but as mbin/mrel/mord if x is mbin/mrel/mord.
+    // This is equivalent to \binrel@{x}\binrel@@{y} in AMSTeX.
+    registry.define_function(FunctionDefSpec {
+        node_type: "mclass",
+        names: &["\\@binrel"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            ..Default::default()
+        },
+        handler: Some(binrel_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // Build a relation or stacked op by placing one symbol on top of another
+    registry.define_function(FunctionDefSpec {
+        node_type: "mclass",
+        names: &["\\stackrel", "\\overset", "\\underset"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            ..Default::default()
+        },
+        handler: Some(stacked_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Mclass(group) = group else {
+        unreachable!()
+    };
+    let elements = build_html::build_expression(&group.body, options, true, false, [None, None]);
+    make_span(vec![group.mclass.clone()], elements, Some(options), None).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Mclass(group) = group else {
+        unreachable!()
+    };
+    let mut inner = build_mathml::build_expression(&group.body, options, false);
+
+    // A character box holds a single token, which we retype instead of
+    // wrapping it in another node.
+    let retype = |inner: &mut Vec<MathDomNode>, type_: MathNodeType| match inner.as_mut_slice() {
+        [MathDomNode::Math(node)] => {
+            node.type_ = type_;
+            inner.remove(0)
+        }
+        _ => MathNode::new(type_, std::mem::take(inner), vec![]).into(),
+    };
+
+    if group.mclass == "minner" {
+        MathNode::new(MathNodeType::Mpadded, inner, vec![]).into()
+    } else if group.mclass == "mord" {
+        if group.is_character_box {
+            retype(&mut inner, MathNodeType::Mi)
+        } else {
+            MathNode::new(MathNodeType::Mi, inner, vec![]).into()
+        }
+    } else {
+        let mut node = if group.is_character_box {
+            retype(&mut inner, MathNodeType::Mo)
+        } else {
+            MathNode::new(MathNodeType::Mo, inner, vec![]).into()
+        };
+
+        // Set spacing based on what is the most likely adjacent atom type.
+        // See TeXbook p170.
+        if let MathDomNode::Math(node) = &mut node {
+            match group.mclass.as_str() {
+                "mbin" => {
+                    node.set_attribute("lspace", "0.22em"); // medium space
+                    node.set_attribute("rspace", "0.22em");
+                }
+                "mpunct" => {
+                    node.set_attribute("lspace", "0em");
+                    node.set_attribute("rspace", "0.17em"); // thinspace
+                }
+                "mopen" | "mclose" => {
+                    node.set_attribute("lspace", "0em");
+                    node.set_attribute("rspace", "0em");
+                }
+                "minner" => {
+                    node.set_attribute("lspace", "0.0556em"); // 1 mu is the most likely option
+                    node.set_attribute("width", "+0.1111em");
+                }
+                // MathML <mo> default space is 5/18 em, so <mrel> needs no action.
+                // Ref: https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mo
+                _ => {}
+            }
+        }
+        node
+    }
+}
+
+/// \binrel@ spacing varies with (bin|rel|ord) of the atom in the argument.
+/// (by rendering separately and with {}s before and after, and measuring
+/// the change in spacing).  We'll do roughly the same by detecting the
+/// atom type directly.
+pub fn binrel_class(arg: &AnyParseNode) -> String {
+    let atom = match arg {
+        AnyParseNode::OrdGroup(group) if !group.body.is_empty() => &group.body[0],
+        arg => arg,
+    };
+    match atom {
+        AnyParseNode::Atom(atom) if atom.family == Group::Bin => "mbin".to_string(),
+        AnyParseNode::Atom(atom) if atom.family == Group::Rel => "mrel".to_string(),
+        _ => "mord".to_string(),
+    }
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let body = args.remove(0);
+    let is_character_box = utils::is_character_box(&body);
+    Ok(AnyParseNode::Mclass(MclassParseNode {
+        type_: "mclass".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        mclass: format!("m{}", &func_name[5..]), // TODO(kevinb): don't prefix with 'm'
+        body: ordargument(body),
+        is_character_box,
+    }))
+}
+
+fn binrel_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(1);
+    let is_character_box = utils::is_character_box(&body);
+    Ok(AnyParseNode::Mclass(MclassParseNode {
+        type_: "mclass".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        mclass: binrel_class(&args[0]),
+        body: ordargument(body),
+        is_character_box,
+    }))
+}
+
+fn stacked_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let base_arg = args.remove(1);
+    let shifted_arg = args.remove(0);
+
+    let mclass = if func_name != "\\stackrel" {
+        // LaTeX applies \binrel spacing to \overset and \underset.
+        binrel_class(&base_arg)
+    } else {
+        "mrel".to_string() // for \stackrel
+    };
+
+    let base_op = AnyParseNode::Op(OpParseNode {
+        type_: "op".into(),
+        mode: base_arg.mode().to_string(),
+        loc: None,
+        limits: true,
+        always_handle_sup_sub: Some(true),
+        suppress_base_shift: Some(func_name != "\\stackrel"),
+        parent_is_sup_sub: false,
+        symbol: false,
+        name: None,
+        body: Some(ordargument(base_arg)),
+    });
+
+    let shifted_mode = shifted_arg.mode().to_string();
+    let shifted = Some(Box::new(shifted_arg));
+    let (sup, sub) = if func_name == "\\underset" {
+        (None, shifted)
+    } else {
+        (shifted, None)
+    };
+    let supsub = AnyParseNode::SupSub(SupSubParseNode {
+        type_: "supsub".into(),
+        mode: shifted_mode,
+        loc: None,
+        base: Some(Box::new(base_op)),
+        sup,
+        sub,
+    });
+
+    let is_character_box = utils::is_character_box(&supsub);
+    Ok(AnyParseNode::Mclass(MclassParseNode {
+        type_: "mclass".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        mclass,
+        body: vec![supsub],
+        is_character_box,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_mclass(input: &str) -> MclassParseNode {
+        let AnyParseNode::Mclass(node) = parse_one(input) else {
+            panic!("expected an mclass node for {}", input);
+        };
+        node
+    }
+
+    #[test]
+    fn class_commands() {
+        assert_eq!(parse_mclass("\\mathbin{x}").mclass, "mbin");
+        assert_eq!(parse_mclass("\\mathrel{x}").mclass, "mrel");
+        assert_eq!(parse_mclass("\\mathinner{x}").mclass, "minner");
+        assert!(parse_mclass("\\mathpunct{x}").is_character_box);
+        assert!(!parse_mclass("\\mathord{xy}").is_character_box);
+    }
+
+    #[test]
+    fn stacked_relations() {
+        let stackrel = parse_mclass("\\stackrel{a}{+}");
+        assert_eq!(stackrel.mclass, "mrel");
+        let [AnyParseNode::SupSub(supsub)] = &stackrel.body[..] else {
+            panic!("expected a supsub body");
+        };
+        assert!(supsub.sup.is_some() && supsub.sub.is_none());
+
+        assert_eq!(parse_mclass("\\overset{a}{+}").mclass, "mbin");
+        let underset = parse_mclass("\\underset{a}{=}");
+        assert_eq!(underset.mclass, "mrel");
+        let [AnyParseNode::SupSub(supsub)] = &underset.body[..] else {
+            panic!("expected a supsub body");
+        };
+        assert!(supsub.sup.is_none() && supsub.sub.is_some());
+    }
+
+    #[test]
+    fn layout() {
+        // \overset sets its script above the base and \underset below it.
+        let base = build_one("=");
+        let over = build_one("\\overset{a}{=}");
+        assert!(over.height() > base.height() + 0.3);
+        assert_eq!(over.depth(), base.depth().max(0.0));
+        let under = build_one("\\underset{a}{=}");
+        assert!(under.depth() > base.depth() + 0.3);
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("a\\mathbin{x}b");
+        assert!(markup.contains("class=\"mbin\""));
+        assert!(markup.contains("lspace=\"0.22em\""));
+    }
+}
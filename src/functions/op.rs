@@ -0,0 +1,568 @@
+//! Limits, symbols
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "op",
+        names: &[
+            "\\coprod",
+            "\\bigvee",
+            "\\bigwedge",
+            "\\biguplus",
+            "\\bigcap",
+            "\\bigcup",
+            "\\intop",
+            "\\prod",
+            "\\sum",
+            "\\bigotimes",
+            "\\bigoplus",
+            "\\bigodot",
+            "\\bigsqcup",
+            "\\smallint",
+            "\u{220F}",
+            "\u{2210}",
+            "\u{2211}",
+            "\u{22c0}",
+            "\u{22c1}",
+            "\u{22c2}",
+            "\u{22c3}",
+            "\u{2a00}",
+            "\u{2a01}",
+            "\u{2a02}",
+            "\u{2a04}",
+            "\u{2a06}",
+        ],
+        props: FunctionPropSpec {
+            num_args: 0,
+            ..Default::default()
+        },
+        handler: Some(big_op_handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "op",
+        names: &["\\mathop"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(mathop_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // There are 2 flags for operators; whether they produce limits in
+    // displaystyle, and whether they are symbols and should grow in
+    // displaystyle. These four groups cover the four possible choices.
+
+    // No limits, not symbols
+    registry.define_function(FunctionDefSpec {
+        node_type: "op",
+        names: &[
+            "\\arcsin", "\\arccos", "\\arctan", "\\arctg", "\\arcctg", "\\arg", "\\ch", "\\cos",
+            "\\cosec", "\\cosh", "\\cot", "\\cotg", "\\coth", "\\csc", "\\ctg", "\\cth", "\\deg",
+            "\\dim", "\\exp", "\\hom", "\\ker", "\\lg", "\\ln", "\\log", "\\sec", "\\sin",
+            "\\sinh", "\\sh", "\\tan", "\\tanh", "\\tg", "\\th",
+        ],
+        props: FunctionPropSpec {
+            num_args: 0,
+            ..Default::default()
+        },
+        handler: Some(text_op_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // Limits, not symbols
+    registry.define_function(FunctionDefSpec {
+        node_type: "op",
+        names: &[
+            "\\det", "\\gcd", "\\inf", "\\lim", "\\max", "\\min", "\\Pr", "\\sup",
+        ],
+        props: FunctionPropSpec {
+            num_args: 0,
+            ..Default::default()
+        },
+        handler: Some(text_op_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+
+    // No limits, symbols
+    registry.define_function(FunctionDefSpec {
+        node_type: "op",
+        names: &[
+            "\\int", "\\iint", "\\iiint", "\\oint", "\\oiint", "\\oiiint", "\u{222b}", "\u{222c}",
+            "\u{222d}", "\u{222e}", "\u{222f}", "\u{2230}",
+        ],
+        props: FunctionPropSpec {
+            num_args: 0,
+            ..Default::default()
+        },
+        handler: Some(integral_handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+// Most operators have a large successor symbol, but these don't.
+const NO_SUCCESSOR: &[&str] = &["\\smallint"];
+
+/// NOTE: Unlike most `html_builder`s, this one handles not only "op", but also
+/// "supsub" since some of them (like \int) can affect super/subscripting.
+pub fn html_builder(grp: &AnyParseNode, options: &Options) -> VirtualNode {
+    // Operators are handled in the TeXbook pg. 443-444, rule 13(a).
+    let (group, sup_group, sub_group, has_limits) = match grp {
+        AnyParseNode::SupSub(supsub) => {
+            // If we have limits, supsub will pass us its group to handle. Pull
+            // out the superscript and subscript and set the group to the op in
+            // its base.
+            let Some(AnyParseNode::Op(group)) = supsub.base.as_deref() else {
+                panic!("Expected node of type op as the base of a supsub");
+            };
+            (group, supsub.sup.as_deref(), supsub.sub.as_deref(), true)
+        }
+        AnyParseNode::Op(group) => (group, None, None, false),
+        _ => unreachable!(),
+    };
+
+    let style = options.style;
+    let name = group.name.as_deref().unwrap_or_default();
+
+    // Most symbol operators get larger in displaystyle (rule 13)
+    let large = style.size == Style::DISPLAY.size && group.symbol && !NO_SUCCESSOR.contains(&name);
+
+    let mut base: VirtualNode;
+    if group.symbol {
+        // If this is a symbol, create the symbol.
+        let font_name = if large {
+            "Size2-Regular"
+        } else {
+            "Size1-Regular"
+        };
+
+        let mut stash = "";
+        let mut symbol_name = name;
+        if name == "\\oiint" || name == "\\oiiint" {
+            // No font glyphs yet, so use a glyph w/o the oval.
+            // TODO: When font glyphs are available, delete this code.
+            stash = &name[1..];
+            symbol_name = if stash == "oiint" {
+                "\\iint"
+            } else {
+                "\\iiint"
+            };
+        }
+
+        let symbol = make_symbol(
+            symbol_name,
+            font_name,
+            Mode::Math,
+            Some(options),
+            vec![
+                "mop".to_string(),
+                "op-symbol".to_string(),
+                if large { "large-op" } else { "small-op" }.to_string(),
+            ],
+        );
+
+        if !stash.is_empty() {
+            // We're in \oiint or \oiiint. Overlay the oval.
+            // TODO: When font glyphs are available, delete this code.
+            let oval = static_svg(
+                &format!("{}Size{}", stash, if large { "2" } else { "1" }),
+                options,
+            );
+            let mut vlist = make_v_list(VListParam::IndividualShift(vec![
+                VListElem::shifted(symbol, 0.0),
+                VListElem::shifted(oval, if large { 0.08 } else { 0.0 }),
+            ]));
+            vlist.classes.insert(0, "mop".to_string());
+            base = vlist.into();
+        } else {
+            base = symbol.into();
+        }
+    } else if let Some(body) = &group.body {
+        // If this is a list, compose that list.
+        let mut inner = build_html::build_expression(body, options, true, false, [None, None]);
+        if let [VirtualNode::Symbol(_)] = inner.as_slice() {
+            let VirtualNode::Symbol(mut symbol) = inner.remove(0) else {
+                unreachable!()
+            };
+            symbol.classes[0] = "mop".to_string(); // replace old mclass
+            base = symbol.into();
+        } else {
+            base = make_span(vec!["mop".to_string()], inner, Some(options), None).into();
+        }
+    } else {
+        // Otherwise, this is a text operator. Build the text from the
+        // operator's name.
+        let mode = Mode::from(group.mode.as_str());
+        let output = name[1..]
+            .chars()
+            .map(|c| math_sym(&c.to_string(), mode, options, vec![]).into())
+            .collect();
+        base = make_span(vec!["mop".to_string()], output, Some(options), None).into();
+    }
+
+    // If content of op is a single symbol, shift it vertically.
+    let mut base_shift = 0.0;
+    let mut slant = 0.0;
+    let is_oval = name == "\\oiint" || name == "\\oiiint";
+    if (matches!(base, VirtualNode::Symbol(_)) || is_oval)
+        && group.suppress_base_shift != Some(true)
+    {
+        // We suppress the shift of the base of \overset and \underset. Otherwise,
+        // shift the symbol so its center lies on the axis (rule 13). It
+        // appears that our fonts have the centers of the symbols already
+        // almost on the axis, so these numbers are very small. Note we
+        // don't actually apply this here, but instead it is used either in
+        // the vlist creation or separately when there are no limits.
+        base_shift = (base.height() - base.depth()) / 2.0 - options.font_metrics().axis_height;
+
+        // The slant of the symbol is just its italic correction.
+        slant = match &base {
+            VirtualNode::Symbol(symbol) => symbol.italic,
+            _ => oval_italic(name, options),
+        };
+    }
+
+    if has_limits {
+        assemble_sup_sub(
+            base, sup_group, sub_group, options, style, slant, base_shift,
+        )
+    } else {
+        if base_shift != 0.0 {
+            if let Some(style) = base.style_mut() {
+                style.insert("position".to_string(), "relative".to_string());
+                style.insert("top".to_string(), make_em(base_shift));
+            }
+        }
+
+        base
+    }
+}
+
+/// The italic correction of \oiint or \oiiint. Their base is a vlist
+/// overlaying the oval on the \iint or \iiint glyph, so it is not a symbol
+/// and has no italic correction of its own.
+pub fn oval_italic(name: &str, options: &Options) -> f64 {
+    let large = options.style.size == Style::DISPLAY.size;
+    make_symbol(
+        if name == "\\oiint" {
+            "\\iint"
+        } else {
+            "\\iiint"
+        },
+        if large {
+            "Size2-Regular"
+        } else {
+            "Size1-Regular"
+        },
+        Mode::Math,
+        Some(options),
+        vec![],
+    )
+    .italic
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Op(group) = group else {
+        unreachable!()
+    };
+    let name = group.name.as_deref().unwrap_or_default();
+    let mode = Mode::from(group.mode.as_str());
+
+    if group.symbol {
+        // This is a symbol. Just add the symbol.
+        let mut node = MathNode::new(
+            MathNodeType::Mo,
+            vec![build_mathml::make_text(name, mode, None).into()],
+            vec![],
+        );
+        if NO_SUCCESSOR.contains(&name) {
+            node.set_attribute("largeop", "false");
+        }
+        node.into()
+    } else if let Some(body) = &group.body {
+        // This is an operator with children. Add them.
+        MathNode::new(
+            MathNodeType::Mo,
+            build_mathml::build_expression(body, options, false),
+            vec![],
+        )
+        .into()
+    } else {
+        // This is a text operator. Add all of the characters from the
+        // operator's name.
+        let node = MathNode::new(
+            MathNodeType::Mi,
+            vec![TextNode::new(&name[1..]).into()],
+            vec![],
+        );
+        // Append an <mo>&ApplyFunction;</mo>.
+        // ref: https://www.w3.org/TR/REC-MathML/chap3_2.html#sec3.2.4
+        let operator = MathNode::new(
+            MathNodeType::Mo,
+            vec![build_mathml::make_text("\u{2061}", Mode::Text, None).into()],
+            vec![],
+        );
+        if group.parent_is_sup_sub {
+            MathNode::new(
+                MathNodeType::Mrow,
+                vec![node.into(), operator.into()],
+                vec![],
+            )
+            .into()
+        } else {
+            MathDomNode::Fragment(vec![node.into(), operator.into()])
+        }
+    }
+}
+
+fn single_char_big_op(name: &str) -> &str {
+    match name {
+        "\u{220F}" => "\\prod",
+        "\u{2210}" => "\\coprod",
+        "\u{2211}" => "\\sum",
+        "\u{22c0}" => "\\bigwedge",
+        "\u{22c1}" => "\\bigvee",
+        "\u{22c2}" => "\\bigcap",
+        "\u{22c3}" => "\\bigcup",
+        "\u{2a00}" => "\\bigodot",
+        "\u{2a01}" => "\\bigoplus",
+        "\u{2a02}" => "\\bigotimes",
+        "\u{2a04}" => "\\biguplus",
+        "\u{2a06}" => "\\bigsqcup",
+        name => name,
+    }
+}
+
+fn single_char_integral(name: &str) -> &str {
+    match name {
+        "\u{222b}" => "\\int",
+        "\u{222c}" => "\\iint",
+        "\u{222d}" => "\\iiint",
+        "\u{222e}" => "\\oint",
+        "\u{222f}" => "\\oiint",
+        "\u{2230}" => "\\oiiint",
+        name => name,
+    }
+}
+
+fn op_node(parser: &Parser, limits: bool, symbol: bool, name: &str) -> AnyParseNode {
+    AnyParseNode::Op(OpParseNode {
+        type_: "op".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        limits,
+        always_handle_sup_sub: None,
+        suppress_base_shift: None,
+        parent_is_sup_sub: false,
+        symbol,
+        name: Some(name.to_string()),
+        body: None,
+    })
+}
+
+/// The op made by `\mathchar` for a character of the large operator class:
+/// the named operator for the character, like `\sum` for ∑, or else an op
+/// of the character itself, as `\mathop` would make.
+pub fn mathchar_op(parser: &Parser, text: &str) -> AnyParseNode {
+    let name = single_char_integral(single_char_big_op(text));
+    if name != text {
+        return op_node(parser, true, true, name);
+    }
+    AnyParseNode::Op(OpParseNode {
+        type_: "op".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        limits: true,
+        always_handle_sup_sub: None,
+        suppress_base_shift: None,
+        parent_is_sup_sub: false,
+        symbol: false,
+        name: None,
+        body: Some(vec![AnyParseNode::TextOrd(TextOrdParseNode {
+            type_: "textord".into(),
+            mode: parser.mode.to_string(),
+            loc: None,
+            text: text.to_string(),
+        })]),
+    })
+}
+
+fn big_op_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    Ok(op_node(parser, true, true, single_char_big_op(&func_name)))
+}
+
+fn mathop_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(0);
+    Ok(AnyParseNode::Op(OpParseNode {
+        type_: "op".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        limits: false,
+        always_handle_sup_sub: None,
+        suppress_base_shift: None,
+        parent_is_sup_sub: false,
+        symbol: false,
+        name: None,
+        body: Some(ordargument(body)),
+    }))
+}
+
+fn text_op_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    // \det, \gcd, \inf, \lim, \max, \min, \Pr and \sup have limits.
+    let limits = matches!(
+        func_name.as_str(),
+        "\\det" | "\\gcd" | "\\inf" | "\\lim" | "\\max" | "\\min" | "\\Pr" | "\\sup"
+    );
+    Ok(op_node(parser, limits, false, &func_name))
+}
+
+fn integral_handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    Ok(op_node(
+        parser,
+        false,
+        true,
+        single_char_integral(&func_name),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_op(input: &str) -> OpParseNode {
+        match parse_one(input) {
+            AnyParseNode::Op(op) => op,
+            node => panic!("expected an op node for {}, got {}", input, node.type_()),
+        }
+    }
+
+    #[test]
+    fn limits_and_symbols() {
+        let sum = parse_op("\\sum");
+        assert!(sum.symbol && sum.limits);
+        assert_eq!(sum.name.as_deref(), Some("\\sum"));
+
+        let int = parse_op("\\int");
+        assert!(int.symbol && !int.limits);
+
+        let sin = parse_op("\\sin");
+        assert!(!sin.symbol && !sin.limits);
+        let lim = parse_op("\\lim");
+        assert!(!lim.symbol && lim.limits);
+
+        let mathop = parse_op("\\mathop{x}");
+        assert!(mathop.name.is_none() && mathop.body.is_some());
+    }
+
+    #[test]
+    fn mathchar_large_operators() {
+        // Class 1 characters of cmex are the named large operators.
+        let sum = parse_op("\\mathchar\"1350");
+        assert_eq!(sum.name.as_deref(), Some("\\sum"));
+        assert!(sum.symbol && sum.limits);
+        let int = parse_op("\\mathchar\"1352");
+        assert_eq!(int.name.as_deref(), Some("\\int"));
+        assert!(int.symbol && int.limits);
+
+        // Any other character becomes an op of its own.
+        let x = parse_op("\\mathchar\"1078");
+        assert!(x.name.is_none() && !x.symbol && x.limits);
+        let body = x.body.unwrap();
+        assert!(matches!(&body[..], [AnyParseNode::TextOrd(x)] if x.text == "x"));
+    }
+
+    #[test]
+    fn layout() {
+        // Symbol operators grow in display style; limits go above and below.
+        let options = Options::new(OptionsData::default());
+        let display = options.having_style(Style::DISPLAY);
+        let sum = parse_one("\\sum");
+        let text_sum = build_html::build_group(Some(&sum), &options, None);
+        let display_sum = build_html::build_group(Some(&sum), &display, None);
+        assert!(display_sum.height() > text_sum.height() + 0.25);
+        assert!(display_sum.depth() > text_sum.depth() + 0.25);
+
+        let limits = parse_one("\\sum_{i=0}^n");
+        let display_limits = build_html::build_group(Some(&limits), &display, None);
+        assert!(display_limits.height() > display_sum.height() + 0.3);
+        assert!(display_limits.depth() > display_sum.depth() + 0.3);
+    }
+
+    #[test]
+    fn operatorname() {
+        let expression = "\\operatorname{tr}\\operatorname*{argmax}";
+        let tree = parse_tree(expression, &Settings::default()).unwrap();
+        let [AnyParseNode::Operatorname(tr), AnyParseNode::Operatorname(argmax)] = &tree[..] else {
+            panic!("expected two operatorname nodes");
+        };
+        assert!(!tr.always_handle_sup_sub && !tr.limits);
+        assert!(argmax.always_handle_sup_sub);
+
+        let expression = "\\DeclareMathOperator{\\tr}{tr}\\tr A";
+        let tree = parse_tree(expression, &Settings::default()).unwrap();
+        assert!(matches!(tree[..], [AnyParseNode::Operatorname(_), AnyParseNode::MathOrd(_)]));
+    }
+
+    #[test]
+    fn markup() {
+        let sum = render_display("\\sum_{i=0}^n");
+        assert!(sum.contains("<munderover><mo>\u{2211}</mo>"));
+        assert!(sum.contains("class=\"mop op-limits\""));
+
+        let int = render_display("\\int_0^1");
+        assert!(int.contains("<msubsup><mo>\u{222b}</mo><mn>0</mn><mn>1</mn></msubsup>"));
+
+        let sin = render_display("\\sin x");
+        assert!(sin.contains("<mi>sin</mi><mo>\u{2061}</mo><mi>x</mi>"));
+        assert!(sin.contains("<span class=\"mop\">sin</span>"));
+
+        let tr = render_display("\\operatorname{tr} A");
+        assert!(tr.contains("<mi mathvariant=\"normal\">tr</mi><mo>\u{2061}</mo>"));
+
+        let argmax = render_display("\\operatorname*{argmax}_x");
+        assert!(argmax.contains("<munder><mrow><mi mathvariant=\"normal\">argmax</mi>"));
+    }
+
+    #[test]
+    fn contour_integrals_draw_their_ovals() {
+        for markup in [render_display("\\oiint"), render("\\oiiint")] {
+            assert!(markup.contains("class=\"overlay\""));
+            assert!(markup.contains("<path d=\"M"));
+            assert!(!markup.contains("<path d=\"\"/>"));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // \operatorname
+    // amsopn.dtx: \mathop{#1\kern\z@\operator@font#3}\newmcodes@
+    registry.define_function(FunctionDefSpec {
+        node_type: "operatorname",
+        names: &["\\operatorname@", "\\operatornamewithlimits"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+/// NOTE: Unlike most `html_builder`s, this one handles not only
+/// "operatorname", but also  "supsub" since \operatorname* can
+/// affect super/subscripting.
+pub fn html_builder(grp: &AnyParseNode, options: &Options) -> VirtualNode {
+    // Operators are handled in the TeXbook pg. 443-444, rule 13(a).
+    let (group, sup_group, sub_group, has_limits) = match grp {
+        AnyParseNode::SupSub(supsub) => {
+            // If we have limits, supsub will pass us its group to handle. Pull
+            // out the superscript and subscript and set the group to the op in
+            // its base.
+            let Some(AnyParseNode::Operatorname(group)) = supsub.base.as_deref() else {
+                panic!("Expected node of type operatorname as the base of a supsub");
+            };
+            (group, supsub.sup.as_deref(), supsub.sub.as_deref(), true)
+        }
+        AnyParseNode::Operatorname(group) => (group, None, None, false),
+        _ => unreachable!(),
+    };
+
+    let base = if !group.body.is_empty() {
+        let body: Vec<AnyParseNode> = group
+            .body
+            .iter()
+            .map(|child| match child.symbol_text() {
+                Some(child_text) => AnyParseNode::TextOrd(TextOrdParseNode {
+                    type_: "textord".into(),
+                    mode: child.mode().to_string(),
+                    loc: None,
+                    text: child_text.to_string(),
+                }),
+                None => child.clone(),
+            })
+            .collect();
+
+        // Consolidate function names into symbol characters.
+        let mut expression = build_html::build_expression(
+            &body,
+            &options.with_font("mathrm"),
+            true,
+            false,
+            [None, None],
+        );
+
+        for child in expression.iter_mut() {
+            if let VirtualNode::Symbol(child) = child {
+                // Per amsopn package,
+                // change minus to hyphen and \ast to asterisk
+                child.text = child
+                    .text
+                    .replacen('\u{2212}', "-", 1)
+                    .replacen('\u{2217}', "*", 1);
+            }
+        }
+        make_span(vec!["mop".to_string()], expression, Some(options), None)
+    } else {
+        make_span(vec!["mop".to_string()], vec![], Some(options), None)
+    };
+
+    if has_limits {
+        assemble_sup_sub(
+            base.into(),
+            sup_group,
+            sub_group,
+            options,
+            options.style,
+            0.0,
+            0.0,
+        )
+    } else {
+        base.into()
+    }
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Operatorname(group) = group else {
+        unreachable!()
+    };
+    // The steps taken here are similar to the html version.
+    let mut expression =
+        build_mathml::build_expression(&group.body, &options.with_font("mathrm"), false);
+
+    // Is expression a string or has it something like a fraction?
+    let mut is_all_string = true; // default
+    for node in expression.iter_mut() {
+        match node {
+            MathDomNode::Space(_) => {} // Do nothing
+            MathDomNode::Math(node) => match node.type_ {
+                MathNodeType::Mi
+                | MathNodeType::Mn
+                | MathNodeType::Mspace
+                | MathNodeType::Mtext => {} // Do nothing yet.
+                MathNodeType::Mo => match node.children.as_mut_slice() {
+                    [MathDomNode::Text(child)] => {
+                        child.text = child
+                            .text
+                            .replacen('\u{2212}', "-", 1)
+                            .replacen('\u{2217}', "*", 1);
+                    }
+                    _ => is_all_string = false,
+                },
+                _ => is_all_string = false,
+            },
+            _ => is_all_string = false,
+        }
+    }
+
+    if is_all_string {
+        // Write a single TextNode instead of multiple nested tags.
+        let word: String = expression.iter().map(MathDomNode::to_text).collect();
+        expression = vec![TextNode::new(&word).into()];
+    }
+
+    let mut identifier = MathNode::new(MathNodeType::Mi, expression, vec![]);
+    identifier.set_attribute("mathvariant", "normal");
+
+    // \u2061 is the same as &ApplyFunction;
+    // ref: https://www.w3schools.com/charsets/ref_html_entities_a.asp
+    let operator = MathNode::new(
+        MathNodeType::Mo,
+        vec![build_mathml::make_text("\u{2061}", Mode::Text, None).into()],
+        vec![],
+    );
+
+    if group.parent_is_sup_sub {
+        MathNode::new(
+            MathNodeType::Mrow,
+            vec![identifier.into(), operator.into()],
+            vec![],
+        )
+        .into()
+    } else {
+        MathDomNode::Fragment(vec![identifier.into(), operator.into()])
+    }
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let body = args.remove(0);
+    Ok(AnyParseNode::Operatorname(OperatornameParseNode {
+        type_: "operatorname".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: ordargument(body),
+        always_handle_sup_sub: func_name == "\\operatornamewithlimits",
+        limits: false,
+        parent_is_sup_sub: false,
+    }))
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function_builders("ordgroup", Some(html_builder), Some(mathml_builder));
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::OrdGroup(group) = group else {
+        unreachable!()
+    };
+    if group.semisimple == Some(true) {
+        return make_fragment(build_html::build_expression(
+            &group.body,
+            options,
+            false,
+            false,
+            [None, None],
+        ))
+        .into();
+    }
+    make_span(
+        vec!["mord".to_string()],
+        build_html::build_expression(&group.body, options, true, false, [None, None]),
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::OrdGroup(group) = group else {
+        unreachable!()
+    };
+    build_mathml::build_expression_row(&group.body, options, true)
+}
@@ -0,0 +1,108 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "overline",
+        names: &["\\overline"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let body = args.remove(0);
+    Ok(AnyParseNode::Overline(OverlineParseNode {
+        type_: "overline".into(),
+        mode: context.parser.mode.to_string(),
+        loc: None,
+        body: Box::new(body),
+    }))
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Overline(group) = group else {
+        unreachable!()
+    };
+    // Overlines are handled in the TeXbook pg 443, Rule 9.
+
+    // Build the inner group in the cramped style.
+    let inner_group =
+        build_html::build_group(Some(&group.body), &options.having_cramped_style(), None);
+
+    // Create the line above the body
+    let line = make_line_span("overline-line", options, None);
+
+    // Generate the vlist, with the appropriate kerns
+    let default_rule_thickness = options.font_metrics().default_rule_thickness;
+    let vlist = make_v_list(VListParam::FirstBaseline(vec![
+        VListElem::new(inner_group).into(),
+        VListChild::Kern(3.0 * default_rule_thickness),
+        VListElem::new(line).into(),
+        VListChild::Kern(default_rule_thickness),
+    ]));
+
+    make_span(
+        vec!["mord".to_string(), "overline".to_string()],
+        vec![vlist.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Overline(group) = group else {
+        unreachable!()
+    };
+    let mut operator = MathNode::new(
+        MathNodeType::Mo,
+        vec![TextNode::new("\u{203e}").into()],
+        vec![],
+    );
+    operator.set_attribute("stretchy", "true");
+
+    let mut node = MathNode::new(
+        MathNodeType::Mover,
+        vec![
+            build_mathml::build_group(Some(&group.body), options),
+            operator.into(),
+        ],
+        vec![],
+    );
+    node.set_attribute("accent", "true");
+    node.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        // The line sits three rule thicknesses above the body, with one more
+        // of clearance above it.
+        let base = build_one("x");
+        let overline = build_one("\\overline{x}");
+        assert!((overline.height() - base.height() - 5.0 * 0.04).abs() < 1e-9);
+        assert_eq!(overline.depth(), base.depth());
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\overline{x}");
+        assert!(markup.contains("class=\"mord overline\""));
+        assert!(markup.contains("class=\"overline-line\""));
+        assert!(markup.contains(
+            "<mover accent=\"true\"><mi>x</mi><mo stretchy=\"true\">‾</mo></mover>"
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "phantom",
+        names: &["\\phantom"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(phantom_handler),
+        html_builder: Some(phantom_html_builder),
+        mathml_builder: Some(phantom_mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "hphantom",
+        names: &["\\hphantom"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(hphantom_handler),
+        html_builder: Some(hphantom_html_builder),
+        mathml_builder: Some(hphantom_mathml_builder),
+    });
+
+    registry.define_function(FunctionDefSpec {
+        node_type: "vphantom",
+        names: &["\\vphantom"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(vphantom_handler),
+        html_builder: Some(vphantom_html_builder),
+        mathml_builder: Some(vphantom_mathml_builder),
+    });
+}
+
+fn phantom_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(0);
+    Ok(AnyParseNode::Phantom(PhantomParseNode {
+        type_: "phantom".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: ordargument(body),
+    }))
+}
+
+fn phantom_html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Phantom(group) = group else {
+        unreachable!()
+    };
+    let elements = build_html::build_expression(
+        &group.body,
+        &options.with_phantom(),
+        false,
+        false,
+        [None, None],
+    );
+
+    // \phantom isn't supposed to affect the elements it contains.
+    // See "color" for more details.
+    make_fragment(elements).into()
+}
+
+fn phantom_mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Phantom(group) = group else {
+        unreachable!()
+    };
+    let inner = build_mathml::build_expression(&group.body, options, false);
+    MathNode::new(MathNodeType::Mphantom, inner, vec![]).into()
+}
+
+fn hphantom_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(0);
+    Ok(AnyParseNode::Hphantom(HphantomParseNode {
+        type_: "hphantom".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: Box::new(body),
+    }))
+}
+
+fn hphantom_html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Hphantom(group) = group else {
+        unreachable!()
+    };
+    let mut node = make_span(
+        vec![],
+        vec![build_html::build_group(
+            Some(&group.body),
+            &options.with_phantom(),
+            None,
+        )],
+        None,
+        None,
+    );
+    node.height = 0.0;
+    node.depth = 0.0;
+    for child in node.children.iter_mut() {
+        child.set_height(0.0);
+        child.set_depth(0.0);
+    }
+
+    // See smash for comment re: use of make_v_list
+    let node = make_v_list(VListParam::FirstBaseline(vec![VListElem::new(node).into()]));
+
+    // For spacing, TeX treats \smash as a math group (same spacing as ord).
+    make_span(
+        vec!["mord".to_string()],
+        vec![node.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn hphantom_mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Hphantom(group) = group else {
+        unreachable!()
+    };
+    let inner = build_mathml::build_expression(&ordargument((*group.body).clone()), options, false);
+    let phantom = MathNode::new(MathNodeType::Mphantom, inner, vec![]);
+    let mut node = MathNode::new(MathNodeType::Mpadded, vec![phantom.into()], vec![]);
+    node.set_attribute("height", "0px");
+    node.set_attribute("depth", "0px");
+    node.into()
+}
+
+fn vphantom_handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(0);
+    Ok(AnyParseNode::Vphantom(VphantomParseNode {
+        type_: "vphantom".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: Box::new(body),
+    }))
+}
+
+fn vphantom_html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Vphantom(group) = group else {
+        unreachable!()
+    };
+    let inner = make_span(
+        vec!["inner".to_string()],
+        vec![build_html::build_group(
+            Some(&group.body),
+            &options.with_phantom(),
+            None,
+        )],
+        None,
+        None,
+    );
+    let fix = make_span(vec!["fix".to_string()], vec![], None, None);
+    make_span(
+        vec!["mord".to_string(), "rlap".to_string()],
+        vec![inner.into(), fix.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn vphantom_mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Vphantom(group) = group else {
+        unreachable!()
+    };
+    let inner = build_mathml::build_expression(&ordargument((*group.body).clone()), options, false);
+    let phantom = MathNode::new(MathNodeType::Mphantom, inner, vec![]);
+    let mut node = MathNode::new(MathNodeType::Mpadded, vec![phantom.into()], vec![]);
+    node.set_attribute("width", "0px");
+    node.into()
+}
@@ -0,0 +1,61 @@
+//! Box manipulation
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // \raisebox{distance}{text}
+    registry.define_function(FunctionDefSpec {
+        node_type: "raisebox",
+        names: &["\\raisebox"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            arg_types: Some(vec![ArgType::Size, ArgType::Hbox]),
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Raisebox(group) = group else {
+        unreachable!()
+    };
+    let body = build_html::build_group(Some(&group.body), options, None);
+    let dy = calculate_size(&group.dy, options);
+    make_v_list(VListParam::Shift(-dy, vec![VListElem::new(body).into()])).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Raisebox(group) = group else {
+        unreachable!()
+    };
+    let mut node = MathNode::new(
+        MathNodeType::Mpadded,
+        vec![build_mathml::build_group(Some(&group.body), options)],
+        vec![],
+    );
+    let dy = format!("{}{}", group.dy.number, group.dy.unit);
+    node.set_attribute("voffset", &dy);
+    node.into()
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let body = args.remove(1);
+    let AnyParseNode::Size(amount) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type size", None));
+    };
+    Ok(AnyParseNode::Raisebox(RaiseboxParseNode {
+        type_: "raisebox".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        dy: amount.value,
+        body: Box::new(body),
+    }))
+}
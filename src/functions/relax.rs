@@ -0,0 +1,30 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "internal",
+        names: &["\\relax"],
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            allowed_in_argument: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: None,
+        mathml_builder: None,
+    });
+}
+
+fn handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    Ok(AnyParseNode::Internal(InternalParseNode {
+        type_: "internal".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+    }))
+}
@@ -0,0 +1,165 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "rule",
+        names: &["\\rule"],
+        props: FunctionPropSpec {
+            num_args: 2,
+            num_optional_args: 1,
+            allowed_in_text: true,
+            arg_types: Some(vec![ArgType::Size, ArgType::Size, ArgType::Size]),
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Rule(group) = group else {
+        unreachable!()
+    };
+    // Make an empty span for the rule
+    let mut rule = make_span(
+        vec!["mord".to_string(), "rule".to_string()],
+        vec![],
+        Some(options),
+        None,
+    );
+
+    // Calculate the shift, width, and height of the rule, and account for units
+    let width = calculate_size(&group.width, options);
+    let height = calculate_size(&group.height, options);
+    let shift = group
+        .shift
+        .as_ref()
+        .map_or(0.0, |shift| calculate_size(shift, options));
+
+    // Style the rule to the right size
+    rule.style
+        .insert("borderRightWidth".to_string(), make_em(width));
+    rule.style
+        .insert("borderTopWidth".to_string(), make_em(height));
+    rule.style.insert("bottom".to_string(), make_em(shift));
+
+    // Record the height and width
+    rule.width = Some(width);
+    rule.height = height + shift;
+    rule.depth = -shift;
+    // Font size is the number large enough that the browser will
+    // reserve at least `absHeight` space above the baseline.
+    // The 1.125 factor was empirically determined
+    rule.max_font_size = height * 1.125 * options.size_multiplier;
+
+    rule.into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Rule(group) = group else {
+        unreachable!()
+    };
+    let width = calculate_size(&group.width, options);
+    let height = calculate_size(&group.height, options);
+    let shift = group
+        .shift
+        .as_ref()
+        .map_or(0.0, |shift| calculate_size(shift, options));
+    let color = options.get_color().unwrap_or_else(|| "black".to_string());
+
+    let mut rule = MathNode::new(MathNodeType::Mspace, vec![], vec![]);
+    rule.set_attribute("mathbackground", &color);
+    rule.set_attribute("width", &make_em(width));
+    rule.set_attribute("height", &make_em(height));
+
+    let mut wrapper = MathNode::new(MathNodeType::Mpadded, vec![rule.into()], vec![]);
+    wrapper.set_attribute("height", &make_em(shift));
+    if shift < 0.0 {
+        wrapper.set_attribute("depth", &make_em(-shift));
+    }
+    wrapper.set_attribute("voffset", &make_em(shift));
+
+    wrapper.into()
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    mut opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let shift = match opt_args.remove(0) {
+        Some(AnyParseNode::Size(shift)) => Some(shift.value),
+        Some(_) => return Err(ParseError::new("Expected node of type size", None)),
+        None => None,
+    };
+    let AnyParseNode::Size(height) = args.remove(1) else {
+        return Err(ParseError::new("Expected node of type size", None));
+    };
+    let AnyParseNode::Size(width) = args.remove(0) else {
+        return Err(ParseError::new("Expected node of type size", None));
+    };
+    Ok(AnyParseNode::Rule(RuleParseNode {
+        type_: "rule".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        shift,
+        width: width.value,
+        height: height.value,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_sizes() {
+        let AnyParseNode::Rule(rule) = parse_one("\\rule[-1ex]{2em}{3pt}") else {
+            panic!("expected a rule node");
+        };
+        assert_eq!(rule.shift.map(|size| size.number), Some(-1.0));
+        assert_eq!((rule.width.number, rule.width.unit.as_str()), (2.0, "em"));
+        assert_eq!((rule.height.number, rule.height.unit.as_str()), (3.0, "pt"));
+
+        assert!(parse_tree("\\rule{1em}", &Settings::default()).is_err());
+    }
+
+    #[test]
+    fn raisebox_and_vcenter() {
+        let AnyParseNode::Raisebox(raisebox) = parse_one("\\raisebox{0.25em}{x}") else {
+            panic!("expected a raisebox node");
+        };
+        assert_eq!(raisebox.dy.number, 0.25);
+
+        assert!(matches!(parse_one("\\vcenter{x}"), AnyParseNode::Vcenter(_)));
+    }
+
+    #[test]
+    fn layout() {
+        // A rule is as tall as given, raised by its optional shift.
+        let rule = build_one("\\rule{1em}{2em}");
+        assert_eq!((rule.height(), rule.depth()), (2.0, 0.0));
+        let rule = build_one("\\rule[-0.5em]{1em}{2em}");
+        assert_eq!((rule.height(), rule.depth()), (1.5, 0.5));
+
+        // \raisebox lifts its box by the given amount.
+        let x = build_one("\\textrm{x}");
+        let raised = build_one("\\raisebox{0.25em}{x}");
+        assert!((raised.height() - x.height() - 0.25).abs() < 1e-9);
+
+        // \vcenter centers its box on the math axis.
+        let centered = build_one("\\vcenter{\\rule{1em}{2em}}");
+        assert!((centered.height() - 1.25).abs() < 1e-9);
+        assert!((centered.depth() - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\rule{1em}{2em}");
+        assert!(markup.contains("mord rule"));
+        assert!(markup.contains("border-right-width:1em;"));
+        assert!(markup.contains("mspace"));
+    }
+}
@@ -0,0 +1,179 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "sizing",
+        names: SIZE_FUNCS,
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+pub fn sizing_group(
+    value: &[AnyParseNode],
+    options: &Options,
+    base_options: &Options,
+) -> DocumentFragment {
+    let mut inner = build_html::build_expression(value, options, false, false, [None, None]);
+    let multiplier = options.size_multiplier / base_options.size_multiplier;
+
+    // Add size-resetting classes to the inner list and set max_font_size
+    // manually. Handle nested size changes.
+    for node in inner.iter_mut() {
+        if let Some(classes) = node.classes_mut() {
+            match classes.iter().position(|class| class == "sizing") {
+                None => classes.extend(options.sizing_classes(base_options)),
+                Some(pos)
+                    if classes.get(pos + 1) == Some(&format!("reset-size{}", options.size)) =>
+                {
+                    // This is a nested size change: e.g., node is the "b" in
+                    // `\Huge a \small b`. Override the old size (the `reset-` class)
+                    // but not the new size.
+                    classes[pos + 1] = format!("reset-size{}", base_options.size);
+                }
+                Some(_) => {}
+            }
+        }
+
+        node.set_height(node.height() * multiplier);
+        node.set_depth(node.depth() * multiplier);
+    }
+
+    make_fragment(inner)
+}
+
+const SIZE_FUNCS: &[&str] = &[
+    "\\tiny",
+    "\\sixptsize",
+    "\\scriptsize",
+    "\\footnotesize",
+    "\\small",
+    "\\normalsize",
+    "\\large",
+    "\\Large",
+    "\\LARGE",
+    "\\huge",
+    "\\Huge",
+];
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Sizing(group) = group else {
+        unreachable!()
+    };
+    // Handle sizing operators like \Huge. Real TeX doesn't actually allow
+    // these functions inside of math expressions, so we do some special
+    // handling.
+    let new_options = options.having_size(group.size as usize);
+    sizing_group(&group.body, &new_options, options).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Sizing(group) = group else {
+        unreachable!()
+    };
+    let new_options = options.having_size(group.size as usize);
+    let inner = build_mathml::build_expression(&group.body, &new_options, false);
+
+    let mut node = MathNode::new(MathNodeType::Mstyle, inner, vec![]);
+
+    // TODO(emily): This doesn't produce the correct size for nested size
+    // changes, because we don't keep state of what style we're currently
+    // in, so we can't reset the size to normal before changing it.  Now
+    // that we're passing an options parameter we should be able to fix
+    // this.
+    node.set_attribute("mathsize", &make_em(new_options.size_multiplier));
+
+    node.into()
+}
+
+fn handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser,
+        func_name,
+        break_on_token_text,
+        ..
+    } = context;
+    let body = parser.parse_expression(false, break_on_token_text.as_deref())?;
+
+    // Figure out what size to use based on the list of functions above
+    let size = SIZE_FUNCS
+        .iter()
+        .position(|name| *name == func_name)
+        .map_or(0, |index| index + 1);
+
+    Ok(AnyParseNode::Sizing(SizingParseNode {
+        type_: "sizing".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        size: size as f64,
+        body,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_switches_take_the_rest_of_the_group() {
+        let mut tree = parse_tree("a{\\small b c}\\Huge d", &Settings::default()).unwrap();
+        assert_eq!(tree.len(), 3);
+        let AnyParseNode::Sizing(huge) = tree.remove(2) else {
+            panic!("expected a sizing node");
+        };
+        assert_eq!(huge.size, 11.0);
+        assert_eq!(huge.body.len(), 1);
+
+        let AnyParseNode::OrdGroup(group) = tree.remove(1) else {
+            panic!("expected an ordgroup");
+        };
+        let AnyParseNode::Sizing(small) = &group.body[0] else {
+            panic!("expected a sizing node");
+        };
+        assert_eq!(small.size, 5.0);
+        assert_eq!(small.body.len(), 2);
+    }
+
+    #[test]
+    fn style_commands() {
+        let mut tree = parse_tree("\\scriptstyle x", &Settings::default()).unwrap();
+        let AnyParseNode::Styling(styling) = tree.remove(0) else {
+            panic!("expected a styling node");
+        };
+        assert_eq!(styling.style, "script");
+    }
+
+    #[test]
+    fn layout() {
+        // Sizes scale the box; \Huge is 2.488 times the normal size.
+        let normal = build_one("{x}");
+        let huge = build_one("{\\Huge x}");
+        assert!((huge.height() - 2.488 * normal.height()).abs() < 1e-9);
+
+        // Styles change the layout of what follows, such as fractions.
+        let display = build_one("{\\displaystyle \\frac{a}{b}}");
+        let script = build_one("{\\scriptstyle \\frac{a}{b}}");
+        assert!(display.height() > build_one("\\frac{a}{b}").height());
+        assert!(script.height() < build_one("\\frac{a}{b}").height());
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\Huge x");
+        assert!(markup.contains("sizing reset-size6 size11"));
+        assert!(markup.contains("mathsize=\"2.488em\""));
+
+        let markup = render("\\displaystyle x");
+        assert!(markup.contains("displaystyle=\"true\""));
+    }
+}
@@ -0,0 +1,192 @@
+//! smash, with optional [tb], as in AMS
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "smash",
+        names: &["\\smash"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            num_optional_args: 1,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Smash(group) = group else {
+        unreachable!()
+    };
+    let mut node = make_span(
+        vec![],
+        vec![build_html::build_group(Some(&group.body), options, None)],
+        None,
+        None,
+    );
+
+    if !group.smash_height && !group.smash_depth {
+        return node.into();
+    }
+
+    if group.smash_height {
+        node.height = 0.0;
+        // In order to influence make_v_list, we have to reset the children.
+        for child in node.children.iter_mut() {
+            child.set_height(0.0);
+        }
+    }
+
+    if group.smash_depth {
+        node.depth = 0.0;
+        for child in node.children.iter_mut() {
+            child.set_depth(0.0);
+        }
+    }
+
+    // At this point, we've reset the TeX-like height and depth values.
+    // But the span still has an HTML line height.
+    // make_v_list applies "display: table-cell", which prevents the browser
+    // from acting on that line height. So we'll call make_v_list now.
+
+    let smashed_node = make_v_list(VListParam::FirstBaseline(vec![VListElem::new(node).into()]));
+
+    // For spacing, TeX treats \hphantom as a math group (same spacing as ord).
+    make_span(
+        vec!["mord".to_string()],
+        vec![smashed_node.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Smash(group) = group else {
+        unreachable!()
+    };
+    let mut node = MathNode::new(
+        MathNodeType::Mpadded,
+        vec![build_mathml::build_group(Some(&group.body), options)],
+        vec![],
+    );
+
+    if group.smash_height {
+        node.set_attribute("height", "0px");
+    }
+
+    if group.smash_depth {
+        node.set_attribute("depth", "0px");
+    }
+
+    node.into()
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    mut opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    let mut smash_height = false;
+    let mut smash_depth = false;
+    match opt_args.remove(0) {
+        Some(AnyParseNode::OrdGroup(tb_arg)) => {
+            // Optional [tb] argument is engaged.
+            // ref: amsmath: \renewcommand{\smash}[1][tb]{%
+            //               def\mb@t{\ht}\def\mb@b{\dp}\def\mb@tb{\ht\z@\z@\dp}%
+            for node in &tb_arg.body {
+                match node.symbol_text() {
+                    Some("t") => smash_height = true,
+                    Some("b") => smash_depth = true,
+                    _ => {
+                        smash_height = false;
+                        smash_depth = false;
+                        break;
+                    }
+                }
+            }
+        }
+        Some(_) => return Err(ParseError::new("Expected node of type ordgroup", None)),
+        None => {
+            smash_height = true;
+            smash_depth = true;
+        }
+    }
+
+    let body = args.remove(0);
+    Ok(AnyParseNode::Smash(SmashParseNode {
+        type_: "smash".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: Box::new(body),
+        smash_height,
+        smash_depth,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smash_options() {
+        let smash = |input| {
+            let AnyParseNode::Smash(smash) = parse_one(input) else {
+                panic!("expected a smash node for {}", input);
+            };
+            (smash.smash_height, smash.smash_depth)
+        };
+        assert_eq!(smash("\\smash{y}"), (true, true));
+        assert_eq!(smash("\\smash[t]{y}"), (true, false));
+        assert_eq!(smash("\\smash[b]{y}"), (false, true));
+        assert_eq!(smash("\\smash[tb]{y}"), (true, true));
+    }
+
+    #[test]
+    fn phantoms_and_laps() {
+        assert!(matches!(parse_one("\\phantom{x}"), AnyParseNode::Phantom(_)));
+        assert!(matches!(parse_one("\\hphantom{x}"), AnyParseNode::Hphantom(_)));
+        assert!(matches!(parse_one("\\vphantom{x}"), AnyParseNode::Vphantom(_)));
+
+        let AnyParseNode::Lap(lap) = parse_one("\\mathrlap{x}") else {
+            panic!("expected a lap node");
+        };
+        assert_eq!(lap.alignment, "rlap");
+        let AnyParseNode::Lap(lap) = parse_one("\\llap{x}") else {
+            panic!("expected a lap node");
+        };
+        assert_eq!(lap.alignment, "llap");
+    }
+
+    #[test]
+    fn layout() {
+        let y = build_one("y");
+        let smash = build_one("\\smash{y}");
+        assert_eq!((smash.height(), smash.depth()), (0.0, 0.0));
+        let top = build_one("\\smash[t]{y}");
+        assert_eq!((top.height(), top.depth()), (0.0, y.depth()));
+
+        // Phantoms keep the size of their body; \vphantom only its height
+        // and depth, and \hphantom only its width.
+        let phantom = build_one("\\phantom{y}");
+        assert_eq!((phantom.height(), phantom.depth()), (y.height(), y.depth()));
+        let vphantom = build_one("\\vphantom{\\frac{a}{b}}");
+        assert_eq!(vphantom.height(), build_one("\\frac{a}{b}").height());
+        let hphantom = build_one("\\hphantom{\\frac{a}{b}}");
+        assert_eq!((hphantom.height(), hphantom.depth()), (0.0, 0.0));
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\phantom{x}");
+        assert!(markup.contains("<mphantom>"));
+        assert!(markup.contains("color:transparent;"));
+
+        let markup = render("\\smash{y}");
+        assert!(markup.contains("<mpadded"));
+    }
+}
@@ -0,0 +1,222 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "sqrt",
+        names: &["\\sqrt"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            num_optional_args: 1,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let index = opt_args.into_iter().next().flatten();
+    let body = args.remove(0);
+    Ok(AnyParseNode::Sqrt(SqrtParseNode {
+        type_: "sqrt".into(),
+        mode: context.parser.mode.to_string(),
+        loc: None,
+        body: Box::new(body),
+        index: index.map(Box::new),
+    }))
+}
+
+/// `\mkern` the root index by `number` mu, as `\r@@t` does in LaTeX.
+fn mkern(number: f64, options: &Options) -> String {
+    make_em(calculate_size(
+        &Measurement {
+            number,
+            unit: "mu".to_string(),
+        },
+        options,
+    ))
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Sqrt(group) = group else {
+        unreachable!()
+    };
+    // Square roots are handled in the TeXbook pg. 443, Rule 11.
+
+    // First, we do the same steps as in overline to build the inner group
+    // and line
+    let mut inner =
+        build_html::build_group(Some(&group.body), &options.having_cramped_style(), None);
+    if inner.height() == 0.0 {
+        // Render a small surd.
+        inner.set_height(options.font_metrics().x_height);
+    }
+
+    // Some groups can return document fragments.  Handle those by wrapping
+    // them in a span.
+    let mut inner = wrap_fragment(inner, options);
+
+    // Calculate the minimum size for the \surd delimiter
+    let metrics = options.font_metrics();
+    let theta = metrics.default_rule_thickness;
+
+    let mut phi = theta;
+    if options.style.id < Style::TEXT.id {
+        phi = options.font_metrics().x_height;
+    }
+
+    // Calculate the clearance between the body and line
+    let mut line_clearance = theta + phi / 4.0;
+
+    let min_delimiter_height = inner.height() + inner.depth() + line_clearance + theta;
+
+    // Create a sqrt SVG of the required minimum size
+    let delimiter::SqrtImage {
+        span: img,
+        rule_width,
+        advance_width,
+    } = delimiter::make_sqrt_image(min_delimiter_height, options);
+
+    let delim_depth = img.height - rule_width;
+
+    // Adjust the clearance based on the delimiter size
+    if delim_depth > inner.height() + inner.depth() + line_clearance {
+        line_clearance = (line_clearance + delim_depth - inner.height() - inner.depth()) / 2.0;
+    }
+
+    // Shift the sqrt image
+    let img_shift = img.height - inner.height() - line_clearance - rule_width;
+
+    if let Some(style) = inner.style_mut() {
+        style.insert("paddingLeft".to_string(), make_em(advance_width));
+    }
+
+    // Overlay the image and the argument.
+    let inner_height = inner.height();
+    let mut inner = VListElem::new(inner);
+    inner.wrapper_classes = vec!["svg-align".to_string()];
+    let body = make_v_list(VListParam::FirstBaseline(vec![
+        inner.into(),
+        VListChild::Kern(-(inner_height + img_shift)),
+        VListElem::new(img).into(),
+        VListChild::Kern(rule_width),
+    ]));
+
+    match &group.index {
+        None => make_span(
+            vec!["mord".to_string(), "sqrt".to_string()],
+            vec![body.into()],
+            Some(options),
+            None,
+        )
+        .into(),
+        Some(index) => {
+            // Handle the optional root index
+
+            // The index is always in scriptscript style
+            let new_options = options.having_style(Style::SCRIPTSCRIPT);
+            let rootm = build_html::build_group(Some(index), &new_options, Some(options));
+
+            // The amount the index is shifted by. This is taken from the TeX
+            // source, in the definition of `\r@@t`.
+            let to_shift = 0.6 * (body.height - body.depth);
+
+            // Build a VList with the superscript shifted up correctly
+            let root_v_list = make_v_list(VListParam::Shift(
+                -to_shift,
+                vec![VListElem::new(rootm).into()],
+            ));
+            // Add a class surrounding it, and kern it like the
+            // `\mkern5mu` and `\mkern-10mu` around the index in `\r@@t`
+            let mut root_v_list_wrap = make_span(
+                vec!["root".to_string()],
+                vec![root_v_list.into()],
+                None,
+                None,
+            );
+            root_v_list_wrap
+                .style
+                .insert("marginLeft".to_string(), mkern(5.0, options));
+            root_v_list_wrap
+                .style
+                .insert("marginRight".to_string(), mkern(-10.0, options));
+
+            make_span(
+                vec!["mord".to_string(), "sqrt".to_string()],
+                vec![root_v_list_wrap.into(), body.into()],
+                Some(options),
+                None,
+            )
+            .into()
+        }
+    }
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Sqrt(group) = group else {
+        unreachable!()
+    };
+    match &group.index {
+        Some(index) => MathNode::new(
+            MathNodeType::Mroot,
+            vec![
+                build_mathml::build_group(Some(&group.body), options),
+                build_mathml::build_group(Some(index), options),
+            ],
+            vec![],
+        ),
+        None => MathNode::new(
+            MathNodeType::Msqrt,
+            vec![build_mathml::build_group(Some(&group.body), options)],
+            vec![],
+        ),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optional_index() {
+        let tree = parse_tree("\\sqrt[3]{x}\\sqrt y", &Settings::default()).unwrap();
+        let [AnyParseNode::Sqrt(root), AnyParseNode::Sqrt(sqrt)] = &tree[..] else {
+            panic!("expected two sqrt nodes");
+        };
+        assert!(matches!(root.index.as_deref(), Some(AnyParseNode::OrdGroup(_))));
+        assert!(sqrt.index.is_none());
+        assert!(matches!(*sqrt.body, AnyParseNode::MathOrd(_)));
+    }
+
+    #[test]
+    fn surd_sizes() {
+        // A short body gets the surd of the main font, 1000 units tall plus
+        // the padding; taller bodies step through the sized fonts.
+        assert!(render("\\sqrt{x}").contains("viewBox=\"0 0 400000 1080\""));
+        assert!(render("\\sqrt{\\frac{a}{b}}").contains("viewBox=\"0 0 400000 1296\""));
+        assert!(render("\\sqrt{\\dfrac{a}{b}}").contains("viewBox=\"0 0 400000 2592\""));
+
+        // The surd clears the body by the rule thickness and the gap.
+        let sqrt = build_one("\\sqrt{x}");
+        assert!(sqrt.height() > build_one("x").height() + 0.04);
+        assert!(build_one("\\sqrt{\\dfrac{a}{b}}").height() > sqrt.height() + 0.5);
+    }
+
+    #[test]
+    fn markup() {
+        let root = render("\\sqrt[3]{x}");
+        assert!(root.contains("<mroot><mi>x</mi><mn>3</mn></mroot>"));
+        assert!(root.contains("class=\"root\""));
+        assert!(root.contains("class=\"hide-tail\""));
+
+        let sqrt = render("\\sqrt{\\frac{a}{b}}");
+        assert!(sqrt.contains("<msqrt>"));
+        assert!(!sqrt.contains("class=\"root\""));
+    }
+}
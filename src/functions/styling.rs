@@ -0,0 +1,83 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "styling",
+        names: &[
+            "\\displaystyle",
+            "\\textstyle",
+            "\\scriptstyle",
+            "\\scriptscriptstyle",
+        ],
+        props: FunctionPropSpec {
+            num_args: 0,
+            allowed_in_text: true,
+            primitive: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Styling(group) = group else {
+        unreachable!()
+    };
+    // Style changes are handled in the TeXbook on pg. 442, Rule 3.
+    let new_style = build_html::style_map(&group.style);
+    let new_options = options.having_style(new_style).with_font("");
+    sizing::sizing_group(&group.body, &new_options, options).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Styling(group) = group else {
+        unreachable!()
+    };
+    // Figure out what style we're changing to.
+    let new_style = build_html::style_map(&group.style);
+    let new_options = options.having_style(new_style);
+
+    let inner = build_mathml::build_expression(&group.body, &new_options, false);
+
+    let mut node = MathNode::new(MathNodeType::Mstyle, inner, vec![]);
+
+    let (script_level, display_style) = match group.style.as_str() {
+        "display" => ("0", "true"),
+        "script" => ("1", "false"),
+        "scriptscript" => ("2", "false"),
+        _ => ("0", "false"),
+    };
+
+    node.set_attribute("scriptlevel", script_level);
+    node.set_attribute("displaystyle", display_style);
+
+    node.into()
+}
+
+fn handler(
+    context: FunctionContext,
+    _args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser,
+        func_name,
+        break_on_token_text,
+        ..
+    } = context;
+    // parse out the implicit body
+    let body = parser.parse_expression(true, break_on_token_text.as_deref())?;
+
+    // Figure out what style to use by pulling out the style from
+    // the function name
+    let style = func_name[1..func_name.len() - 5].to_string();
+    Ok(AnyParseNode::Styling(StylingParseNode {
+        type_: "styling".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        style,
+        body,
+    }))
+}
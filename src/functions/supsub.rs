@@ -0,0 +1,292 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // Super scripts and subscripts, whose precise placement can depend on other
+    // functions that precede them.
+    registry.define_function_builders("supsub", Some(html_builder), Some(mathml_builder));
+}
+
+/// Sometimes, groups perform special rules when they have superscripts or
+/// subscripts attached to them. This function lets the `supsub` group know that
+/// its inner element should handle the superscripts and subscripts instead of
+/// handling them itself.
+fn html_builder_delegate(group: &SupSubParseNode, options: &Options) -> Option<HtmlBuilder> {
+    match group.base.as_deref()? {
+        AnyParseNode::Op(base) => {
+            // Operators handle supsubs differently when they have limits
+            // (e.g. `\displaystyle\sum_2^3`)
+            let delegate = base.limits
+                && (options.style.size == Style::DISPLAY.size
+                    || base.always_handle_sup_sub == Some(true));
+            if delegate {
+                Some(op::html_builder)
+            } else {
+                None
+            }
+        }
+        AnyParseNode::Operatorname(base) => {
+            let delegate = base.always_handle_sup_sub
+                && (options.style.size == Style::DISPLAY.size || base.limits);
+            if delegate {
+                Some(operatorname::html_builder)
+            } else {
+                None
+            }
+        }
+        AnyParseNode::Accent(base) => {
+            if utils::is_character_box(&base.base) {
+                Some(accent::html_builder)
+            } else {
+                None
+            }
+        }
+        AnyParseNode::HorizBrace(base) => {
+            let is_sup = group.sub.is_none();
+            if is_sup == base.is_over {
+                Some(horiz_brace::html_builder)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn html_builder(grp: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::SupSub(group) = grp else {
+        unreachable!()
+    };
+    // Superscript and subscripts are handled in the TeXbook on page
+    // 445-446, rules 18(a-f).
+
+    // Here is where we defer to the inner group if it should handle
+    // superscripts and subscripts itself.
+    if let Some(builder_delegate) = html_builder_delegate(group, options) {
+        return builder_delegate(grp, options);
+    }
+
+    let value_base = group.base.as_deref();
+    let base = build_html::build_group(value_base, options, None);
+    let mut supm = None;
+    let mut subm = None;
+
+    let metrics = options.font_metrics();
+
+    // Rule 18a
+    let mut sup_shift = 0.0;
+    let mut sub_shift = 0.0;
+
+    let is_character_box = value_base.is_some_and(utils::is_character_box);
+    if let Some(value_sup) = group.sup.as_deref() {
+        let new_options = options.having_style(options.style.sup());
+        let elem = build_html::build_group(Some(value_sup), &new_options, Some(options));
+        if !is_character_box {
+            sup_shift = base.height()
+                - new_options.font_metrics().sup_drop * new_options.size_multiplier
+                    / options.size_multiplier;
+        }
+        supm = Some(elem);
+    }
+
+    if let Some(value_sub) = group.sub.as_deref() {
+        let new_options = options.having_style(options.style.sub());
+        let elem = build_html::build_group(Some(value_sub), &new_options, Some(options));
+        if !is_character_box {
+            sub_shift = base.depth()
+                + new_options.font_metrics().sub_drop * new_options.size_multiplier
+                    / options.size_multiplier;
+        }
+        subm = Some(elem);
+    }
+
+    // Rule 18c
+    let min_sup_shift = if options.style == Style::DISPLAY {
+        metrics.sup1
+    } else if options.style.cramped {
+        metrics.sup3
+    } else {
+        metrics.sup2
+    };
+
+    // scriptspace is a font-size-independent size, so scale it
+    // appropriately for use as the marginRight.
+    let multiplier = options.size_multiplier;
+    let margin_right = make_em((0.5 / metrics.pt_per_em) / multiplier);
+
+    let mut margin_left = None;
+    if subm.is_some() {
+        // Subscripts shouldn't be shifted by the base's italic correction.
+        // Account for that by shifting the subscript back the appropriate
+        // amount. Note we only do this when the base is a single symbol.
+        let oiint_name = match group.base.as_deref() {
+            Some(AnyParseNode::Op(op)) => op
+                .name
+                .as_deref()
+                .filter(|name| *name == "\\oiint" || *name == "\\oiiint"),
+            _ => None,
+        };
+        if let VirtualNode::Symbol(symbol) = &base {
+            margin_left = Some(make_em(-symbol.italic));
+        } else if let Some(name) = oiint_name {
+            margin_left = Some(make_em(-op::oval_italic(name, options)));
+        }
+    }
+
+    let supsub = match (supm, subm) {
+        (Some(supm), Some(subm)) => {
+            sup_shift = sup_shift
+                .max(min_sup_shift)
+                .max(supm.depth() + 0.25 * metrics.x_height);
+            sub_shift = sub_shift.max(metrics.sub2);
+
+            let rule_width = metrics.default_rule_thickness;
+
+            // Rule 18e
+            let max_width = 4.0 * rule_width;
+            if (sup_shift - supm.depth()) - (subm.height() - sub_shift) < max_width {
+                sub_shift = max_width - (sup_shift - supm.depth()) + subm.height();
+                let psi = 0.8 * metrics.x_height - (sup_shift - supm.depth());
+                if psi > 0.0 {
+                    sup_shift += psi;
+                    sub_shift -= psi;
+                }
+            }
+
+            let mut sub_elem = VListElem::shifted(subm, sub_shift);
+            sub_elem.margin_right = Some(margin_right.clone());
+            sub_elem.margin_left = margin_left;
+            let mut sup_elem = VListElem::shifted(supm, -sup_shift);
+            sup_elem.margin_right = Some(margin_right);
+
+            make_v_list(VListParam::IndividualShift(vec![sub_elem, sup_elem]))
+        }
+        (None, Some(subm)) => {
+            // Rule 18b
+            sub_shift = sub_shift
+                .max(metrics.sub1)
+                .max(subm.height() - 0.8 * metrics.x_height);
+
+            let mut sub_elem = VListElem::new(subm);
+            sub_elem.margin_left = margin_left;
+            sub_elem.margin_right = Some(margin_right);
+
+            make_v_list(VListParam::Shift(sub_shift, vec![sub_elem.into()]))
+        }
+        (Some(supm), None) => {
+            // Rule 18c, d
+            sup_shift = sup_shift
+                .max(min_sup_shift)
+                .max(supm.depth() + 0.25 * metrics.x_height);
+
+            let mut sup_elem = VListElem::new(supm);
+            sup_elem.margin_right = Some(margin_right);
+
+            make_v_list(VListParam::Shift(-sup_shift, vec![sup_elem.into()]))
+        }
+        (None, None) => panic!("supsub must have either sup or sub."),
+    };
+
+    // Wrap the supsub vlist in a span.msupsub to reset text-align.
+    let mclass =
+        build_html::get_type_of_dom_tree(&base, Some(build_html::Side::Right)).unwrap_or("mord");
+    make_span(
+        vec![mclass.to_string()],
+        vec![
+            base,
+            make_span(vec!["msupsub".to_string()], vec![supsub.into()], None, None).into(),
+        ],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::SupSub(group) = group else {
+        unreachable!()
+    };
+    // Is the inner group a relevant horizonal brace?
+    let mut is_brace = false;
+    let mut is_over = false;
+
+    if let Some(AnyParseNode::HorizBrace(base)) = group.base.as_deref() {
+        let is_sup = group.sup.is_some();
+        if is_sup == base.is_over {
+            is_brace = true;
+            is_over = base.is_over;
+        }
+    }
+
+    let base = group.base.as_deref().map(|base| match base {
+        AnyParseNode::Op(op) => AnyParseNode::Op(OpParseNode {
+            parent_is_sup_sub: true,
+            ..op.clone()
+        }),
+        AnyParseNode::Operatorname(operatorname) => {
+            AnyParseNode::Operatorname(OperatornameParseNode {
+                parent_is_sup_sub: true,
+                ..operatorname.clone()
+            })
+        }
+        base => base.clone(),
+    });
+
+    let mut children = vec![build_mathml::build_group(base.as_ref(), options)];
+
+    if let Some(sub) = group.sub.as_deref() {
+        children.push(build_mathml::build_group(Some(sub), options));
+    }
+
+    if let Some(sup) = group.sup.as_deref() {
+        children.push(build_mathml::build_group(Some(sup), options));
+    }
+
+    let is_display = options.style == Style::DISPLAY;
+    let node_type = if is_brace {
+        if is_over {
+            MathNodeType::Mover
+        } else {
+            MathNodeType::Munder
+        }
+    } else if group.sub.is_none() {
+        match &base {
+            Some(AnyParseNode::Op(base))
+                if base.limits && (is_display || base.always_handle_sup_sub == Some(true)) =>
+            {
+                MathNodeType::Mover
+            }
+            Some(AnyParseNode::Operatorname(base))
+                if base.always_handle_sup_sub && (base.limits || is_display) =>
+            {
+                MathNodeType::Mover
+            }
+            _ => MathNodeType::Msup,
+        }
+    } else if group.sup.is_none() {
+        match &base {
+            Some(AnyParseNode::Op(base))
+                if base.limits && (is_display || base.always_handle_sup_sub == Some(true)) =>
+            {
+                MathNodeType::Munder
+            }
+            Some(AnyParseNode::Operatorname(base))
+                if base.always_handle_sup_sub && (base.limits || is_display) =>
+            {
+                MathNodeType::Munder
+            }
+            _ => MathNodeType::Msub,
+        }
+    } else {
+        match &base {
+            Some(AnyParseNode::Op(base)) if base.limits && is_display => MathNodeType::Munderover,
+            Some(AnyParseNode::Operatorname(base))
+                if base.always_handle_sup_sub && (is_display || base.limits) =>
+            {
+                MathNodeType::Munderover
+            }
+            _ => MathNodeType::Msubsup,
+        }
+    };
+
+    MathNode::new(node_type, children, vec![]).into()
+}
@@ -0,0 +1,46 @@
+//! Operator ParseNodes created in parser.rs from symbol Groups in symbols.rs.
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function_builders("atom", Some(html_builder), Some(mathml_builder));
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Atom(group) = group else {
+        unreachable!()
+    };
+    math_sym(
+        &group.text,
+        Mode::from(group.mode.as_str()),
+        options,
+        vec![format!("m{}", group.family.as_str())],
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Atom(atom) = group else {
+        unreachable!()
+    };
+    let mut node = MathNode::new(
+        MathNodeType::Mo,
+        vec![build_mathml::make_text(&atom.text, Mode::from(atom.mode.as_str()), None).into()],
+        vec![],
+    );
+    match atom.family {
+        Group::Bin => {
+            let variant = build_mathml::get_variant(group, options);
+            if variant == Some("bold-italic") {
+                node.set_attribute("mathvariant", "bold-italic");
+            }
+        }
+        Group::Punct => node.set_attribute("separator", "true"),
+        Group::Open | Group::Close => {
+            // Delims built here should not stretch vertically.
+            // See delimsizing.rs for stretchy delims.
+            node.set_attribute("stretchy", "false");
+        }
+        _ => {}
+    }
+    node.into()
+}
@@ -0,0 +1,82 @@
+//! "mathord" and "textord" ParseNodes created in parser.rs from symbol Groups in
+//! symbols.rs.
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function_builders(
+        "mathord",
+        Some(mathord_html_builder),
+        Some(mathord_mathml_builder),
+    );
+    registry.define_function_builders(
+        "textord",
+        Some(textord_html_builder),
+        Some(textord_mathml_builder),
+    );
+}
+
+fn default_variant(type_: MathNodeType) -> Option<&'static str> {
+    match type_ {
+        MathNodeType::Mi => Some("italic"),
+        MathNodeType::Mn | MathNodeType::Mtext => Some("normal"),
+        _ => None,
+    }
+}
+
+fn mathord_html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    make_ord(group, options, "mathord")
+}
+
+fn mathord_mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::MathOrd(mathord) = group else {
+        unreachable!()
+    };
+    let mut node = MathNode::new(
+        MathNodeType::Mi,
+        vec![build_mathml::make_text(
+            &mathord.text,
+            Mode::from(mathord.mode.as_str()),
+            Some(options),
+        )
+        .into()],
+        vec![],
+    );
+
+    let variant = build_mathml::get_variant(group, options).unwrap_or("italic");
+    if Some(variant) != default_variant(node.type_) {
+        node.set_attribute("mathvariant", variant);
+    }
+    node.into()
+}
+
+fn textord_html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    make_ord(group, options, "textord")
+}
+
+fn textord_mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::TextOrd(textord) = group else {
+        unreachable!()
+    };
+    let text = build_mathml::make_text(
+        &textord.text,
+        Mode::from(textord.mode.as_str()),
+        Some(options),
+    );
+    let variant = build_mathml::get_variant(group, options).unwrap_or("normal");
+
+    let type_ = if textord.mode == "text" {
+        MathNodeType::Mtext
+    } else if textord.text.chars().any(|ch| ch.is_ascii_digit()) {
+        MathNodeType::Mn
+    } else if textord.text == "\\prime" {
+        MathNodeType::Mo
+    } else {
+        MathNodeType::Mi
+    };
+    let mut node = MathNode::new(type_, vec![text.into()], vec![]);
+    if Some(variant) != default_variant(node.type_) {
+        node.set_attribute("mathvariant", variant);
+    }
+
+    node.into()
+}
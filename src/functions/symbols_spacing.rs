@@ -0,0 +1,84 @@
+//! "spacing" ParseNodes created in parser.rs from symbol Groups in symbols.rs.
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function_builders("spacing", Some(html_builder), Some(mathml_builder));
+}
+
+/// A map of CSS-based spacing functions to their CSS class.
+fn css_space(text: &str) -> Option<&'static str> {
+    match text {
+        "\\nobreak" => Some("nobreak"),
+        "\\allowbreak" => Some("allowbreak"),
+        _ => None,
+    }
+}
+
+/// A lookup table to determine whether a spacing function/symbol should be
+/// treated like a regular space character.  If a symbol or command is a key
+/// in this table, then it should be a regular space character.  Furthermore,
+/// the associated value may have a class name specifying an extra CSS class
+/// to add to the created `span`.
+fn regular_space(text: &str) -> Option<&'static str> {
+    match text {
+        " " | "\\ " | "\\space" => Some(""),
+        "~" | "\\nobreakspace" => Some("nobreak"),
+        _ => None,
+    }
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Spacing(spacing) = group else {
+        unreachable!()
+    };
+    if let Some(class_name) = regular_space(&spacing.text) {
+        // Spaces are generated by adding an actual space. Each of these
+        // things has an entry in the symbols table, so these will be turned
+        // into appropriate outputs.
+        if spacing.mode == "text" {
+            let mut ord = make_ord(group, options, "textord");
+            if let Some(classes) = ord.classes_mut() {
+                classes.push(class_name.to_string());
+            }
+            ord
+        } else {
+            make_span(
+                vec!["mspace".to_string(), class_name.to_string()],
+                vec![math_sym(&spacing.text, Mode::Math, options, vec![]).into()],
+                Some(options),
+                None,
+            )
+            .into()
+        }
+    } else if let Some(class_name) = css_space(&spacing.text) {
+        // Spaces based on just a CSS class.
+        make_span(
+            vec!["mspace".to_string(), class_name.to_string()],
+            vec![],
+            Some(options),
+            None,
+        )
+        .into()
+    } else {
+        panic!("Unknown type of space \"{}\"", spacing.text)
+    }
+}
+
+fn mathml_builder(group: &AnyParseNode, _options: &Options) -> MathDomNode {
+    let AnyParseNode::Spacing(spacing) = group else {
+        unreachable!()
+    };
+    if regular_space(&spacing.text).is_some() {
+        MathNode::new(
+            MathNodeType::Mtext,
+            vec![TextNode::new("\u{00a0}").into()],
+            vec![],
+        )
+        .into()
+    } else if css_space(&spacing.text).is_some() {
+        // CSS-based MathML spaces (\nobreak, \allowbreak) are ignored
+        MathNode::new(MathNodeType::Mspace, vec![], vec![]).into()
+    } else {
+        panic!("Unknown type of space \"{}\"", spacing.text)
+    }
+}
@@ -0,0 +1,167 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // The HTML layout of tags is done in build_html.rs, around the whole tree.
+    registry.define_function_builders("tag", None, Some(mathml_builder));
+}
+
+fn pad() -> MathDomNode {
+    let mut pad_node = MathNode::new(MathNodeType::Mtd, vec![], vec![]);
+    pad_node.set_attribute("width", "50%");
+    pad_node.into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Tag(group) = group else {
+        unreachable!()
+    };
+    let mut body = MathNode::new(
+        MathNodeType::Mtd,
+        vec![build_mathml::build_expression_row(&group.body, options, false)],
+        vec![],
+    );
+    let tag = MathNode::new(
+        MathNodeType::Mtd,
+        vec![build_mathml::build_expression_row(&group.tag, options, false)],
+        vec![],
+    );
+
+    // With fleqn the equation is set flush left, so it is padded on one side only.
+    let mut cells = if options.fleqn {
+        body.set_attribute("columnalign", "left");
+        vec![body.into(), pad()]
+    } else {
+        vec![pad(), body.into(), pad()]
+    };
+    if options.leqno {
+        cells.insert(0, tag.into());
+    } else {
+        cells.push(tag.into());
+    }
+
+    let mut table = MathNode::new(
+        MathNodeType::Mtable,
+        vec![MathNode::new(MathNodeType::Mtr, cells, vec![]).into()],
+        vec![],
+    );
+    table.set_attribute("width", "100%");
+    if options.leqno {
+        table.set_attribute("side", "left");
+    }
+    table.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display() -> Settings {
+        Settings {
+            display_mode: true,
+            output: OutputFormat::HtmlAndMathml,
+            ..Default::default()
+        }
+    }
+
+    fn parse_tag(input: &str) -> TagParseNode {
+        let mut tree = parse_tree(input, &display()).unwrap();
+        assert_eq!(tree.len(), 1);
+        let AnyParseNode::Tag(node) = tree.remove(0) else {
+            panic!("expected a tag node for {}", input);
+        };
+        node
+    }
+
+    fn text_of(nodes: &[AnyParseNode]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node {
+                AnyParseNode::TextOrd(ord) => ord.text.clone(),
+                AnyParseNode::OrdGroup(group) => text_of(&group.body),
+                AnyParseNode::Text(text) => text_of(&text.body),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    fn tag_text(tag: &TagParseNode) -> String {
+        assert!(matches!(&tag.tag[..], [AnyParseNode::Text(_)]));
+        text_of(&tag.tag)
+    }
+
+    #[test]
+    fn tags_wrap_the_whole_tree() {
+        let tag = parse_tag("x+y\\tag{1}");
+        assert_eq!(tag.body.len(), 3);
+        assert_eq!(tag_text(&tag), "(1)");
+        assert_eq!(tag_text(&parse_tag("x\\tag*{A}")), "A");
+    }
+
+    #[test]
+    fn nonumber_drops_the_tag() {
+        let tree = parse_tree("x\\tag{1}\\nonumber", &display()).unwrap();
+        assert!(matches!(&tree[..], [AnyParseNode::MathOrd(_)]));
+        let tree = parse_tree("x\\nonumber", &display()).unwrap();
+        assert!(matches!(&tree[..], [AnyParseNode::MathOrd(_)]));
+    }
+
+    #[test]
+    fn notag_drops_the_tag() {
+        let tree = parse_tree("\\notag x\\tag*{A}", &display()).unwrap();
+        assert!(matches!(&tree[..], [AnyParseNode::MathOrd(_)]));
+        assert!(!render_display("x\\tag{1}\\notag").contains("class=\"tag\""));
+    }
+
+    #[test]
+    fn tag_errors() {
+        let error = parse_tree("x\\tag{1}\\tag{2}", &display()).unwrap_err();
+        assert_eq!(error.raw_message(), "Multiple \\tag");
+        let error = parse_tree("x\\tag{1}", &Settings::default()).unwrap_err();
+        assert_eq!(error.raw_message(), "\\tag works only in display equations");
+    }
+
+    #[test]
+    fn mathml_follows_leqno_and_fleqn() {
+        let cells = |settings: &Settings| {
+            let markup = render_to_string("x\\tag{1}", settings);
+            let row = &markup[markup.find("<mtr>").unwrap()..markup.find("</mtr>").unwrap()];
+            row.split("<mtd")
+                .skip(1)
+                .map(|cell| {
+                    if cell.contains("<mi>x</mi>") {
+                        "body"
+                    } else if cell.contains("<mtext>") {
+                        "tag"
+                    } else {
+                        "pad"
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(cells(&display()), ["pad", "body", "pad", "tag"]);
+
+        let leqno = Settings {
+            leqno: true,
+            ..display()
+        };
+        assert_eq!(cells(&leqno), ["tag", "pad", "body", "pad"]);
+        assert!(render_to_string("x\\tag{1}", &leqno).contains("side=\"left\""));
+
+        let fleqn = Settings {
+            fleqn: true,
+            ..display()
+        };
+        assert_eq!(cells(&fleqn), ["body", "pad", "tag"]);
+        assert!(render_to_string("x\\tag{1}", &fleqn).contains("columnalign=\"left\""));
+        assert_eq!(cells(&Settings { leqno: true, ..fleqn }), ["tag", "body", "pad"]);
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render_display("x\\tag{1}");
+        assert!(markup.contains("class=\"tag\""));
+        assert!(markup.contains("<mtable width=\"100%\">"));
+        assert!(!markup.contains("side="));
+        assert!(markup.contains("<mtd width=\"50%\"></mtd>"));
+    }
+}
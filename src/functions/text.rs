@@ -0,0 +1,120 @@
+//! Non-mathy text, possibly in a font
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "text",
+        names: &[
+            // Font families
+            "\\text",
+            "\\textrm",
+            "\\textsf",
+            "\\texttt",
+            "\\textnormal",
+            // Font weights
+            "\\textbf",
+            "\\textmd",
+            // Font Shapes
+            "\\textit",
+            "\\textup",
+            "\\emph",
+        ],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Mode(Mode::Text)]),
+            allowed_in_argument: true,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn text_font_family(font: &str) -> Option<&'static str> {
+    match font {
+        "\\textrm" => Some("textrm"),
+        "\\textsf" => Some("textsf"),
+        "\\texttt" => Some("texttt"),
+        "\\textnormal" => Some("textrm"),
+        _ => None,
+    }
+}
+
+fn text_font_weight(font: &str) -> Option<FontWeight> {
+    match font {
+        "\\textbf" => Some(FontWeight::TextBF),
+        "\\textmd" => Some(FontWeight::TextMD),
+        _ => None,
+    }
+}
+
+fn text_font_shape(font: &str) -> FontShape {
+    match font {
+        "\\textit" => FontShape::TextIt,
+        "\\textup" => FontShape::TextUp,
+        _ => FontShape::None,
+    }
+}
+
+fn options_with_font(group: &TextParseNode, options: &Options) -> Options {
+    // Checks if the argument is a font family or a font style.
+    let Some(font) = group.font.as_deref() else {
+        return options.clone();
+    };
+    if let Some(font_family) = text_font_family(font) {
+        options.with_text_font_family(font_family)
+    } else if let Some(font_weight) = text_font_weight(font) {
+        options.with_text_font_weight(font_weight)
+    } else if font == "\\emph" {
+        if options.font_shape == FontShape::TextIt {
+            options.with_text_font_shape(FontShape::TextUp)
+        } else {
+            options.with_text_font_shape(FontShape::TextIt)
+        }
+    } else {
+        options.with_text_font_shape(text_font_shape(font))
+    }
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Text(group) = group else {
+        unreachable!()
+    };
+    let new_options = options_with_font(group, options);
+    let inner = build_html::build_expression(&group.body, &new_options, true, false, [None, None]);
+    make_span(
+        vec!["mord".to_string(), "text".to_string()],
+        inner,
+        Some(&new_options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Text(group) = group else {
+        unreachable!()
+    };
+    let new_options = options_with_font(group, options);
+    build_mathml::build_expression_row(&group.body, &new_options, false)
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext {
+        parser, func_name, ..
+    } = context;
+    let body = args.remove(0);
+    Ok(AnyParseNode::Text(TextParseNode {
+        type_: "text".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: ordargument(body),
+        font: Some(func_name),
+    }))
+}
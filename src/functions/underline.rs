@@ -0,0 +1,112 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    registry.define_function(FunctionDefSpec {
+        node_type: "underline",
+        names: &["\\underline"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            allowed_in_text: true,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let body = args.remove(0);
+    Ok(AnyParseNode::Underline(UnderlineParseNode {
+        type_: "underline".into(),
+        mode: context.parser.mode.to_string(),
+        loc: None,
+        body: Box::new(body),
+    }))
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Underline(group) = group else {
+        unreachable!()
+    };
+    // Underlines are handled in the TeXbook pg 443, Rule 10.
+
+    // Build the inner group.
+    let inner_group = build_html::build_group(Some(&group.body), options, None);
+
+    // Create the line to go below the body
+    let line = make_line_span("underline-line", options, None);
+
+    // Generate the vlist, with the appropriate kerns
+    let default_rule_thickness = options.font_metrics().default_rule_thickness;
+    let inner_height = inner_group.height();
+    let vlist = make_v_list(VListParam::Top(
+        inner_height,
+        vec![
+            VListChild::Kern(default_rule_thickness),
+            VListElem::new(line).into(),
+            VListChild::Kern(3.0 * default_rule_thickness),
+            VListElem::new(inner_group).into(),
+        ],
+    ));
+
+    make_span(
+        vec!["mord".to_string(), "underline".to_string()],
+        vec![vlist.into()],
+        Some(options),
+        None,
+    )
+    .into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Underline(group) = group else {
+        unreachable!()
+    };
+    let mut operator = MathNode::new(
+        MathNodeType::Mo,
+        vec![TextNode::new("\u{203e}").into()],
+        vec![],
+    );
+    operator.set_attribute("stretchy", "true");
+
+    let mut node = MathNode::new(
+        MathNodeType::Munder,
+        vec![
+            build_mathml::build_group(Some(&group.body), options),
+            operator.into(),
+        ],
+        vec![],
+    );
+    node.set_attribute("accentunder", "true");
+    node.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        // The line hangs three rule thicknesses below the body, with one
+        // more of clearance below it.
+        let base = build_one("y");
+        let underline = build_one("\\underline{y}");
+        assert!((underline.depth() - base.depth() - 5.0 * 0.04).abs() < 1e-9);
+        assert_eq!(underline.height(), base.height());
+    }
+
+    #[test]
+    fn markup() {
+        let markup = render("\\underline{x}");
+        assert!(markup.contains("class=\"mord underline\""));
+        assert!(markup.contains("class=\"underline-line\""));
+        assert!(markup.contains(
+            "<munder accentunder=\"true\"><mi>x</mi><mo stretchy=\"true\">‾</mo></munder>"
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+use super::*;
+
+pub fn define(registry: &mut FunctionRegistry) {
+    // \vcenter:  Vertically center the argument group on the math axis.
+    registry.define_function(FunctionDefSpec {
+        node_type: "vcenter",
+        names: &["\\vcenter"],
+        props: FunctionPropSpec {
+            num_args: 1,
+            arg_types: Some(vec![ArgType::Original]), // In LaTeX, \vcenter can act only on a box.
+            allowed_in_text: false,
+            ..Default::default()
+        },
+        handler: Some(handler),
+        html_builder: Some(html_builder),
+        mathml_builder: Some(mathml_builder),
+    });
+}
+
+fn html_builder(group: &AnyParseNode, options: &Options) -> VirtualNode {
+    let AnyParseNode::Vcenter(group) = group else {
+        unreachable!()
+    };
+    let body = build_html::build_group(Some(&group.body), options, None);
+    let axis_height = options.font_metrics().axis_height;
+    let dy = 0.5 * ((body.height() - axis_height) - (body.depth() + axis_height));
+    make_v_list(VListParam::Shift(dy, vec![VListElem::new(body).into()])).into()
+}
+
+fn mathml_builder(group: &AnyParseNode, options: &Options) -> MathDomNode {
+    let AnyParseNode::Vcenter(group) = group else {
+        unreachable!()
+    };
+    // There is no way to do this in MathML.
+    // Write a class as a breadcrumb in case some post-processor wants
+    // to perform a vcenter adjustment.
+    MathNode::new(
+        MathNodeType::Mpadded,
+        vec![build_mathml::build_group(Some(&group.body), options)],
+        vec!["vcenter".to_string()],
+    )
+    .into()
+}
+
+fn handler(
+    context: FunctionContext,
+    mut args: Vec<AnyParseNode>,
+    _opt_args: Vec<Option<AnyParseNode>>,
+) -> Result<AnyParseNode, ParseError> {
+    let FunctionContext { parser, .. } = context;
+    Ok(AnyParseNode::Vcenter(VcenterParseNode {
+        type_: "vcenter".into(),
+        mode: parser.mode.to_string(),
+        loc: None,
+        body: Box::new(args.remove(0)),
+    }))
+}
@@ -0,0 +1,206 @@
+//! The Lexer class handles tokenizing the input in various ways. Since our
+//! parser expects us to be able to backtrack, the lexer allows lexing from any
+//! given starting point.
+//!
+//! Its main exposed function is the `lex` function, which returns the next
+//! token together with its position in the input.
+use super::*;
+
+/* The following tokenRegex
+ * - matches typical whitespace (but not NBSP etc.) using its first group
+ * - does not match any control character \x00-\x1f except whitespace
+ * - does not match a bare backslash
+ * - matches any ASCII character except those just mentioned
+ * - does not match the BMP private use area \uE000-\uF8FF
+ * - does not match bare surrogate code units
+ * - matches any BMP character except for those just described
+ * - matches any valid Unicode surrogate pair
+ * - matches a backslash followed by one or more whitespace characters
+ * - matches a backslash followed by one or more letters then whitespace
+ * - matches a backslash followed by any BMP character
+ * Capturing groups:
+ *   [1] regular whitespace
+ *   [2] backslash followed by whitespace
+ *   [3] anything else, which may include:
+ *     [4] backslash followed by word, excluding any trailing whitespace
+ * Just because the Lexer matches something doesn't mean it's valid input:
+ * If there is no matching function or symbol definition, the Parser will
+ * still reject the input.
+ *
+ * The regex crate has no backreferences, so `\verb` is matched by hand in
+ * `Lexer::match_verb` instead of by the regex.
+ */
+static TOKEN_REGEX: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(concat!(
+        // whitespace
+        r"^(?:([ \r\n\t]+)|",
+        // \whitespace
+        r"\\(\n|[ \r\t]+\n?)[ \r\t]*|",
+        // single codepoint plus accents
+        r"([!-\[\]-\x{2027}\x{202A}-\x{D7FF}\x{F900}-\x{FFFF}\x{10000}-\x{10FFFF}]",
+        r"[\x{0300}-\x{036f}]*|",
+        // \macroName + spaces
+        r"(\\[a-zA-Z@]+)[ \r\n\t]*|",
+        // \\, \', etc.
+        r"\\(?s:.)))",
+    ))
+    .unwrap()
+});
+
+/// Main Lexer class
+pub struct Lexer<'a> {
+    pub input: Arc<String>,
+    settings: &'a Settings,
+    pos: usize,
+    /// Category codes. The lexer only supports comment characters (14) for
+    /// now. MacroExpander additionally distinguishes active (13).
+    pub catcodes: HashMap<char, u8>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &str, settings: &'a Settings) -> Self {
+        Lexer {
+            input: Arc::new(input.to_string()),
+            settings,
+            pos: 0,
+            catcodes: HashMap::from([
+                ('%', 14), // comment character
+                ('~', 13), // active character
+            ]),
+        }
+    }
+
+    pub fn set_catcode(&mut self, ch: char, code: u8) {
+        self.catcodes.insert(ch, code);
+    }
+
+    fn loc(&self, start: usize, end: usize) -> Option<SourceLocation> {
+        Some(SourceLocation::new(self.input.clone(), start, end))
+    }
+
+    /// The length of a `\verb` or `\verb*` token at the start of `rest`, if
+    /// there is one: the delimiter is the first character after `\verb*`, or
+    /// the first non-letter after `\verb`, and the token runs on the same line
+    /// up to its next occurrence.
+    fn match_verb(rest: &str) -> Option<usize> {
+        let after = rest.strip_prefix("\\verb")?;
+        let (prefix, delimiter) = match after.chars().next()? {
+            '*' => ("\\verb*".len(), after[1..].chars().next()?),
+            ch if ch.is_ascii_alphabetic() => return None,
+            ch => ("\\verb".len(), ch),
+        };
+        let body_start = prefix + delimiter.len_utf8();
+        let body = &rest[body_start..];
+        let end = body.find([delimiter, '\n', '\r'])?;
+        body[end..]
+            .starts_with(delimiter)
+            .then(|| body_start + end + delimiter.len_utf8())
+    }
+
+    /// This function lexes a single token.
+    pub fn lex(&mut self) -> Result<Token, ParseError> {
+        let input = self.input.clone();
+        let pos = self.pos;
+        if pos == input.len() {
+            return Ok(Token::new("EOF".into(), self.loc(pos, pos)));
+        }
+        let rest = &input[pos..];
+        let text = if let Some(len) = Self::match_verb(rest) {
+            self.pos += len;
+            rest[..len].to_string()
+        } else {
+            let Some(captures) = TOKEN_REGEX.captures(rest) else {
+                let ch = rest.chars().next().unwrap();
+                return Err(ParseError::new(
+                    &format!("Unexpected character: '{}'", ch),
+                    Some(&Token::new(
+                        ch.to_string(),
+                        self.loc(pos, pos + ch.len_utf8()),
+                    )),
+                ));
+            };
+            self.pos += captures[0].len();
+            if let Some(word) = captures.get(4) {
+                word.as_str().to_string()
+            } else if let Some(other) = captures.get(3) {
+                other.as_str().to_string()
+            } else if captures.get(2).is_some() {
+                "\\ ".to_string()
+            } else {
+                " ".to_string()
+            }
+        };
+
+        let mut chars = text.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            if self.catcodes.get(&ch) == Some(&14) {
+                // comment character
+                match input[self.pos..].find('\n') {
+                    None => {
+                        self.pos = input.len(); // EOF
+                        self.settings.report_nonstrict(
+                            "commentAtEnd",
+                            "% comment has no terminating newline; LaTeX would \
+                             fail because of commenting the end of math mode (e.g. $)",
+                            None,
+                        )?;
+                    }
+                    Some(nl_index) => self.pos += nl_index + 1,
+                }
+                return self.lex();
+            }
+        }
+
+        Ok(Token::new(text, self.loc(pos, self.pos)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex_all(input: &str) -> Vec<String> {
+        let settings = Settings::default();
+        let mut lexer = Lexer::new(input, &settings);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.lex().unwrap();
+            if token.text == "EOF" {
+                return tokens;
+            }
+            tokens.push(token.text);
+        }
+    }
+
+    #[test]
+    fn tokens() {
+        assert_eq!(lex_all("a+\\frac  {b}"), ["a", "+", "\\frac", "{", "b", "}"]);
+        assert_eq!(lex_all("x  \\,\\\\"), ["x", " ", "\\,", "\\\\"]);
+        assert_eq!(lex_all("\\ \n"), ["\\ "]);
+        assert_eq!(lex_all("e\u{301}"), ["e\u{301}"]);
+    }
+
+    #[test]
+    fn verb_and_comments() {
+        assert_eq!(lex_all("\\verb|a b|c"), ["\\verb|a b|", "c"]);
+        assert_eq!(lex_all("\\verb*+x+"), ["\\verb*+x+"]);
+        assert_eq!(lex_all("\\verbatim"), ["\\verbatim"]);
+        assert_eq!(lex_all("a%comment\nb"), ["a", "b"]);
+    }
+
+    #[test]
+    fn errors_point_at_the_input() {
+        let settings = Settings::default();
+        let mut lexer = Lexer::new("\u{1}", &settings);
+        let error = lexer.lex().unwrap_err();
+        assert_eq!(error.raw_message(), "Unexpected character: '\u{1}'");
+
+        let strict = Settings {
+            strict: true,
+            ..Default::default()
+        };
+        let mut lexer = Lexer::new("a%", &strict);
+        lexer.lex().unwrap();
+        assert!(lexer.lex().is_err());
+    }
+}
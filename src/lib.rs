@@ -2,30 +2,60 @@ use std::{
     cmp::PartialEq,
     collections::HashMap,
     fmt::{self, Display},
-    sync::Arc,
+    sync::{Arc, LazyLock},
 };
 
 use lazy_regex::regex;
 use thiserror::Error;
 
 pub mod build_common;
+pub mod build_html;
+pub mod build_mathml;
 pub mod build_tree;
+pub mod define_function;
+pub mod define_macro;
+pub mod delimiter;
 pub mod dom_tree;
+pub mod font_metrics;
+pub mod font_metrics_data;
+pub mod functions;
+pub mod lexer;
 pub mod macro_expander;
+pub mod macros;
+pub mod mathml_tree;
+pub mod named_colors;
+pub mod namespace;
 pub mod options;
 pub mod parse_error;
 pub mod parse_node;
 pub mod parse_tree;
 pub mod parser;
+pub mod preamble;
+pub mod render_session;
 pub mod settings;
 pub mod source_location;
+pub mod spacing_data;
+pub mod stretchy;
+pub mod style;
+pub mod svg_geometry;
+pub mod symbols;
+#[cfg(test)]
+mod test_util;
 pub mod token;
+pub mod units;
 pub mod utils;
 
 use build_common::*;
 use build_tree::*;
+use define_function::*;
+use define_macro::*;
 use dom_tree::*;
+use font_metrics::*;
+use lexer::*;
 use macro_expander::*;
+use mathml_tree::*;
+use named_colors::*;
+use namespace::*;
 use options::*;
 use parse_error::*;
 use parse_node::*;
@@ -33,7 +63,14 @@ use parse_tree::*;
 use parser::*;
 use settings::*;
 use source_location::*;
+use spacing_data::*;
+use style::*;
+use svg_geometry::*;
+use symbols::*;
+#[cfg(test)]
+use test_util::*;
 use token::*;
+use units::*;
 
 pub fn add(left: usize, right: usize) -> usize {
     left + right
@@ -49,12 +86,13 @@ pub fn render_to_string(expression: &str, options: &Settings) -> String {
 /// renders the invalid LaTeX as a span with hover title giving the KaTeX
 /// error message.  Otherwise, simply throws the error.
 fn render_error(error: ParseError, expression: &str, options: &Settings) -> DomSpan {
-    let node = make_span(
-        Some(vec!["katex-error".into()]),
-        Some(vec![SymbolNode {
+    let mut node = make_span(
+        vec!["katex-error".into()],
+        vec![SymbolNode {
             text: expression.to_string(),
             ..Default::default()
-        }]),
+        }
+        .into()],
         None,
         None,
     );
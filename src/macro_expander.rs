@@ -7,19 +7,48 @@ use super::*;
 /// function, or symbol. Used in `is_defined`.
 pub const IMPLICIT_COMMANDS: [&str; 4] = ["^", "_", "\\limits", "\\nolimits"];
 
+/// One step of macro expansion, as recorded when tracing is enabled.
+/// Token lists are printed as TeX source, like TeX's `\tracingmacros`.
+#[derive(Clone, Debug)]
+pub struct ExpansionStep {
+    /// The expanded macro, e.g. `\foo`.
+    pub name: String,
+    /// The arguments consumed for the macro's parameters. Macros implemented
+    /// in Rust consume their own arguments, so theirs are not listed.
+    pub args: Vec<String>,
+    /// The tokens the macro expanded to.
+    pub expansion: String,
+    /// Where the macro token came from.
+    pub loc: Option<SourceLocation>,
+    /// How many groups deep the expansion happened.
+    pub group_depth: usize,
+    /// The index in the trace of the step whose expansion contained the
+    /// macro token, or `None` if the token came from the input.
+    pub parent: Option<usize>,
+}
+
 pub struct MacroExpander<'a> {
     settings: &'a Settings,
     expansion_count: usize,
-    lexer: Lexer<'a>,
-    macros: Namespace<'a>,
+    pub lexer: Lexer<'a>,
+    pub macros: Namespace<'static>,
     stack: Vec<Token>,
-    mode: Mode,
+    pub mode: Mode,
+    /// The expansion steps so far, if tracing is enabled by setting this to
+    /// `Some`.
+    pub trace: Option<Vec<ExpansionStep>>,
+    /// The traced expansions that still have tokens on the stack, as the
+    /// stack length below their tokens and their index in `trace`.
+    open_expansions: Vec<(usize, usize)>,
 }
 
 impl<'a> MacroExpander<'a> {
-    pub fn new(input: &'a str, settings: &'a Settings, mode: Mode) -> Self {
+    /// `macros` are the initial global-level macros, usually a copy of
+    /// `settings.macros`; read them back from `self.macros.current` after
+    /// parsing to keep any global definitions.
+    pub fn new(input: &'a str, settings: &'a Settings, mode: Mode, macros: MacroMap) -> Self {
         let lexer = Lexer::new(input, settings);
-        let macros = Namespace::new(&functions::MACROS, settings.macros);
+        let macros = Namespace::new(&macros::MACROS, macros);
         MacroExpander {
             settings,
             expansion_count: 0,
@@ -27,286 +56,449 @@ impl<'a> MacroExpander<'a> {
             macros,
             stack: Vec::new(),
             mode,
+            trace: None,
+            open_expansions: Vec::new(),
         }
     }
 
+    /// Feed a new input string to the same MacroExpander
+    /// (with existing macros etc.).
     pub fn feed(&mut self, input: &'a str) {
         self.lexer = Lexer::new(input, self.settings);
     }
 
+    /// Switches between "text" and "math" modes.
     pub fn switch_mode(&mut self, new_mode: Mode) {
         self.mode = new_mode;
     }
 
+    /// Start a new group nesting within all namespaces.
     pub fn begin_group(&mut self) {
         self.macros.begin_group();
     }
 
+    /// End current group nesting within all namespaces.
     pub fn end_group(&mut self) {
         self.macros.end_group();
     }
 
+    /// Ends all currently nested groups (if any), restoring values before the
+    /// groups began.  Useful in case of an error in the middle of parsing.
     pub fn end_groups(&mut self) {
         self.macros.end_groups();
     }
 
-    pub fn future(&mut self) -> Token {
+    /// Returns the topmost token on the stack, without expanding it.
+    /// Similar in behavior to TeX's `\futurelet`.
+    pub fn future(&mut self) -> Result<Token, ParseError> {
         if self.stack.is_empty() {
-            self.stack.push(self.lexer.lex());
+            // Every expansion has been used up.
+            self.open_expansions.clear();
+            let token = self.lexer.lex()?;
+            self.push_token(token);
         }
-        self.stack.last().cloned().unwrap()
+        Ok(self.stack.last().cloned().unwrap())
     }
 
-    pub fn pop_token(&mut self) -> Token {
-        self.future(); // ensure non-empty stack
-        self.stack.pop().unwrap()
+    /// Remove and return the next unexpanded token.
+    pub fn pop_token(&mut self) -> Result<Token, ParseError> {
+        self.future()?; // ensure non-empty stack
+        Ok(self.stack.pop().unwrap())
     }
 
+    /// Add a given token to the token stack.  In particular, this get be used
+    /// to put back a token returned from one of the other methods.
     pub fn push_token(&mut self, token: Token) {
         self.stack.push(token);
     }
 
+    /// Append an array of tokens to the token stack.
     pub fn push_tokens(&mut self, tokens: Vec<Token>) {
         self.stack.extend(tokens);
     }
 
-    pub fn scan_argument(&mut self, is_optional: bool) -> Option<Token> {
+    /// Find an macro argument without expanding tokens and append the array of
+    /// tokens to the token stack. Uses Token as a container for the result.
+    pub fn scan_argument(&mut self, is_optional: bool) -> Result<Option<Token>, ParseError> {
         let (start, tokens, end) = if is_optional {
-            self.consume_spaces();
-            if self.future().text != "[" {
-                return None;
+            self.consume_spaces()?; // \@ifnextchar gobbles any space following it
+            if self.future()?.text != "[" {
+                return Ok(None);
             }
-            let start = self.pop_token(); // don't include [ in tokens
-            self.consume_arg(Some("]"))
+            let start = self.pop_token()?; // don't include [ in tokens
+            let (_, tokens, end) = self.consume_arg(Some(&["]".to_string()]))?;
+            (start, tokens, end)
         } else {
-            self.consume_arg(None)
+            self.consume_arg(None)?
         };
 
-        self.push_token(Token::new("EOF".into(), end.loc));
+        // indicate the end of an argument
+        self.push_token(Token::new("EOF".into(), end.loc.clone()));
 
         self.push_tokens(tokens);
-        Some(start.range(&end, "".into()))
+        Ok(Some(start.range(&end, "".into())))
     }
 
-    pub fn consume_spaces(&mut self) {
+    /// Consume all following space tokens, without expansion.
+    pub fn consume_spaces(&mut self) -> Result<(), ParseError> {
         loop {
-            let token = self.future();
+            let token = self.future()?;
             if token.text == " " {
                 self.stack.pop();
             } else {
                 break;
             }
         }
+        Ok(())
     }
 
-    pub fn consume_arg(&mut self, delims: Option<&str>) -> (Token, Vec<Token>, Token) {
+    /// Consume an argument from the token stream, and return the resulting
+    /// array of tokens and start/end token.
+    pub fn consume_arg(
+        &mut self,
+        delims: Option<&[String]>,
+    ) -> Result<(Token, Vec<Token>, Token), ParseError> {
+        // The argument for a delimited parameter is the shortest (possibly
+        // empty) sequence of tokens with properly nested {...} groups that is
+        // followed ... by this particular list of non-parameter tokens.
+        // The argument for an undelimited parameter is the next nonblank
+        // token, unless that token is ‘{’, when the argument will be the
+        // entire {...} group that follows.
         let mut tokens = Vec::new();
-        let start = self.future();
-        let (mut depth, mut match_delim) = (0, false);
-        let (mut start_delim, mut end_delim) = (0, 0);
-        let delims = delims.map(|d| d.chars().collect::<String>());
+        let delims = delims.filter(|delims| !delims.is_empty());
+        if delims.is_none() {
+            // Ignore spaces between arguments. As the TeXbook says:
+            // "After you have said ‘\def\row#1#2{...}’, you are allowed to
+            //  put spaces between the arguments (e.g., ‘\row x n’), because
+            //  TeX doesn’t use single spaces as undelimited arguments."
+            self.consume_spaces()?;
+        }
+        let start = self.future()?;
+        let mut tok;
+        let mut depth = 0;
+        let mut matched = 0;
         loop {
-            let tok = self.pop_token();
+            tok = self.pop_token()?;
             tokens.push(tok.clone());
-            if let Some(ref d) = delims {
-                if (depth == 0 || (depth == 1 && d[start_delim..start_delim + 1] == *"{"))
-                    && tok.text == d[start_delim..start_delim + 1]
-                {
-                    if !match_delim {
-                        start_delim += 1;
-                        match_delim = true;
-                    }
-                    if start_delim == d.len() {
-                        tokens.truncate(tokens.len() - start_delim);
-                        break;
-                    }
-                } else if match_delim {
-                    start_delim = 0;
-                    match_delim = false;
-                }
-            }
             if tok.text == "{" {
                 depth += 1;
             } else if tok.text == "}" {
                 depth -= 1;
                 if depth == -1 {
-                    panic!("Extra }");
+                    return Err(ParseError::new("Extra }", Some(&tok)));
                 }
             } else if tok.text == "EOF" {
-                panic!("Unexpected end of input in a macro argument");
+                let expected = delims.map_or("}", |delims| delims[matched].as_str());
+                return Err(ParseError::new(
+                    &format!(
+                        "Unexpected end of input in a macro argument, expected '{}'",
+                        expected
+                    ),
+                    Some(&tok),
+                ));
+            }
+            if let Some(delims) = delims {
+                if (depth == 0 || (depth == 1 && delims[matched] == "{"))
+                    && tok.text == delims[matched]
+                {
+                    matched += 1;
+                    if matched == delims.len() {
+                        // don't include delims in tokens
+                        tokens.truncate(tokens.len() - matched);
+                        break;
+                    }
+                } else {
+                    matched = 0;
+                }
+            } else if depth == 0 {
+                break;
             }
         }
-
-        if start.text == "{" && tokens.last().unwrap().text == "}" {
+        // If the argument found ... has the form ‘{<nested tokens>}’,
+        // ... the outermost braces enclosing the argument are removed
+        if start.text == "{" && tokens.last().is_some_and(|token| token.text == "}") {
             tokens.pop();
             tokens.remove(0);
         }
-
-        tokens.reverse();
-        (start, tokens, self.pop_token())
+        tokens.reverse(); // to fit in with stack order
+        Ok((start, tokens, tok))
     }
 
+    /// Consume the specified number of (delimited) arguments from the token
+    /// stream and return the resulting array of arguments.
     pub fn consume_args(
         &mut self,
         num_args: usize,
-        delimiters: Option<Vec<Vec<&str>>>,
-    ) -> Vec<Vec<Token>> {
-        if let Some(delims) = delimiters {
-            if delims.len() != num_args + 1 {
-                panic!("The length of delimiters doesn't match the number of args!");
+        delimiters: Option<&[Vec<String>]>,
+    ) -> Result<Vec<Vec<Token>>, ParseError> {
+        if let Some(delimiters) = delimiters {
+            if delimiters.len() != num_args + 1 {
+                return Err(ParseError::new(
+                    "The length of delimiters doesn't match the number of args!",
+                    None,
+                ));
             }
-            let delims = delims.iter().map(|d| d[0]).collect::<Vec<_>>();
-            for d in &delims {
-                let tok = self.pop_token();
-                if d != &tok.text {
-                    panic!("Use of the macro doesn't match its definition");
+            for delim in &delimiters[0] {
+                let tok = self.pop_token()?;
+                if *delim != tok.text {
+                    return Err(ParseError::new(
+                        "Use of the macro doesn't match its definition",
+                        Some(&tok),
+                    ));
                 }
             }
         }
 
         let mut args = Vec::new();
         for i in 0..num_args {
-            args.push(
-                self.consume_arg(delimiters.as_ref().and_then(|d| d.get(i + 1)).map(|d| d[0]))
-                    .1,
-            );
+            let delims = delimiters.map(|delimiters| delimiters[i + 1].as_slice());
+            args.push(self.consume_arg(delims)?.1);
         }
-        args
+        Ok(args)
     }
 
-    pub fn count_expansion(&mut self, amount: usize) {
+    /// Increment `expansion_count` by the specified amount.
+    /// Throw an error if it exceeds `max_expand`.
+    pub fn count_expansion(&mut self, amount: usize) -> Result<(), ParseError> {
         self.expansion_count += amount;
         if self.expansion_count > self.settings.max_expand {
-            panic!("Too many expansions: infinite loop or need to increase maxExpand setting");
+            return Err(ParseError::new(
+                "Too many expansions: infinite loop or need to increase maxExpand setting",
+                None,
+            ));
         }
+        Ok(())
     }
 
-    pub fn expand_once(&mut self, expandable_only: bool) -> Option<usize> {
-        let top_token = self.pop_token();
+    /// Expand the next token only once if possible.
+    ///
+    /// If the token is expanded, the resulting tokens will be pushed onto
+    /// the stack in reverse order, and the number of such tokens will be
+    /// returned.  This number might be zero or positive.
+    ///
+    /// If not, the return value is `None`, and the next token remains at the
+    /// top of the stack.
+    ///
+    /// In either case, the next token will be on the top of the stack,
+    /// or the stack will be empty (in case of empty expansion
+    /// and no other tokens).
+    ///
+    /// Used to implement `expand_after_future` and `expand_next_token`.
+    ///
+    /// If `expandable_only`, only expandable tokens are expanded and
+    /// an undefined control sequence results in an error.
+    pub fn expand_once(&mut self, expandable_only: bool) -> Result<Option<usize>, ParseError> {
+        let top_token = self.pop_token()?;
+        // The token came from the last expansion whose tokens were not all
+        // popped before it.
+        while self.open_expansions.last().is_some_and(|&(base, _)| base > self.stack.len()) {
+            self.open_expansions.pop();
+        }
+        let parent = self.open_expansions.last().map(|&(_, step)| step);
         let name = top_token.text.clone();
-        let expansion = if !top_token.no_expand {
-            self._get_expansion(&name)
+        let expansion = if !top_token.noexpand.unwrap_or_default() {
+            self._get_expansion(&name)?
         } else {
             None
         };
-        match expansion {
-            None => {
-                if expandable_only && !self.is_defined(&name) {
-                    panic!("Undefined control sequence: {}", name);
+        let expansion = match expansion {
+            Some(expansion) if !(expandable_only && expansion.unexpandable) => expansion,
+            expansion => {
+                if expandable_only
+                    && expansion.is_none()
+                    && name.starts_with('\\')
+                    && !self.is_defined(&name)
+                {
+                    return Err(ParseError::new(
+                        &format!("Undefined control sequence: {}", name),
+                        Some(&top_token),
+                    ));
                 }
                 self.push_token(top_token);
-                None
+                return Ok(None);
             }
-            Some(exp) => {
-                self.count_expansion(1);
-                let mut tokens = exp.tokens.clone();
-                let args = self.consume_args(exp.num_args, exp.delimiters.clone());
-                if exp.num_args > 0 {
-                    tokens = tokens.into_iter().rev().collect(); // make a shallow copy
-                    for i in (0..tokens.len()).rev() {
-                        let tok = &tokens[i];
-                        if tok.text == "#" {
-                            if i == 0 {
-                                panic!("Incomplete placeholder at end of macro body");
-                            }
-                            let tok = &tokens[i - 1];
-                            if tok.text == "#" {
-                                tokens.remove(i);
-                            } else if let Some(idx) =
-                                tok.text.chars().nth(0).and_then(|c| c.to_digit(10))
-                            {
-                                tokens.splice(i - 1..=i, args[idx as usize - 1].iter().cloned());
-                            } else {
-                                panic!("Not a valid argument number");
-                            }
-                        }
+        };
+        self.count_expansion(1)?;
+        let mut tokens = expansion.tokens;
+        let args = self.consume_args(expansion.num_args, expansion.delimiters.as_deref())?;
+        if expansion.num_args > 0 {
+            // paste arguments in place of the placeholders
+            let mut i = tokens.len();
+            while i > 0 {
+                i -= 1;
+                if tokens[i].text == "#" {
+                    if i == 0 {
+                        return Err(ParseError::new(
+                            "Incomplete placeholder at end of macro body",
+                            Some(&tokens[i]),
+                        ));
+                    }
+                    i -= 1; // next token on stack
+                    let tok = &tokens[i];
+                    if tok.text == "#" {
+                        // ## → #
+                        tokens.remove(i + 1); // drop first #
+                    } else if let Some(arg) = match tok.text.as_bytes() {
+                        [digit @ b'1'..=b'9'] => args.get((digit - b'1') as usize),
+                        _ => None,
+                    } {
+                        // replace the placeholder with the indicated argument
+                        let arg = arg.clone();
+                        tokens.splice(i..i + 2, arg);
+                    } else {
+                        return Err(ParseError::new("Not a valid argument number", Some(tok)));
                     }
                 }
-                self.push_tokens(tokens);
-                Some(tokens.len())
             }
         }
+        if let Some(trace) = &mut self.trace {
+            trace.push(ExpansionStep {
+                name,
+                args: args.iter().map(|arg| tokens_to_string(arg)).collect(),
+                expansion: tokens_to_string(&tokens),
+                loc: top_token.loc.clone(),
+                group_depth: self.macros.depth(),
+                parent,
+            });
+            self.open_expansions.push((self.stack.len(), trace.len() - 1));
+        }
+        // Concatenate expansion onto top of stack.
+        let len = tokens.len();
+        self.push_tokens(tokens);
+        Ok(Some(len))
     }
 
-    pub fn expand_after_future(&mut self) -> Token {
-        self.expand_once(false);
+    /// Expand the next token only once (if possible), and return the resulting
+    /// top token on the stack (without removing anything from the stack).
+    /// Similar in behavior to TeX's `\expandafter\futurelet`.
+    /// Equivalent to `expand_once()` followed by `future()`.
+    pub fn expand_after_future(&mut self) -> Result<Token, ParseError> {
+        self.expand_once(false)?;
         self.future()
     }
 
-    pub fn expand_next_token(&mut self) -> Token {
+    /// Recursively expand first token, then return first non-expandable token.
+    pub fn expand_next_token(&mut self) -> Result<Token, ParseError> {
         loop {
-            if let Some(_) = self.expand_once(false) {
-                let token = self.stack.pop().unwrap();
-                if token.treat_as_relax {
+            if self.expand_once(false)?.is_none() {
+                // fully expanded
+                let mut token = self.stack.pop().unwrap();
+                // the token after \noexpand is interpreted as if its meaning
+                // were ‘\relax’
+                if token.treat_as_relax.unwrap_or_default() {
                     token.text = "\\relax".to_string();
                 }
-                return token;
+                return Ok(token);
             }
         }
     }
 
-    pub fn expand_macro(&mut self, name: &str) -> Option<Vec<Token>> {
-        self.macros
-            .has(name)
-            .map(|_| self.expand_tokens(vec![Token::new(name, name)]))
+    /// Fully expand the given macro name and return the resulting list of
+    /// tokens, or return `None` if no such macro is defined.
+    pub fn expand_macro(&mut self, name: &str) -> Result<Option<Vec<Token>>, ParseError> {
+        if self.macros.has(name) {
+            Ok(Some(
+                self.expand_tokens(vec![Token::new(name.to_string(), None)])?,
+            ))
+        } else {
+            Ok(None)
+        }
     }
 
-    pub fn expand_tokens(&mut self, mut tokens: Vec<Token>) -> Vec<Token> {
+    /// Fully expand the given token stream and return the resulting list of
+    /// tokens.  Note that the input tokens are in reverse order, but the
+    /// output tokens are in forward order.
+    pub fn expand_tokens(&mut self, tokens: Vec<Token>) -> Result<Vec<Token>, ParseError> {
         let mut output = Vec::new();
         let old_stack_length = self.stack.len();
         self.push_tokens(tokens);
         while self.stack.len() > old_stack_length {
-            if let Some(_) = self.expand_once(true) {
-                let token = self.stack.pop().unwrap();
-                if token.treat_as_relax {
-                    token.no_expand = false;
-                    token.treat_as_relax = false;
+            // Expand only expandable tokens
+            if self.expand_once(true)?.is_none() {
+                // fully expanded
+                let mut token = self.stack.pop().unwrap();
+                if token.treat_as_relax.unwrap_or_default() {
+                    // the expansion of \noexpand is the token itself
+                    token.noexpand = Some(false);
+                    token.treat_as_relax = Some(false);
                 }
                 output.push(token);
             }
         }
-        self.count_expansion(output.len());
-        output
+        // Count all of these tokens as additional expansions, to prevent
+        // exponential blowup from linearly many \edef's.
+        self.count_expansion(output.len())?;
+        Ok(output)
     }
 
-    pub fn expand_macro_as_text(&mut self, name: &str) -> Option<String> {
-        self.expand_macro(name)
-            .map(|tokens| tokens.iter().map(|token| &token.text).collect::<String>())
+    /// Fully expand the given macro name and return the result as a string,
+    /// or return `None` if no such macro is defined.
+    pub fn expand_macro_as_text(&mut self, name: &str) -> Result<Option<String>, ParseError> {
+        Ok(self.expand_macro(name)?.map(|tokens| {
+            tokens
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect::<String>()
+        }))
     }
 
-    fn _get_expansion(&self, name: &str) -> Option<MacroExpansion> {
-        let definition = self.macros.get(name)?;
-        if name.len() == 1 {
-            if let Some(catcode) = self.lexer.catcodes.get(&name.chars().next().unwrap()) {
-                if catcode != &13 {
-                    return None;
+    /// Returns the expanded macro as a reversed array of tokens and a macro
+    /// argument count.  Or returns `None` if no such macro.
+    fn _get_expansion(&mut self, name: &str) -> Result<Option<MacroExpansion>, ParseError> {
+        let definition = match self.macros.get(name) {
+            Some(definition) => definition.clone(),
+            None => return Ok(None),
+        };
+        // If a single character has an associated catcode other than 13
+        // (active character), then don't expand it.
+        let mut chars = name.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            if let Some(catcode) = self.lexer.catcodes.get(&ch) {
+                if *catcode != 13 {
+                    return Ok(None);
                 }
             }
         }
-        if let Some(exp) = match definition {
-            &functions::MACROS => functions::macos(name),
-            _ => None,
-        } {
-            if let Some(ref exp) = exp {
-                if let Some(idx) = exp.find('#') {
-                    let num_args = exp.chars().filter(|&c| c == '#').count();
-                    let mut tokens = Vec::new();
-                    let mut body_lexer = Lexer::new(exp, self.settings);
-                    loop {
-                        let tok = body_lexer.lex();
-                        if tok.text == "EOF" {
-                            break;
-                        }
-                        tokens.push(tok);
+        let expansion = match definition {
+            MacroDefinition::Function(function) => function(self)?,
+            definition => definition,
+        };
+        match expansion {
+            MacroDefinition::Text(body) => {
+                let mut num_args = 0;
+                if body.contains('#') {
+                    let stripped = body.replace("##", "");
+                    while stripped.contains(&format!("#{}", num_args + 1)) {
+                        num_args += 1;
                     }
-                    tokens.reverse();
-                    return Some(MacroExpansion { tokens, num_args });
                 }
+                let mut body_lexer = Lexer::new(&body, self.settings);
+                let mut tokens = Vec::new();
+                let mut tok = body_lexer.lex()?;
+                while tok.text != "EOF" {
+                    tokens.push(tok);
+                    tok = body_lexer.lex()?;
+                }
+                tokens.reverse(); // to fit in with stack using push and pop
+                Ok(Some(MacroExpansion {
+                    tokens,
+                    num_args,
+                    delimiters: None,
+                    unexpandable: false,
+                }))
             }
+            MacroDefinition::Expansion(expansion) => Ok(Some(expansion)),
+            MacroDefinition::Function(_) => Err(ParseError::new(
+                &format!("Macro {} expanded to another macro function", name),
+                None,
+            )),
         }
-        None
     }
 
+    /// Determine whether a command is currently "defined" (has some
+    /// functionality), meaning that it's a macro (in the current group),
+    /// a function, a symbol, or one of the special commands listed in
+    /// `IMPLICIT_COMMANDS`.
     pub fn is_defined(&self, name: &str) -> bool {
         self.macros.has(name)
             || functions::FUNCTIONS.contains_key(name)
@@ -315,22 +507,98 @@ impl<'a> MacroExpander<'a> {
             || IMPLICIT_COMMANDS.contains(&name)
     }
 
+    /// Determine whether a command is expandable.
     pub fn is_expandable(&self, name: &str) -> bool {
-        let macro_ = self.macros.get(name);
-        if let Some(macro_) = macro_ {
-            match macro_ {
-                &functions::MACROS => true,
-                _ => false,
+        match self.macros.get(name) {
+            Some(MacroDefinition::Expansion(expansion)) => !expansion.unexpandable,
+            Some(_) => true,
+            None => {
+                functions::FUNCTIONS.contains_key(name) && !functions::FUNCTIONS[name].primitive
             }
-        } else {
-            functions::FUNCTIONS.contains_key(name) && !functions::FUNCTIONS[name].primitive
         }
     }
 }
 
-/// Represents a macro expansion.
-#[derive(Clone, Debug)]
-pub struct MacroExpansion {
-    tokens: Vec<Token>,
-    num_args: usize,
+/// Print tokens given in reverse order as TeX source, separating control
+/// words from following letters.
+fn tokens_to_string(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    let mut after_control_word = false;
+    for token in tokens.iter().rev() {
+        if after_control_word && token.text.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+            text.push(' ');
+        }
+        text += &token.text;
+        after_control_word = regex!(r"^\\[a-zA-Z@]+$").is_match(&token.text);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_argument_numbers_beyond_the_parameters() {
+        let mut settings = Settings::default();
+        settings.macros.insert("\\foo".into(), "#1#3".into());
+        let error = parse_tree("\\foo{a}{b}{c}", &settings).unwrap_err();
+        assert_eq!(error.raw_message(), "Not a valid argument number");
+    }
+
+    #[test]
+    fn traces_each_expansion_step() {
+        let mut settings = Settings::default();
+        settings.macros.insert("\\pair".into(), "(#1,#2)".into());
+        let (result, trace) = parse_tree_with_trace("\\pair x{\\pair ab}", &settings);
+        result.unwrap();
+        let steps: Vec<_> = trace
+            .iter()
+            .map(|step| (step.name.as_str(), step.args.clone(), step.expansion.as_str()))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                ("\\pair", vec!["x".to_string(), "\\pair ab".to_string()], "(x,\\pair ab)"),
+                ("\\pair", vec!["a".to_string(), "b".to_string()], "(a,b)"),
+            ]
+        );
+        // The expression is parsed in its own group.
+        assert_eq!(trace[0].group_depth, 1);
+        assert_eq!(trace[0].loc.as_ref().map(|loc| loc.start), Some(0));
+    }
+
+    #[test]
+    fn traced_steps_link_to_the_expansion_they_came_from() {
+        let mut settings = Settings::default();
+        settings.macros.insert("\\pair".into(), "(#1,#2)".into());
+        settings.macros.insert("\\twice".into(), "\\pair{#1}{#1}".into());
+        let (result, trace) = parse_tree_with_trace("\\twice{\\pair ab}\\pair xy", &settings);
+        result.unwrap();
+        let steps: Vec<_> = trace
+            .iter()
+            .map(|step| (step.expansion.as_str(), step.parent))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                ("\\pair{\\pair ab}{\\pair ab}", None),
+                ("(\\pair ab,\\pair ab)", Some(0)),
+                ("(a,b)", Some(1)),
+                ("(a,b)", Some(1)),
+                ("(x,y)", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_is_returned_when_parsing_fails() {
+        let mut settings = Settings::default();
+        settings.macros.insert("\\bad".into(), "{\\undefinedcs}".into());
+        let (result, trace) = parse_tree_with_trace("\\bad", &settings);
+        assert!(result.is_err());
+        assert_eq!(trace.len(), 1);
+        assert_eq!(trace[0].expansion, "{\\undefinedcs}");
+        assert!(Parser::new("x", &settings).gullet.trace.is_none());
+    }
 }
@@ -0,0 +1,602 @@
+//! Predefined macros for KaTeX.
+//! This can be used to define some commands in terms of others.
+use super::*;
+
+pub static MACROS: LazyLock<BuiltinMacros> = LazyLock::new(|| {
+    let mut macros = BuiltinMacros::new();
+    define_macros(&mut macros);
+    macros
+});
+
+/// Define a macro whose body is given in TeX source.
+fn define_macro(macros: &mut BuiltinMacros, name: &'static str, body: &str) {
+    macros.insert(name, body.into());
+}
+
+/// Define a macro computed by `function` at expansion time.
+fn define_function_macro(macros: &mut BuiltinMacros, name: &'static str, function: MacroFunction) {
+    macros.insert(name, MacroDefinition::Function(function));
+}
+
+fn define_macros(macros: &mut BuiltinMacros) {
+    //////////////////////////////////////////////////////////////////////
+    // macro tools
+
+    // LaTeX's \@firstoftwo{#1}{#2} expands to #1, skipping #2
+    // TeX source: \long\def\@firstoftwo#1#2{#1}
+    define_function_macro(macros, "\\@firstoftwo", first_of_two);
+
+    // LaTeX's \@secondoftwo{#1}{#2} expands to #2, skipping #1
+    // TeX source: \long\def\@secondoftwo#1#2{#2}
+    define_function_macro(macros, "\\@secondoftwo", second_of_two);
+
+    // LaTeX's \@ifnextchar#1#2#3 looks ahead to the next (unexpanded) token.
+    // If it is `#1`, then it expands to #2; otherwise, it expands to #3.
+    // Note, however, that it does not consume the next token.
+    define_function_macro(macros, "\\@ifnextchar", if_next_char);
+
+    // LaTeX's \@ifstar#1#2 looks ahead to the next (unexpanded) token.
+    // If it is `*`, then it consumes the symbol, and the macro expands to #1;
+    // otherwise, the macro expands to #2 (without consuming the symbol).
+    // TeX source: \def\@ifstar#1{\@ifnextchar *{\@firstoftwo{#1}}}
+    define_macro(macros, "\\@ifstar", "\\@ifnextchar *{\\@firstoftwo{#1}}");
+
+    // LaTeX's \TextOrMath{#1}{#2} expands to #1 in text mode, #2 in math mode
+    define_function_macro(macros, "\\TextOrMath", text_or_math);
+
+    // TeX \char makes a literal character (catcode 12) using the following forms:
+    // (see The TeXBook, p. 43)
+    //   \char123  -- decimal
+    //   \char'123 -- octal
+    //   \char"123 -- hex
+    //   \char`x   -- character that can be written (i.e. isn't active)
+    //   \char`\x  -- character that cannot be written (e.g. %)
+    // These all refer to characters from the font, so we turn them into special
+    // calls to a function \@char dealt with in the Parser.
+    define_function_macro(macros, "\\char", char_);
+
+    // TeX \mathchar takes a number in the same forms as \char, encoding the
+    // math class in its top hex digit, the family in the next one, and the
+    // position in that family's font in the low byte, e.g. \mathchar"2203
+    // is \ast, the binary operator at position 3 of family 2 (cmsy). These
+    // turn into calls to a function \@mathchar, which decodes the number.
+    define_function_macro(macros, "\\mathchar", mathchar);
+
+    // \newcommand{\macro}[args]{definition}
+    // \renewcommand{\macro}[args]{definition}
+    // TODO: Optional arguments: \newcommand{\macro}[args][default]{definition}
+    define_function_macro(macros, "\\newcommand", |context| {
+        newcommand(context, false, true, false)
+    });
+    define_function_macro(macros, "\\renewcommand", |context| {
+        newcommand(context, true, false, false)
+    });
+    define_function_macro(macros, "\\providecommand", |context| {
+        newcommand(context, true, true, true)
+    });
+
+    //////////////////////////////////////////////////////////////////////
+    // amsmath.sty
+    // http://mirrors.concertpass.com/tex-archive/macros/latex/required/amsmath/amsmath.pdf
+
+    // \def\dots{\ifmmode\expandafter\mdots@\else\expandafter\tdots@\fi}
+    //
+    // \mdots@ looks at the next token to decide which kind of dots to use:
+    // binary operators and relations get \dotsb, commas get \dotsc, integrals
+    // get \dotsi, and anything else gets \dotso.
+    define_function_macro(macros, "\\dots", dots);
+
+    define_function_macro(macros, "\\dotso", dotso);
+    define_function_macro(macros, "\\dotsc", dotsc);
+    define_function_macro(macros, "\\cdots", cdots);
+
+    define_macro(macros, "\\dotsb", "\\cdots");
+    define_macro(macros, "\\dotsm", "\\cdots");
+    define_macro(macros, "\\dotsi", "\\!\\cdots");
+    // amsmath doesn't actually define \dotsx, but \dots followed by a macro
+    // starting with \DOTSX implies \dotso, and then \extra@ detects this case
+    // and forces the added `\,`.
+    define_macro(macros, "\\dotsx", "\\ldots\\,");
+
+    // \let\DOTSI\relax
+    // \let\DOTSB\relax
+    // \let\DOTSX\relax
+    define_macro(macros, "\\DOTSI", "\\relax");
+    define_macro(macros, "\\DOTSB", "\\relax");
+    define_macro(macros, "\\DOTSX", "\\relax");
+
+    // \def\tmspace#1#2#3{\ifmmode\mskip#1#2\else\kern#1#3\fi\relax}
+    // TODO: math mode should use \thinmuskip
+    define_macro(macros, "\\tmspace", "\\TextOrMath{\\kern#1#3}{\\mskip#1#2}\\relax");
+    // \renewcommand{\,}{\tmspace+\thinmuskip{.1667em}}
+    // TODO: math mode should use \thinmuskip
+    define_macro(macros, "\\,", "\\tmspace+{3mu}{.1667em}");
+    // \let\thinspace\,
+    define_macro(macros, "\\thinspace", "\\,");
+    // \def\>{\mskip\medmuskip}
+    // \renewcommand{\:}{\tmspace+\medmuskip{.2222em}}
+    // TODO: \> and math mode of \: should use \medmuskip = 4mu plus 2mu minus 4mu
+    define_macro(macros, "\\>", "\\mskip{4mu}");
+    define_macro(macros, "\\:", "\\tmspace+{4mu}{.2222em}");
+    // \let\medspace\:
+    define_macro(macros, "\\medspace", "\\:");
+    // \renewcommand{\;}{\tmspace+\thickmuskip{.2777em}}
+    // TODO: math mode should use \thickmuskip = 5mu plus 5mu
+    define_macro(macros, "\\;", "\\tmspace+{5mu}{.2777em}");
+    // \let\thickspace\;
+    define_macro(macros, "\\thickspace", "\\;");
+    // \renewcommand{\!}{\tmspace-\thinmuskip{.1667em}}
+    // TODO: math mode should use \thinmuskip
+    define_macro(macros, "\\!", "\\tmspace-{3mu}{.1667em}");
+    // \let\negthinspace\!
+    define_macro(macros, "\\negthinspace", "\\!");
+    // \newcommand{\negmedspace}{\tmspace-\medmuskip{.2222em}}
+    // TODO: math mode should use \medmuskip
+    define_macro(macros, "\\negmedspace", "\\tmspace-{4mu}{.2222em}");
+    // \newcommand{\negthickspace}{\tmspace-\thickmuskip{.277em}}
+    // TODO: math mode should use \thickmuskip
+    define_macro(macros, "\\negthickspace", "\\tmspace-{5mu}{.277em}");
+    // \def\enspace{\kern.5em }
+    define_macro(macros, "\\enspace", "\\kern.5em ");
+    // \def\enskip{\hskip.5em\relax}
+    define_macro(macros, "\\enskip", "\\hskip.5em\\relax");
+    // \def\quad{\hskip1em\relax}
+    define_macro(macros, "\\quad", "\\hskip1em\\relax");
+    // \def\qquad{\hskip2em\relax}
+    define_macro(macros, "\\qquad", "\\hskip2em\\relax");
+
+    // \operatorname* is \operatornamewithlimits, see functions/operatorname.rs
+    define_macro(
+        macros,
+        "\\operatorname",
+        "\\@ifstar\\operatornamewithlimits\\operatorname@",
+    );
+
+    // \DeclareMathOperator{\cmd}{text} and \DeclareMathOperator*{\cmd}{text}
+    define_macro(
+        macros,
+        "\\DeclareMathOperator",
+        "\\@ifstar{\\@DeclareMathOperator{*}}{\\@DeclareMathOperator{}}",
+    );
+    define_macro(
+        macros,
+        "\\@DeclareMathOperator",
+        "\\newcommand{#2}{\\operatorname#1{#3}}",
+    );
+
+    // \tag{x} numbers the equation with (x), and \tag*{x} with just x.
+    define_macro(macros, "\\tag", "\\@ifstar\\tag@literal\\tag@paren");
+    define_macro(macros, "\\tag@paren", "\\tag@literal{({#1})}");
+    define_function_macro(macros, "\\tag@literal", tag_literal);
+
+    // \nonumber and \notag clear the equation switch, which drops the \tag.
+    define_macro(macros, "\\nonumber", "\\gdef\\@eqnsw{0}");
+    define_macro(macros, "\\notag", "\\nonumber");
+
+    //////////////////////////////////////////////////////////////////////
+    // LaTeX_2ε
+
+    // \mbox{x} sets x in text mode, like \text{x}
+    define_macro(macros, "\\mbox", "\\text{#1}");
+
+    // \def~{\nobreakspace{}}
+    define_macro(macros, "~", "\\nobreakspace");
+
+    // \DeclareRobustCommand\hspace{\@ifstar\@hspacer\@hspace}
+    // \def\@hspace#1{\hskip  #1\relax}
+    // \def\@hspacer#1{\vrule \@width\z@\nobreak
+    //                 \hskip #1\hskip \z@skip}
+    define_macro(macros, "\\hspace", "\\@ifstar\\@hspacer\\@hspace");
+    define_macro(macros, "\\@hspace", "\\hskip #1\\relax");
+    define_macro(macros, "\\@hspacer", "\\rule{0pt}{0pt}\\hskip #1\\relax");
+
+    // \def\llap#1{\hb@xt@\z@{\hss#1}}
+    // \def\rlap#1{\hb@xt@\z@{#1\hss}}
+    // mathtools.sty: \def\clap#1{\hb@xt@\z@{\hss#1\hss}}
+    define_macro(macros, "\\llap", "\\mathllap{\\textrm{#1}}");
+    define_macro(macros, "\\rlap", "\\mathrlap{\\textrm{#1}}");
+    define_macro(macros, "\\clap", "\\mathclap{\\textrm{#1}}");
+}
+
+/// The kind of dots \dots should become when followed by the given token,
+/// according to amsmath's \mdots@.
+fn dots_by_token(text: &str) -> Option<&'static str> {
+    match text {
+        "," => Some("\\dotsc"),
+        "\\not" | "+" | "=" | "<" | ">" | "-" | "*" | ":" | "\\DOTSB" | "\\coprod"
+        | "\\bigvee" | "\\bigwedge" | "\\biguplus" | "\\bigcap" | "\\bigcup" | "\\prod"
+        | "\\sum" | "\\bigotimes" | "\\bigoplus" | "\\bigodot" | "\\bigsqcup" | "\\And"
+        | "\\longrightarrow" | "\\Longrightarrow" | "\\longleftarrow" | "\\Longleftarrow"
+        | "\\longleftrightarrow" | "\\Longleftrightarrow" | "\\mapsto" | "\\longmapsto"
+        | "\\hookrightarrow" | "\\doteq" | "\\mathbin" | "\\mathrel" | "\\relbar"
+        | "\\Relbar" | "\\xrightarrow" | "\\xleftarrow" => Some("\\dotsb"),
+        "\\DOTSI" | "\\int" | "\\oint" | "\\iint" | "\\iiint" | "\\iiiint" | "\\idotsint" => {
+            Some("\\dotsi")
+        }
+        "\\DOTSX" => Some("\\dotsx"),
+        _ => None,
+    }
+}
+
+fn dots(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    // TODO: If used in text mode, should expand to \textellipsis.
+    // However, in KaTeX, \textellipsis and \ldots behave the same
+    // (in text mode), and it's unlikely we'd see any of the math commands
+    // that affect the behavior of \dots when in text mode.  So fine for now
+    // (until we support \ifmmode ... \else ... \fi).
+    let next = context.expand_after_future()?.text;
+    let thedots = if let Some(thedots) = dots_by_token(&next) {
+        thedots
+    } else if next.starts_with("\\not")
+        || symbols::MATH_SYMBOLS
+            .get(next.as_str())
+            .is_some_and(|symbol| matches!(symbol.group, Group::Bin | Group::Rel))
+    {
+        "\\dotsb"
+    } else {
+        "\\dotso"
+    };
+    Ok(thedots.into())
+}
+
+/// Whether amsmath's \extra@ adds a thin space after dots followed by the
+/// given token.
+fn space_after_dots(text: &str) -> bool {
+    matches!(
+        text,
+        // \rightdelim@ checks for the following:
+        ")" | "]" | "\\rbrack" | "\\}" | "\\rbrace" | "\\rangle" | "\\rceil" | "\\rfloor"
+            | "\\rgroup" | "\\rmoustache" | "\\right" | "\\bigr" | "\\biggr" | "\\Bigr"
+            | "\\Biggr"
+            // \extra@ also tests for the following:
+            | "$"
+            // \extrap@ checks for the following:
+            | ";" | "." | ","
+    )
+}
+
+fn dotso(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let next = context.future()?.text;
+    if space_after_dots(&next) {
+        Ok("\\ldots\\,".into())
+    } else {
+        Ok("\\ldots".into())
+    }
+}
+
+fn dotsc(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let next = context.future()?.text;
+    // \dotsc uses \extra@ but not \extrap@, instead specially checking for
+    // ';' and '.', but doesn't check for ','.
+    if space_after_dots(&next) && next != "," {
+        Ok("\\ldots\\,".into())
+    } else {
+        Ok("\\ldots".into())
+    }
+}
+
+fn cdots(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let next = context.future()?.text;
+    if space_after_dots(&next) {
+        Ok("\\@cdots\\,".into())
+    } else {
+        Ok("\\@cdots".into())
+    }
+}
+
+fn first_of_two(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let mut args = context.consume_args(2, None)?;
+    Ok(args.swap_remove(0).into())
+}
+
+fn second_of_two(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let mut args = context.consume_args(2, None)?;
+    Ok(args.swap_remove(1).into())
+}
+
+fn if_next_char(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let mut args = context.consume_args(3, None)?; // symbol, if, else
+    context.consume_spaces()?;
+    let next_token = context.future()?;
+    // Like LaTeX's \ifx test, the next token matches if it is the same single
+    // token, so `\@ifnextchar[` does not match `{[}`.
+    if args[0].len() == 1 && args[0][0].text == next_token.text {
+        Ok(args.swap_remove(1).into())
+    } else {
+        Ok(args.swap_remove(2).into())
+    }
+}
+
+fn text_or_math(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let mut args = context.consume_args(2, None)?;
+    if context.mode == Mode::Text {
+        Ok(args.swap_remove(0).into())
+    } else {
+        Ok(args.swap_remove(1).into())
+    }
+}
+
+/// Parse the number following \char or \mathchar, in any of the forms
+/// described above \char.
+fn scan_char_number(context: &mut MacroExpander, command: &str) -> Result<u32, ParseError> {
+    let mut token = context.pop_token()?;
+    let base = if token.text == "'" {
+        token = context.pop_token()?;
+        8
+    } else if token.text == "\"" {
+        token = context.pop_token()?;
+        16
+    } else if token.text == "`" {
+        token = context.pop_token()?;
+        if token.text == "EOF" {
+            return Err(ParseError::new(
+                &format!("{}` missing argument", command),
+                Some(&token),
+            ));
+        }
+        let mut chars = token.text.chars();
+        let first = chars.next().unwrap_or_default();
+        let code = if first == '\\' {
+            chars.next().unwrap_or(first)
+        } else {
+            first
+        };
+        return Ok(code as u32);
+    } else {
+        10
+    };
+    // Parse a number in the given base, starting with first `token`.
+    let digit_of = |token: &Token| {
+        let mut chars = token.text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => ch.to_digit(16).filter(|digit| *digit < base),
+            _ => None,
+        }
+    };
+    let Some(mut number) = digit_of(&token) else {
+        return Err(ParseError::new(
+            &format!("Invalid base-{} digit {}", base, token.text),
+            Some(&token),
+        ));
+    };
+    while let Some(digit) = digit_of(&context.future()?) {
+        number = number.saturating_mul(base).saturating_add(digit);
+        context.pop_token()?;
+    }
+    Ok(number)
+}
+
+fn char_(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let number = scan_char_number(context, "\\char")?;
+    Ok(format!("\\@char{{{}}}", number).into())
+}
+
+fn mathchar(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    let number = scan_char_number(context, "\\mathchar")?;
+    if number >= 0x8000 {
+        return Err(ParseError::new(
+            &format!("Bad mathchar ({})", number),
+            None,
+        ));
+    }
+    Ok(format!("\\@mathchar{{{}}}", number).into())
+}
+
+fn newcommand(
+    context: &mut MacroExpander,
+    exists_ok: bool,
+    nonexists_ok: bool,
+    skip_if_exists: bool,
+) -> Result<MacroDefinition, ParseError> {
+    let (_, mut arg, _) = context.consume_arg(None)?;
+    if arg.len() != 1 {
+        return Err(ParseError::new(
+            "\\newcommand's first argument must be a macro name",
+            None,
+        ));
+    }
+    let name = arg[0].text.clone();
+
+    let exists = context.is_defined(&name);
+    if exists && !exists_ok {
+        return Err(ParseError::new(
+            &format!(
+                "\\newcommand{{{}}} attempting to redefine {}; use \\renewcommand",
+                name, name
+            ),
+            None,
+        ));
+    }
+    if !exists && !nonexists_ok {
+        return Err(ParseError::new(
+            &format!(
+                "\\renewcommand{{{}}} when command {} does not yet exist; use \\newcommand",
+                name, name
+            ),
+            None,
+        ));
+    }
+
+    let mut num_args = 0;
+    (_, arg, _) = context.consume_arg(None)?;
+    if arg.len() == 1 && arg[0].text == "[" {
+        let mut arg_text = String::new();
+        let mut token = context.expand_next_token()?;
+        while token.text != "]" && token.text != "EOF" {
+            // TODO: Should properly expand arg, e.g., ignore {}s
+            arg_text += &token.text;
+            token = context.expand_next_token()?;
+        }
+        if !regex!(r"^\s*[0-9]+\s*$").is_match(&arg_text) {
+            return Err(ParseError::new(
+                &format!("Invalid number of arguments: {}", arg_text),
+                Some(&token),
+            ));
+        }
+        num_args = arg_text.trim().parse().unwrap_or_default();
+        (_, arg, _) = context.consume_arg(None)?;
+    }
+
+    if !(exists && skip_if_exists) {
+        // Final arg is the expansion of the macro
+        context.macros.set(
+            &name,
+            Some(MacroDefinition::Expansion(MacroExpansion {
+                tokens: arg,
+                num_args,
+                delimiters: None,
+                unexpandable: false,
+            })),
+            false,
+        );
+    }
+    Ok("".into())
+}
+
+fn tag_literal(context: &mut MacroExpander) -> Result<MacroDefinition, ParseError> {
+    if context.macros.has("\\df@tag") {
+        return Err(ParseError::new("Multiple \\tag", None));
+    }
+    Ok("\\gdef\\df@tag{\\textrm{#1}}".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expand the first token of `input` once and return the resulting tokens.
+    fn expand_once(input: &str) -> Vec<String> {
+        let settings = Settings::default();
+        let mut gullet = MacroExpander::new(input, &settings, Mode::Math, MacroMap::new());
+        gullet.expand_once(false).unwrap();
+        let mut tokens = Vec::new();
+        loop {
+            let token = gullet.pop_token().unwrap();
+            if token.text == "EOF" {
+                return tokens;
+            }
+            tokens.push(token.text);
+        }
+    }
+
+    /// Fully expand `input` in the given mode and return the resulting tokens.
+    fn expand(input: &str, mode: Mode) -> Vec<String> {
+        let settings = Settings::default();
+        let mut gullet = MacroExpander::new(input, &settings, mode, MacroMap::new());
+        let mut tokens = Vec::new();
+        loop {
+            let token = gullet.expand_next_token().unwrap();
+            if token.text == "EOF" {
+                return tokens;
+            }
+            tokens.push(token.text);
+        }
+    }
+
+    #[test]
+    fn dots_chooses_variant_from_next_token() {
+        assert_eq!(expand_once("\\dots+"), ["\\dotsb", "+"]);
+        assert_eq!(expand_once("\\dots\\le"), ["\\dotsb", "\\le"]);
+        assert_eq!(expand_once("\\dots,"), ["\\dotsc", ","]);
+        assert_eq!(expand_once("\\dots\\int"), ["\\dotsi", "\\int"]);
+        assert_eq!(expand_once("\\dots x"), ["\\dotso", "x"]);
+    }
+
+    #[test]
+    fn dots_add_thin_space_before_closing_delimiters() {
+        assert_eq!(expand_once("\\dotso)"), ["\\ldots", "\\,", ")"]);
+        assert_eq!(expand_once("\\dotso x"), ["\\ldots", "x"]);
+        assert_eq!(expand_once("\\cdots;"), ["\\@cdots", "\\,", ";"]);
+        assert_eq!(expand_once("\\cdots+"), ["\\@cdots", "+"]);
+        assert_eq!(expand_once("\\dotsc;"), ["\\ldots", "\\,", ";"]);
+        assert_eq!(expand_once("\\dotsc,"), ["\\ldots", ","]);
+        assert_eq!(expand_once("\\dotsi"), ["\\!", "\\cdots"]);
+    }
+
+    #[test]
+    fn dots_render_as_ellipses() {
+        let settings = Settings::default();
+        assert!(render_to_string("a\\dots+b", &settings).contains("\u{22ef}"));
+        assert!(render_to_string("a,\\dots,b", &settings).contains("\u{2026}"));
+
+        // Every variant expands to defined control sequences, even before a
+        // closing delimiter.
+        for dots in ["\\dotsb", "\\dotsc", "\\dotsi", "\\dotsm", "\\dotso", "\\dotsx"] {
+            let tree = parse_tree(&format!("({})", dots), &settings);
+            assert!(tree.is_ok(), "{}", dots);
+        }
+    }
+
+    #[test]
+    fn if_next_char_peeks_without_consuming() {
+        assert_eq!(expand("\\@ifnextchar[{a}{b}[", Mode::Math), ["a", "["]);
+        assert_eq!(expand("\\@ifnextchar[{a}{b} x", Mode::Math), ["b", "x"]);
+        assert_eq!(expand("\\@ifnextchar[{a}{b}{[}", Mode::Math), ["b", "{", "[", "}"]);
+    }
+
+    #[test]
+    fn if_star_consumes_the_star() {
+        assert_eq!(expand("\\@ifstar{a}{b}*", Mode::Math), ["a"]);
+        assert_eq!(expand("\\@ifstar{a}{b}x", Mode::Math), ["b", "x"]);
+    }
+
+    #[test]
+    fn text_or_math_depends_on_mode() {
+        assert_eq!(expand("\\TextOrMath{a}{b}", Mode::Text), ["a"]);
+        assert_eq!(expand("\\TextOrMath{a}{b}", Mode::Math), ["b"]);
+    }
+
+    #[test]
+    fn char_accepts_all_number_forms() {
+        let settings = Settings::default();
+        for input in ["\\char65", "\\char'101", "\\char\"41", "\\char`A", "\\char`\\A"] {
+            let tree = parse_tree(input, &settings).unwrap();
+            let [AnyParseNode::TextOrd(node)] = &tree[..] else {
+                panic!("{} did not parse to a textord", input);
+            };
+            assert_eq!(node.text, "A");
+        }
+        assert!(parse_tree("\\char\"4G", &settings).is_ok());
+        assert!(parse_tree("\\charx", &settings).is_err());
+
+        // \char accepts every Unicode scalar value, up to U+10FFFF.
+        let tree = parse_tree("\\char1114111", &settings).unwrap();
+        let [AnyParseNode::TextOrd(node)] = &tree[..] else {
+            panic!("\\char1114111 did not parse to a textord");
+        };
+        assert_eq!(node.text, "\u{10ffff}");
+        assert!(parse_tree("\\char1114112", &settings).is_err());
+        assert!(parse_tree("\\char\"D800", &settings).is_err());
+    }
+
+    #[test]
+    fn mathchar_decodes_class_family_and_position() {
+        let settings = Settings::default();
+        let parse_one = |input: &str| {
+            let mut tree = parse_tree(input, &settings).unwrap();
+            assert_eq!(tree.len(), 1, "{}", input);
+            tree.remove(0)
+        };
+        let AnyParseNode::Atom(ast) = parse_one("\\mathchar\"2203") else {
+            panic!("\\mathchar\"2203 did not parse to an atom");
+        };
+        assert_eq!((ast.family, ast.text.as_str()), (Group::Bin, "\u{2217}"));
+        let AnyParseNode::Atom(equals) = parse_one("\\mathchar\"303D") else {
+            panic!("\\mathchar\"303D did not parse to an atom");
+        };
+        assert_eq!((equals.family, equals.text.as_str()), (Group::Rel, "="));
+        let AnyParseNode::MathOrd(alpha) = parse_one("\\mathchar\"010B") else {
+            panic!("\\mathchar\"010B did not parse to a mathord");
+        };
+        assert_eq!(alpha.text, "\u{3b1}");
+        let AnyParseNode::TextOrd(digit) = parse_one("\\mathchar\"7031") else {
+            panic!("\\mathchar\"7031 did not parse to a textord");
+        };
+        assert_eq!(digit.text, "1");
+
+        let error = |input| parse_tree(input, &settings).unwrap_err().raw_message().to_string();
+        assert_eq!(error("\\mathchar\"8000"), "Bad mathchar (32768)");
+        assert_eq!(error("\\mathchar\"0500"), "\\mathchar family 5 has no font");
+        assert_eq!(error("\\mathchar\"012C"), "\\mathchar\"012C has no character");
+    }
+}
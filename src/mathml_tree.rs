@@ -0,0 +1,261 @@
+//! These objects store data about MathML nodes. This is the MathML equivalent
+//! of the types in dom_tree.rs. Since MathML handles its own rendering, and
+//! since we're mainly using MathML to improve accessibility, we don't manage
+//! any of the styling state that the plain DOM nodes do.
+//!
+//! The `to_markup` functions work similarly to how they do in dom_tree.rs,
+//! generating HTML markup for the MathML nodes.
+use super::*;
+
+/// MathML node types used in KaTeX. For a complete list of MathML nodes, see
+/// https://developer.mozilla.org/en-US/docs/Web/MathML/Element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathNodeType {
+    Math,
+    Annotation,
+    Semantics,
+    Mtext,
+    Mn,
+    Mo,
+    Mi,
+    Mspace,
+    Mover,
+    Munder,
+    Munderover,
+    Msup,
+    Msub,
+    Msubsup,
+    Mfrac,
+    Mroot,
+    Msqrt,
+    Mtable,
+    Mtr,
+    Mtd,
+    Mlabeledtr,
+    Mrow,
+    Menclose,
+    Mstyle,
+    Mpadded,
+    Mphantom,
+    Mglyph,
+}
+
+impl MathNodeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MathNodeType::Math => "math",
+            MathNodeType::Annotation => "annotation",
+            MathNodeType::Semantics => "semantics",
+            MathNodeType::Mtext => "mtext",
+            MathNodeType::Mn => "mn",
+            MathNodeType::Mo => "mo",
+            MathNodeType::Mi => "mi",
+            MathNodeType::Mspace => "mspace",
+            MathNodeType::Mover => "mover",
+            MathNodeType::Munder => "munder",
+            MathNodeType::Munderover => "munderover",
+            MathNodeType::Msup => "msup",
+            MathNodeType::Msub => "msub",
+            MathNodeType::Msubsup => "msubsup",
+            MathNodeType::Mfrac => "mfrac",
+            MathNodeType::Mroot => "mroot",
+            MathNodeType::Msqrt => "msqrt",
+            MathNodeType::Mtable => "mtable",
+            MathNodeType::Mtr => "mtr",
+            MathNodeType::Mtd => "mtd",
+            MathNodeType::Mlabeledtr => "mlabeledtr",
+            MathNodeType::Mrow => "mrow",
+            MathNodeType::Menclose => "menclose",
+            MathNodeType::Mstyle => "mstyle",
+            MathNodeType::Mpadded => "mpadded",
+            MathNodeType::Mphantom => "mphantom",
+            MathNodeType::Mglyph => "mglyph",
+        }
+    }
+}
+
+/// A node in the MathML tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MathDomNode {
+    Math(MathNode),
+    Text(TextNode),
+    Space(SpaceNode),
+    /// A list of nodes without a representation of its own.
+    Fragment(Vec<MathDomNode>),
+}
+
+impl MathDomNode {
+    /// Converts the math node into a string, similar to innerText, but
+    /// escaped.
+    pub fn to_markup(&self) -> String {
+        match self {
+            MathDomNode::Math(node) => node.to_markup(),
+            MathDomNode::Text(node) => node.to_markup(),
+            MathDomNode::Space(node) => node.to_markup(),
+            MathDomNode::Fragment(children) => {
+                children.iter().map(MathDomNode::to_markup).collect()
+            }
+        }
+    }
+
+    /// Converts the math node into a string, similar to innerText.
+    pub fn to_text(&self) -> String {
+        match self {
+            MathDomNode::Math(node) => node.to_text(),
+            MathDomNode::Text(node) => node.text.clone(),
+            MathDomNode::Space(node) => node.to_text(),
+            MathDomNode::Fragment(children) => children.iter().map(MathDomNode::to_text).collect(),
+        }
+    }
+}
+
+impl From<MathNode> for MathDomNode {
+    fn from(node: MathNode) -> Self {
+        MathDomNode::Math(node)
+    }
+}
+
+impl From<TextNode> for MathDomNode {
+    fn from(node: TextNode) -> Self {
+        MathDomNode::Text(node)
+    }
+}
+
+impl From<SpaceNode> for MathDomNode {
+    fn from(node: SpaceNode) -> Self {
+        MathDomNode::Space(node)
+    }
+}
+
+/// This node represents a general purpose MathML node of any type. The
+/// constructor requires the type of node to create (for example, `"mo"` or
+/// `"mspace"`, corresponding to `<mo>` and `<mspace>` tags).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathNode {
+    pub type_: MathNodeType,
+    pub attributes: HashMap<String, String>,
+    pub children: Vec<MathDomNode>,
+    pub classes: Vec<String>,
+}
+
+impl MathNode {
+    pub fn new(type_: MathNodeType, children: Vec<MathDomNode>, classes: Vec<String>) -> Self {
+        MathNode {
+            type_,
+            attributes: HashMap::new(),
+            children,
+            classes,
+        }
+    }
+
+    /// Sets an attribute on a MathML node. MathML depends on attributes to convey a
+    /// semantic content, so this is used heavily.
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        self.attributes.insert(name.to_string(), value.to_string());
+    }
+
+    /// Gets an attribute on a MathML node.
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    /// Converts the math node into an HTML markup string.
+    pub fn to_markup(&self) -> String {
+        let mut markup = format!("<{}", self.type_.as_str());
+
+        // Add the attributes
+        for (attr, value) in &self.attributes {
+            markup += &format!(" {}=\"{}\"", attr, utils::escape(value));
+        }
+
+        if !self.classes.is_empty() {
+            markup += &format!(" class=\"{}\"", utils::escape(&create_class(&self.classes)));
+        }
+
+        markup += ">";
+
+        for child in &self.children {
+            markup += &child.to_markup();
+        }
+
+        markup += &format!("</{}>", self.type_.as_str());
+
+        markup
+    }
+
+    /// Converts the math node into a string, similar to innerText, but escaped.
+    pub fn to_text(&self) -> String {
+        self.children.iter().map(MathDomNode::to_text).collect()
+    }
+}
+
+/// This node represents a piece of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextNode {
+    pub text: String,
+}
+
+impl TextNode {
+    pub fn new(text: &str) -> Self {
+        TextNode {
+            text: text.to_string(),
+        }
+    }
+
+    /// Converts the text node into escaped HTML markup
+    /// (representing the text itself).
+    pub fn to_markup(&self) -> String {
+        utils::escape(&self.text)
+    }
+}
+
+/// This node represents a space, but may render as <mspace.../> or as text,
+/// depending on the width.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpaceNode {
+    pub width: f64,
+    pub character: Option<&'static str>,
+}
+
+impl SpaceNode {
+    /// Create a Space node with width given in CSS ems.
+    pub fn new(width: f64) -> Self {
+        // See https://www.w3.org/TR/2000/WD-MathML2-20000328/chapter6.html
+        // for a table of space-like characters.  We use Unicode
+        // representations instead of &LongNames; as it's not clear how to
+        // make the latter via document.createTextNode.
+        let character = if (0.05555..=0.05556).contains(&width) {
+            Some("\u{200a}") // &VeryThinSpace;
+        } else if (0.1666..=0.1667).contains(&width) {
+            Some("\u{2009}") // &ThinSpace;
+        } else if (0.2222..=0.2223).contains(&width) {
+            Some("\u{2005}") // &MediumSpace;
+        } else if (0.2777..=0.2778).contains(&width) {
+            Some("\u{2005}\u{200a}") // &ThickSpace;
+        } else if (-0.05556..=-0.05555).contains(&width) {
+            Some("\u{200a}\u{2063}") // &NegativeVeryThinSpace;
+        } else if (-0.1667..=-0.1666).contains(&width) {
+            Some("\u{2009}\u{2063}") // &NegativeThinSpace;
+        } else if (-0.2223..=-0.2222).contains(&width) {
+            Some("\u{205f}\u{2063}") // &NegativeMediumSpace;
+        } else if (-0.2778..=-0.2777).contains(&width) {
+            Some("\u{2005}\u{2063}") // &NegativeThickSpace;
+        } else {
+            None
+        };
+        SpaceNode { width, character }
+    }
+
+    /// Converts the math node into an HTML markup string.
+    pub fn to_markup(&self) -> String {
+        match self.character {
+            Some(character) => format!("<mtext>{}</mtext>", character),
+            None => format!("<mspace width=\"{}\"/>", make_em(self.width)),
+        }
+    }
+
+    /// Converts the math node into a string, similar to innerText.
+    pub fn to_text(&self) -> String {
+        self.character.unwrap_or(" ").to_string()
+    }
+}
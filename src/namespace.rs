@@ -0,0 +1,146 @@
+//! A `Namespace` refers to a space of nameable things like macros or lengths,
+//! which can be `set` either globally or local to a nested group, using an
+//! undo stack similar to how TeX implements this functionality.
+//! Performance-wise, `get` and local `set` take constant time, while global
+//! `set` takes time proportional to the depth of group nesting.
+use super::*;
+
+pub struct Namespace<'a> {
+    pub current: MacroMap,
+    builtins: &'a BuiltinMacros,
+    undef_stack: Vec<HashMap<String, Option<MacroDefinition>>>,
+}
+
+impl<'a> Namespace<'a> {
+    /// Both arguments are optional. The first argument is an object of
+    /// built-in mappings which never change. The second argument is an object
+    /// of initial (global-level) mappings, which will constantly change
+    /// according to any global/top-level `set`s done.
+    pub fn new(builtins: &'a BuiltinMacros, global_macros: MacroMap) -> Self {
+        Namespace {
+            current: global_macros,
+            builtins,
+            undef_stack: Vec::new(),
+        }
+    }
+
+    /// Start a new nested group, affecting future local `set`s.
+    pub fn begin_group(&mut self) {
+        self.undef_stack.push(HashMap::new());
+    }
+
+    /// End current nested group, restoring values before the group began.
+    pub fn end_group(&mut self) {
+        let undefs = self.undef_stack.pop().expect(
+            "Unbalanced namespace destruction: attempt to pop global namespace; \
+             please report this as a bug",
+        );
+        for (undef, value) in undefs {
+            match value {
+                None => self.current.remove(&undef),
+                Some(value) => self.current.insert(undef, value),
+            };
+        }
+    }
+
+    /// Ends all currently nested groups (if any), restoring values before the
+    /// groups began.  Useful in case of an error in the middle of parsing.
+    pub fn end_groups(&mut self) {
+        while !self.undef_stack.is_empty() {
+            self.end_group();
+        }
+    }
+
+    /// The number of nested groups currently open.
+    pub fn depth(&self) -> usize {
+        self.undef_stack.len()
+    }
+
+    /// Detect whether `name` has a definition.  Equivalent to
+    /// `get(name).is_some()`.
+    pub fn has(&self, name: &str) -> bool {
+        self.current.contains_key(name) || self.builtins.contains_key(name)
+    }
+
+    /// Get the current value of a name, or `None` if there is no value.
+    pub fn get(&self, name: &str) -> Option<&MacroDefinition> {
+        self.current.get(name).or_else(|| self.builtins.get(name))
+    }
+
+    /// Set the current value of a name, and optionally set it globally too.
+    /// Local set() sets the current value and (when appropriate) adds an undo
+    /// operation to the undo stack.  Global set() may change the undo
+    /// operation at every level, so takes time linear in their number.
+    /// A value of `None` means to delete existing definitions.
+    pub fn set(&mut self, name: &str, value: Option<MacroDefinition>, global: bool) {
+        if global {
+            // Global set is equivalent to setting in all groups.  Simulate this
+            // by destroying any undos currently scheduled for this name,
+            // and adding an undo with the *new* value (in case it later gets
+            // locally reset within this environment).
+            for undefs in self.undef_stack.iter_mut() {
+                undefs.remove(name);
+            }
+            if let Some(undefs) = self.undef_stack.last_mut() {
+                undefs.insert(name.to_string(), value.clone());
+            }
+        } else {
+            // Undo this set at end of this group (possibly to `None`),
+            // unless an undo is already in place, in which case that older
+            // value is the correct one.
+            if let Some(top) = self.undef_stack.last_mut() {
+                if !top.contains_key(name) {
+                    top.insert(name.to_string(), self.current.get(name).cloned());
+                }
+            }
+        }
+        match value {
+            None => self.current.remove(name),
+            Some(value) => self.current.insert(name.to_string(), value),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(namespace: &Namespace, name: &str) -> Option<String> {
+        match namespace.get(name) {
+            Some(MacroDefinition::Text(body)) => Some(body.clone()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn groups_undo_local_definitions() {
+        let builtins = BuiltinMacros::from([("\\b", "builtin".into())]);
+        let mut namespace = Namespace::new(&builtins, MacroMap::new());
+        namespace.set("\\a", Some("outer".into()), false);
+        namespace.begin_group();
+        namespace.set("\\a", Some("inner".into()), false);
+        namespace.set("\\b", Some("local".into()), false);
+        assert_eq!(text(&namespace, "\\a").as_deref(), Some("inner"));
+        assert_eq!(text(&namespace, "\\b").as_deref(), Some("local"));
+        namespace.end_group();
+        assert_eq!(text(&namespace, "\\a").as_deref(), Some("outer"));
+        assert_eq!(text(&namespace, "\\b").as_deref(), Some("builtin"));
+    }
+
+    #[test]
+    fn global_definitions_survive_groups() {
+        let builtins = BuiltinMacros::new();
+        let mut namespace = Namespace::new(&builtins, MacroMap::new());
+        namespace.begin_group();
+        namespace.begin_group();
+        namespace.set("\\a", Some("local".into()), false);
+        namespace.set("\\a", Some("global".into()), true);
+        namespace.set("\\c", Some("gone".into()), false);
+        namespace.end_groups();
+        assert_eq!(text(&namespace, "\\a").as_deref(), Some("global"));
+        assert!(!namespace.has("\\c"));
+
+        namespace.set("\\a", None, false);
+        assert!(!namespace.has("\\a"));
+    }
+}
@@ -1,48 +1,79 @@
 //! This file contains information about the options that the Parser carries
 //! around with it while parsing. Data is held in an `Options` object, and when
-//! recursing, a new `Options` object can be created with the `.with*` and
-//! `.reset` functions.
+//! recursing, a new `Options` object can be created with the `.having*` and
+//! `.with*` functions.
+use super::*;
 
 /// Each element contains [textsize, scriptsize, scriptscriptsize].
 /// The size mappings are taken from TeX with \normalsize=10pt.
-const SIZE_STYLE_MAP: [[f64; 3]; 11] = [
-    [5.0, 5.0, 5.0],       // size1: [5, 5, 5]              \tiny
-    [6.0, 5.0, 5.0],       // size2: [6, 5, 5]
-    [7.0, 5.0, 5.0],       // size3: [7, 5, 5]              \scriptsize
-    [8.0, 6.0, 5.0],       // size4: [8, 6, 5]              \footnotesize
-    [9.0, 6.0, 5.0],       // size5: [9, 6, 5]              \small
-    [10.0, 7.0, 5.0],      // size6: [10, 7, 5]             \normalsize
-    [12.0, 8.0, 6.0],      // size7: [12, 8, 6]             \large
-    [14.4, 10.0, 7.0],     // size8: [14.4, 10, 7]          \Large
-    [17.28, 12.0, 10.0],   // size9: [17.28, 12, 10]        \LARGE
-    [20.74, 14.4, 12.0],   // size10: [20.74, 14.4, 12]     \huge
-    [24.88, 20.74, 17.28], // size11: [24.88, 20.74, 17.28] \HUGE
+const SIZE_STYLE_MAP: [[usize; 3]; 11] = [
+    [1, 1, 1],    // size1: [5, 5, 5]              \tiny
+    [2, 1, 1],    // size2: [6, 5, 5]
+    [3, 1, 1],    // size3: [7, 5, 5]              \scriptsize
+    [4, 2, 1],    // size4: [8, 6, 5]              \footnotesize
+    [5, 2, 1],    // size5: [9, 6, 5]              \small
+    [6, 3, 1],    // size6: [10, 7, 5]             \normalsize
+    [7, 4, 2],    // size7: [12, 8, 6]             \large
+    [8, 6, 3],    // size8: [14.4, 10, 7]          \Large
+    [9, 7, 6],    // size9: [17.28, 12, 10]        \LARGE
+    [10, 8, 7],   // size10: [20.74, 14.4, 12]     \huge
+    [11, 10, 9],  // size11: [24.88, 20.74, 17.28] \HUGE
 ];
 
 /// fontMetrics.js:getGlobalMetrics also uses size indexes, so if
 /// you change size indexes, change that function.
 const SIZE_MULTIPLIERS: [f64; 11] = [0.5, 0.6, 0.7, 0.8, 0.9, 1.0, 1.2, 1.44, 1.728, 2.074, 2.488];
 
-#[derive(Clone)]
+fn size_at_style(size: usize, style: Style) -> usize {
+    if style.size < 2 {
+        size
+    } else {
+        SIZE_STYLE_MAP[size - 1][style.size - 1]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FontWeight {
     TextBF,
     TextMD,
     None,
 }
 
-#[derive(Clone)]
+impl FontWeight {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FontWeight::TextBF => "textbf",
+            FontWeight::TextMD => "textmd",
+            FontWeight::None => "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FontShape {
     TextIt,
     TextUp,
+    None,
+}
+
+impl FontShape {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FontShape::TextIt => "textit",
+            FontShape::TextUp => "textup",
+            FontShape::None => "",
+        }
+    }
 }
 
 /// This is the main options class. It contains the current style, size, color,
 /// and font.
 ///
 /// Options objects should not be modified. To create a new Options with
-/// different properties, call a `.having*` method.
+/// different properties, call a `.having*` or `.with*` method.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Options {
-    // pub style: StyleInterface,
+    pub style: Style,
     pub color: Option<String>,
     pub size: usize,
     pub text_size: usize,
@@ -55,81 +86,234 @@ pub struct Options {
     pub font_weight: FontWeight,
     pub font_shape: FontShape,
     pub size_multiplier: f64,
-    pub max_size: usize,
-    pub min_rule_thickness: usize,
-    // pub font_metrics: Option<FontMetrics>,
+    pub max_size: f64,
+    pub min_rule_thickness: f64,
+    /// Put equation tags on the left, as in the leqno document option.
+    pub leqno: bool,
+    /// Set display equations flush left, as in the fleqn document option.
+    pub fleqn: bool,
 }
 
 impl Options {
     /// The base size index.
-    const BASESIZE: usize = 6;
+    pub const BASESIZE: usize = 6;
 
     pub fn new(data: OptionsData) -> Self {
-        let size_multiplier = SIZE_MULTIPLIERS[data.size - 1];
+        let size = data.size.unwrap_or(Options::BASESIZE);
         Options {
-            // style: data.style,
+            style: data.style,
             color: data.color,
-            size: data.size,
-            text_size: data.text_size,
+            size,
+            text_size: data.text_size.unwrap_or(size),
             phantom: data.phantom,
             font: data.font.unwrap_or_default(),
             font_family: data.font_family.unwrap_or_default(),
             font_weight: data.font_weight.unwrap_or(FontWeight::None),
-            font_shape: data.font_shape.unwrap_or(FontShape::TextUp),
-            size_multiplier,
+            font_shape: data.font_shape.unwrap_or(FontShape::None),
+            size_multiplier: SIZE_MULTIPLIERS[size - 1],
             max_size: data.max_size,
             min_rule_thickness: data.min_rule_thickness,
-            // font_metrics: None,
+            leqno: data.leqno,
+            fleqn: data.fleqn,
+        }
+    }
+
+    /// Return an options object with the given style. If `self.style ==
+    /// style`, returns a copy of `self`.
+    pub fn having_style(&self, style: Style) -> Self {
+        if self.style == style {
+            return self.clone();
+        }
+        let size = size_at_style(self.text_size, style);
+        Options {
+            style,
+            size,
+            size_multiplier: SIZE_MULTIPLIERS[size - 1],
+            ..self.clone()
         }
     }
 
-    /// Returns a new options object with the same properties as "this".  Properties
-    /// from "extension" will be copied to the new options object.
-    pub fn extend(&self, extension: OptionsData) -> Self {
-        let mut data = extension;
-        data.style = self.style;
-        data.size = self.size;
-        data.text_size = self.text_size;
-        data.color = self.color.clone();
-        data.phantom = self.phantom;
-        data.font = Some(self.font.clone());
-        data.font_family = Some(self.font_family.clone());
-        data.font_weight = Some(self.font_weight.clone());
-        data.font_shape = Some(self.font_shape.clone());
-        Options::new(data)
+    /// Return an options object with a cramped version of the current style. If
+    /// the current style is cramped, returns a copy of `self`.
+    pub fn having_cramped_style(&self) -> Self {
+        self.having_style(self.style.cramp())
     }
 
-    // Implement other methods similarly
+    /// Return an options object with the given size and in at least `\textstyle`.
+    /// Returns a copy of `self` if appropriate.
+    pub fn having_size(&self, size: usize) -> Self {
+        if self.size == size && self.text_size == size {
+            return self.clone();
+        }
+        Options {
+            style: self.style.text(),
+            size,
+            text_size: size,
+            size_multiplier: SIZE_MULTIPLIERS[size - 1],
+            ..self.clone()
+        }
+    }
+
+    /// Like `self.having_size(BASESIZE).having_style(style)`. If `style` is
+    /// omitted, changes to at least `\textstyle`.
+    pub fn having_base_style(&self, style: Option<Style>) -> Self {
+        let style = style.unwrap_or_else(|| self.style.text());
+        let want_size = size_at_style(Options::BASESIZE, style);
+        if self.size == want_size && self.text_size == Options::BASESIZE && self.style == style {
+            return self.clone();
+        }
+        Options {
+            style,
+            size: want_size,
+            size_multiplier: SIZE_MULTIPLIERS[want_size - 1],
+            ..self.clone()
+        }
+    }
+
+    /// Remove the effect of sizing changes such as \Huge.
+    /// Keep the effect of the current style, such as \scriptstyle.
+    pub fn having_base_sizing(&self) -> Self {
+        let size = match self.style.id {
+            4 | 5 => 3, // normalsize in scriptstyle
+            6 | 7 => 1, // normalsize in scriptscriptstyle
+            _ => 6,     // normalsize in textstyle or displaystyle
+        };
+        Options {
+            style: self.style.text(),
+            size,
+            size_multiplier: SIZE_MULTIPLIERS[size - 1],
+            ..self.clone()
+        }
+    }
+
+    /// Create a new options object with the given color.
+    pub fn with_color(&self, color: &str) -> Self {
+        Options {
+            color: Some(color.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// Create a new options object with "phantom" set to true.
+    pub fn with_phantom(&self) -> Self {
+        Options {
+            phantom: true,
+            ..self.clone()
+        }
+    }
+
+    /// Creates a new options object with the given math font or old text font.
+    pub fn with_font(&self, font: &str) -> Self {
+        Options {
+            font: font.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Create a new options objects with the given font family.
+    pub fn with_text_font_family(&self, font_family: &str) -> Self {
+        Options {
+            font_family: font_family.to_string(),
+            font: String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Creates a new options object with the given font weight
+    pub fn with_text_font_weight(&self, font_weight: FontWeight) -> Self {
+        Options {
+            font_weight,
+            font: String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Creates a new options object with the given font shape
+    pub fn with_text_font_shape(&self, font_shape: FontShape) -> Self {
+        Options {
+            font_shape,
+            font: String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Return the CSS sizing classes required to switch from enclosing options
+    /// `old_options` to `self`. Returns an empty list if no change is needed.
+    pub fn sizing_classes(&self, old_options: &Options) -> Vec<String> {
+        if old_options.size != self.size {
+            vec![
+                "sizing".to_string(),
+                format!("reset-size{}", old_options.size),
+                format!("size{}", self.size),
+            ]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Return the CSS sizing classes required to switch to the base size. Like
+    /// `self.having_size(BASESIZE).sizing_classes(self)`.
+    pub fn base_sizing_classes(&self) -> Vec<String> {
+        if self.size != Options::BASESIZE {
+            vec![
+                "sizing".to_string(),
+                format!("reset-size{}", self.size),
+                format!("size{}", Options::BASESIZE),
+            ]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Return the font metrics for this size.
+    pub fn font_metrics(&self) -> &'static FontMetrics {
+        get_global_metrics(self.size)
+    }
+
+    /// Gets the CSS color of the current options object
+    pub fn get_color(&self) -> Option<String> {
+        if self.phantom {
+            Some("transparent".to_string())
+        } else {
+            self.color.clone()
+        }
+    }
 }
 
+/// The data to create `Options` with. Fields left as `None` get their
+/// defaults.
 pub struct OptionsData {
-    // pub style: StyleInterface,
+    pub style: Style,
     pub color: Option<String>,
-    pub size: usize,
-    pub text_size: usize,
+    pub size: Option<usize>,
+    pub text_size: Option<usize>,
     pub phantom: bool,
     pub font: Option<String>,
     pub font_family: Option<String>,
     pub font_weight: Option<FontWeight>,
     pub font_shape: Option<FontShape>,
-    pub max_size: usize,
-    pub min_rule_thickness: usize,
+    pub max_size: f64,
+    pub min_rule_thickness: f64,
+    pub leqno: bool,
+    pub fleqn: bool,
 }
 
 impl Default for OptionsData {
     fn default() -> Self {
         OptionsData {
-            // style: Default::default(),
+            style: Style::TEXT,
             color: None,
-            size: Options::BASESIZE,
-            text_size: Options::BASESIZE,
+            size: None,
+            text_size: None,
             phantom: false,
             font: None,
             font_family: None,
             font_weight: None,
             font_shape: None,
-            max_size: 0,
-            min_rule_thickness: 0,
+            max_size: f64::INFINITY,
+            min_rule_thickness: 0.0,
+            leqno: false,
+            fleqn: false,
         }
     }
 }
@@ -11,6 +11,7 @@ pub struct ParseError {
     position: Option<usize>, // Error start position based on passed-in Token or ParseNode.
     length: Option<usize>,   // Length of affected text based on passed-in Token or ParseNode.
     raw_message: String,     // The underlying error message without any context added.
+    message: String,         // The message with the position and context added.
 }
 
 impl ParseError {
@@ -18,49 +19,49 @@ impl ParseError {
         let mut error = format!("KaTeX parse error: {}", message);
         let (mut start, mut end) = (None, None);
 
-        if let Some(token) = token {
-            let loc = &token.loc;
+        if let Some(loc) = token.and_then(|token| token.loc.as_ref()) {
             if loc.start <= loc.end {
                 // If we have the input and a position, make the error a bit fancier
 
                 // Get the input
-                let input = &loc.lexer.input;
+                let input = loc.lexer.input();
 
                 // Prepend some information
-                start = Some(loc.start);
-                end = Some(loc.end);
-                if let Some(start) = start {
-                    if start == input.len() {
-                        error += " at end of input: ";
-                    } else {
-                        error += &format!(" at position {}: ", start + 1);
-                    }
+                if loc.start == input.len() {
+                    error += " at end of input: ";
+                } else {
+                    error += &format!(" at position {}: ", loc.start + 1);
+                }
 
-                    // Underline token in question using combining underscores
-                    let underlined = input[start..end]
-                        .chars()
-                        .map(|c| {
-                            if c != '\n' {
-                                format!("{}{}", c, '\u{0332}')
-                            } else {
-                                c.to_string()
-                            }
-                        })
-                        .collect::<String>();
+                // Underline token in question using combining underscores
+                let underlined = input
+                    .get(loc.start..loc.end)
+                    .unwrap_or_default()
+                    .chars()
+                    .map(|c| {
+                        if c != '\n' {
+                            format!("{}{}", c, '\u{0332}')
+                        } else {
+                            c.to_string()
+                        }
+                    })
+                    .collect::<String>();
 
-                    // Extract some context from the input and add it to the error
-                    let left = if start > 15 {
-                        format!("{}{}", "…", &input[start - 15..start])
-                    } else {
-                        input[..start].to_string()
-                    };
-                    let right = if end.unwrap() + 15 < input.len() {
-                        format!("{}{}", &input[end.unwrap()..end.unwrap() + 15], "…")
-                    } else {
-                        input[end.unwrap()..].to_string()
-                    };
-                    error += &format!("{}{}{}", left, underlined, right);
-                }
+                // Extract some context from the input and add it to the error
+                let before = &input[..loc.start.min(input.len())];
+                let after = &input[loc.end.min(input.len())..];
+                let left = match before.char_indices().rev().nth(14) {
+                    Some((index, _)) if index > 0 => format!("…{}", &before[index..]),
+                    _ => before.to_string(),
+                };
+                let right = match after.char_indices().nth(15) {
+                    Some((index, _)) => format!("{}…", &after[..index]),
+                    None => after.to_string(),
+                };
+                error += &format!("{}{}{}", left, underlined, right);
+
+                start = Some(loc.start);
+                end = Some(loc.end);
             }
         }
 
@@ -68,12 +69,28 @@ impl ParseError {
             position: start,
             length: end.map(|end| end - start.unwrap()),
             raw_message: message.to_string(),
+            message: error,
         }
     }
+
+    /// The byte offset of the error in the input, if known.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    /// The byte length of the offending input, if known.
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+
+    /// The error message without the position and context.
+    pub fn raw_message(&self) -> &str {
+        &self.raw_message
+    }
 }
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.raw_message)
+        write!(f, "{}", self.message)
     }
 }
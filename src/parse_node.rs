@@ -12,10 +12,11 @@ pub enum ColSeparationType {
     // Define your ColSeparationType variants here
 }
 
-/// Represents a measurement.
+/// Represents a measurement: a number and a TeX unit such as "pt" or "em".
 #[derive(Debug, Clone, PartialEq)]
 pub struct Measurement {
-    // Define your Measurement struct fields here
+    pub number: f64,
+    pub unit: String,
 }
 
 /// Represents an atom.
@@ -79,6 +80,219 @@ pub enum AnyParseNode {
     Underline(UnderlineParseNode),
     Vcenter(VcenterParseNode),
     XArrow(XArrowParseNode),
+    Atom(AtomParseNode),
+    AccentToken(AccentTokenParseNode),
+    MathOrd(MathOrdParseNode),
+    OpToken(OpTokenParseNode),
+    Spacing(SpacingParseNode),
+    TextOrd(TextOrdParseNode),
+}
+
+impl AnyParseNode {
+    /// The type of the node, e.g. "ordgroup" or "genfrac".
+    pub fn type_(&self) -> &str {
+        match self {
+            AnyParseNode::Array(node) => &node.type_,
+            AnyParseNode::CdLabel(node) => &node.type_,
+            AnyParseNode::CdLabelParent(node) => &node.type_,
+            AnyParseNode::Color(node) => &node.type_,
+            AnyParseNode::ColorToken(node) => &node.type_,
+            AnyParseNode::Op(node) => &node.type_,
+            AnyParseNode::OrdGroup(node) => &node.type_,
+            AnyParseNode::Raw(node) => &node.type_,
+            AnyParseNode::Size(node) => &node.type_,
+            AnyParseNode::Styling(node) => &node.type_,
+            AnyParseNode::SupSub(node) => &node.type_,
+            AnyParseNode::Tag(node) => &node.type_,
+            AnyParseNode::Text(node) => &node.type_,
+            AnyParseNode::Url(node) => &node.type_,
+            AnyParseNode::Verb(node) => &node.type_,
+            AnyParseNode::Accent(node) => &node.type_,
+            AnyParseNode::AccentUnder(node) => &node.type_,
+            AnyParseNode::Cr(node) => &node.type_,
+            AnyParseNode::DelimSizing(node) => &node.type_,
+            AnyParseNode::Enclose(node) => &node.type_,
+            AnyParseNode::Environment(node) => &node.type_,
+            AnyParseNode::Font(node) => &node.type_,
+            AnyParseNode::Genfrac(node) => &node.type_,
+            AnyParseNode::Hbox(node) => &node.type_,
+            AnyParseNode::HorizBrace(node) => &node.type_,
+            AnyParseNode::Href(node) => &node.type_,
+            AnyParseNode::Html(node) => &node.type_,
+            AnyParseNode::HtmlMathml(node) => &node.type_,
+            AnyParseNode::Includegraphics(node) => &node.type_,
+            AnyParseNode::Infix(node) => &node.type_,
+            AnyParseNode::Internal(node) => &node.type_,
+            AnyParseNode::Kern(node) => &node.type_,
+            AnyParseNode::Lap(node) => &node.type_,
+            AnyParseNode::LeftRight(node) => &node.type_,
+            AnyParseNode::LeftRightRight(node) => &node.type_,
+            AnyParseNode::MathChoice(node) => &node.type_,
+            AnyParseNode::Middle(node) => &node.type_,
+            AnyParseNode::Mclass(node) => &node.type_,
+            AnyParseNode::Operatorname(node) => &node.type_,
+            AnyParseNode::Overline(node) => &node.type_,
+            AnyParseNode::Phantom(node) => &node.type_,
+            AnyParseNode::Hphantom(node) => &node.type_,
+            AnyParseNode::Vphantom(node) => &node.type_,
+            AnyParseNode::Pmb(node) => &node.type_,
+            AnyParseNode::Raisebox(node) => &node.type_,
+            AnyParseNode::Rule(node) => &node.type_,
+            AnyParseNode::Sizing(node) => &node.type_,
+            AnyParseNode::Smash(node) => &node.type_,
+            AnyParseNode::Sqrt(node) => &node.type_,
+            AnyParseNode::Underline(node) => &node.type_,
+            AnyParseNode::Vcenter(node) => &node.type_,
+            AnyParseNode::XArrow(node) => &node.type_,
+            AnyParseNode::Atom(node) => &node.type_,
+            AnyParseNode::AccentToken(node) => &node.type_,
+            AnyParseNode::MathOrd(node) => &node.type_,
+            AnyParseNode::OpToken(node) => &node.type_,
+            AnyParseNode::Spacing(node) => &node.type_,
+            AnyParseNode::TextOrd(node) => &node.type_,
+        }
+    }
+
+    /// The text of a symbol node, i.e. one created from the symbol table.
+    pub fn symbol_text(&self) -> Option<&str> {
+        match self {
+            AnyParseNode::Atom(node) => Some(&node.text),
+            AnyParseNode::AccentToken(node) => Some(&node.text),
+            AnyParseNode::MathOrd(node) => Some(&node.text),
+            AnyParseNode::OpToken(node) => Some(&node.text),
+            AnyParseNode::Spacing(node) => Some(&node.text),
+            AnyParseNode::TextOrd(node) => Some(&node.text),
+            _ => None,
+        }
+    }
+
+    /// The mode the node was parsed in.
+    pub fn mode(&self) -> &str {
+        match self {
+            AnyParseNode::Array(node) => &node.mode,
+            AnyParseNode::CdLabel(node) => &node.mode,
+            AnyParseNode::CdLabelParent(node) => &node.mode,
+            AnyParseNode::Color(node) => &node.mode,
+            AnyParseNode::ColorToken(node) => &node.mode,
+            AnyParseNode::Op(node) => &node.mode,
+            AnyParseNode::OrdGroup(node) => &node.mode,
+            AnyParseNode::Raw(node) => &node.mode,
+            AnyParseNode::Size(node) => &node.mode,
+            AnyParseNode::Styling(node) => &node.mode,
+            AnyParseNode::SupSub(node) => &node.mode,
+            AnyParseNode::Tag(node) => &node.mode,
+            AnyParseNode::Text(node) => &node.mode,
+            AnyParseNode::Url(node) => &node.mode,
+            AnyParseNode::Verb(node) => &node.mode,
+            AnyParseNode::Accent(node) => &node.mode,
+            AnyParseNode::AccentUnder(node) => &node.mode,
+            AnyParseNode::Cr(node) => &node.mode,
+            AnyParseNode::DelimSizing(node) => &node.mode,
+            AnyParseNode::Enclose(node) => &node.mode,
+            AnyParseNode::Environment(node) => &node.mode,
+            AnyParseNode::Font(node) => &node.mode,
+            AnyParseNode::Genfrac(node) => &node.mode,
+            AnyParseNode::Hbox(node) => &node.mode,
+            AnyParseNode::HorizBrace(node) => &node.mode,
+            AnyParseNode::Href(node) => &node.mode,
+            AnyParseNode::Html(node) => &node.mode,
+            AnyParseNode::HtmlMathml(node) => &node.mode,
+            AnyParseNode::Includegraphics(node) => &node.mode,
+            AnyParseNode::Infix(node) => &node.mode,
+            AnyParseNode::Internal(node) => &node.mode,
+            AnyParseNode::Kern(node) => &node.mode,
+            AnyParseNode::Lap(node) => &node.mode,
+            AnyParseNode::LeftRight(node) => &node.mode,
+            AnyParseNode::LeftRightRight(node) => &node.mode,
+            AnyParseNode::MathChoice(node) => &node.mode,
+            AnyParseNode::Middle(node) => &node.mode,
+            AnyParseNode::Mclass(node) => &node.mode,
+            AnyParseNode::Operatorname(node) => &node.mode,
+            AnyParseNode::Overline(node) => &node.mode,
+            AnyParseNode::Phantom(node) => &node.mode,
+            AnyParseNode::Hphantom(node) => &node.mode,
+            AnyParseNode::Vphantom(node) => &node.mode,
+            AnyParseNode::Pmb(node) => &node.mode,
+            AnyParseNode::Raisebox(node) => &node.mode,
+            AnyParseNode::Rule(node) => &node.mode,
+            AnyParseNode::Sizing(node) => &node.mode,
+            AnyParseNode::Smash(node) => &node.mode,
+            AnyParseNode::Sqrt(node) => &node.mode,
+            AnyParseNode::Underline(node) => &node.mode,
+            AnyParseNode::Vcenter(node) => &node.mode,
+            AnyParseNode::XArrow(node) => &node.mode,
+            AnyParseNode::Atom(node) => &node.mode,
+            AnyParseNode::AccentToken(node) => &node.mode,
+            AnyParseNode::MathOrd(node) => &node.mode,
+            AnyParseNode::OpToken(node) => &node.mode,
+            AnyParseNode::Spacing(node) => &node.mode,
+            AnyParseNode::TextOrd(node) => &node.mode,
+        }
+    }
+
+    /// The source location of the node, if known.
+    pub fn loc(&self) -> Option<&SourceLocation> {
+        match self {
+            AnyParseNode::Array(node) => node.loc.as_ref(),
+            AnyParseNode::CdLabel(node) => node.loc.as_ref(),
+            AnyParseNode::CdLabelParent(node) => node.loc.as_ref(),
+            AnyParseNode::Color(node) => node.loc.as_ref(),
+            AnyParseNode::ColorToken(node) => node.loc.as_ref(),
+            AnyParseNode::Op(node) => node.loc.as_ref(),
+            AnyParseNode::OrdGroup(node) => node.loc.as_ref(),
+            AnyParseNode::Raw(node) => node.loc.as_ref(),
+            AnyParseNode::Size(node) => node.loc.as_ref(),
+            AnyParseNode::Styling(node) => node.loc.as_ref(),
+            AnyParseNode::SupSub(node) => node.loc.as_ref(),
+            AnyParseNode::Tag(node) => node.loc.as_ref(),
+            AnyParseNode::Text(node) => node.loc.as_ref(),
+            AnyParseNode::Url(node) => node.loc.as_ref(),
+            AnyParseNode::Verb(node) => node.loc.as_ref(),
+            AnyParseNode::Accent(node) => node.loc.as_ref(),
+            AnyParseNode::AccentUnder(node) => node.loc.as_ref(),
+            AnyParseNode::Cr(node) => node.loc.as_ref(),
+            AnyParseNode::DelimSizing(node) => node.loc.as_ref(),
+            AnyParseNode::Enclose(node) => node.loc.as_ref(),
+            AnyParseNode::Environment(node) => node.loc.as_ref(),
+            AnyParseNode::Font(node) => node.loc.as_ref(),
+            AnyParseNode::Genfrac(node) => node.loc.as_ref(),
+            AnyParseNode::Hbox(node) => node.loc.as_ref(),
+            AnyParseNode::HorizBrace(node) => node.loc.as_ref(),
+            AnyParseNode::Href(node) => node.loc.as_ref(),
+            AnyParseNode::Html(node) => node.loc.as_ref(),
+            AnyParseNode::HtmlMathml(node) => node.loc.as_ref(),
+            AnyParseNode::Includegraphics(node) => node.loc.as_ref(),
+            AnyParseNode::Infix(node) => node.loc.as_ref(),
+            AnyParseNode::Internal(node) => node.loc.as_ref(),
+            AnyParseNode::Kern(node) => node.loc.as_ref(),
+            AnyParseNode::Lap(node) => node.loc.as_ref(),
+            AnyParseNode::LeftRight(node) => node.loc.as_ref(),
+            AnyParseNode::LeftRightRight(node) => node.loc.as_ref(),
+            AnyParseNode::MathChoice(node) => node.loc.as_ref(),
+            AnyParseNode::Middle(node) => node.loc.as_ref(),
+            AnyParseNode::Mclass(node) => node.loc.as_ref(),
+            AnyParseNode::Operatorname(node) => node.loc.as_ref(),
+            AnyParseNode::Overline(node) => node.loc.as_ref(),
+            AnyParseNode::Phantom(node) => node.loc.as_ref(),
+            AnyParseNode::Hphantom(node) => node.loc.as_ref(),
+            AnyParseNode::Vphantom(node) => node.loc.as_ref(),
+            AnyParseNode::Pmb(node) => node.loc.as_ref(),
+            AnyParseNode::Raisebox(node) => node.loc.as_ref(),
+            AnyParseNode::Rule(node) => node.loc.as_ref(),
+            AnyParseNode::Sizing(node) => node.loc.as_ref(),
+            AnyParseNode::Smash(node) => node.loc.as_ref(),
+            AnyParseNode::Sqrt(node) => node.loc.as_ref(),
+            AnyParseNode::Underline(node) => node.loc.as_ref(),
+            AnyParseNode::Vcenter(node) => node.loc.as_ref(),
+            AnyParseNode::XArrow(node) => node.loc.as_ref(),
+            AnyParseNode::Atom(node) => node.loc.as_ref(),
+            AnyParseNode::AccentToken(node) => node.loc.as_ref(),
+            AnyParseNode::MathOrd(node) => node.loc.as_ref(),
+            AnyParseNode::OpToken(node) => node.loc.as_ref(),
+            AnyParseNode::Spacing(node) => node.loc.as_ref(),
+            AnyParseNode::TextOrd(node) => node.loc.as_ref(),
+        }
+    }
 }
 
 /// Represents an array parse node.
@@ -332,7 +546,10 @@ pub struct GenfracParseNode {
     pub left_delim: Option<String>,
     pub right_delim: Option<String>,
     pub size: String,
-    pub bar_size: Option<Measurement>, // Define your GenfracParseNode struct fields here
+    pub bar_size: Option<Measurement>,
+    /// The alignment of the numerator of a `\cfrac`, "left" or "right";
+    /// centered if `None`.
+    pub numer_align: Option<String>,
 }
 
 /// Represents an hbox parse node.
@@ -629,3 +846,58 @@ pub struct XArrowParseNode {
     pub body: Box<AnyParseNode>,
     pub below: Option<Box<AnyParseNode>>, // Define your XArrowParseNode struct fields here
 }
+
+/// Represents an atom parse node, a symbol in one of the TeX atom classes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomParseNode {
+    pub type_: String,
+    pub mode: String,
+    pub loc: Option<SourceLocation>,
+    pub family: Group,
+    pub text: String,
+}
+
+/// Represents an accent token parse node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccentTokenParseNode {
+    pub type_: String,
+    pub mode: String,
+    pub loc: Option<SourceLocation>,
+    pub text: String,
+}
+
+/// Represents a math ord parse node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathOrdParseNode {
+    pub type_: String,
+    pub mode: String,
+    pub loc: Option<SourceLocation>,
+    pub text: String,
+}
+
+/// Represents an op token parse node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpTokenParseNode {
+    pub type_: String,
+    pub mode: String,
+    pub loc: Option<SourceLocation>,
+    pub text: String,
+}
+
+/// Represents a spacing parse node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpacingParseNode {
+    pub type_: String,
+    pub mode: String,
+    pub loc: Option<SourceLocation>,
+    pub text: String,
+}
+
+/// Represents a text ord parse node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextOrdParseNode {
+    pub type_: String,
+    pub mode: String,
+    pub loc: Option<SourceLocation>,
+    pub text: String,
+}
@@ -1,24 +1,89 @@
 use super::*;
 
+/// Macros the parser defines for its own bookkeeping while parsing one
+/// expression. They are never handed back as global macros.
+const INTERNAL_MACROS: &[&str] = &["\\df@tag", "\\@eqnsw", "\\current@color"];
+
 /// Parses an expression using a Parser, then returns the parsed result.
 pub fn parse_tree(to_parse: &str, settings: &Settings) -> Result<Vec<AnyParseNode>, ParseError> {
-    let mut parser = Parser::new(to_parse, settings);
+    let mut macros = settings.macros.clone();
+    parse_tree_with_macros(to_parse, settings, &mut macros)
+}
+
+/// Like `parse_tree`, but starts from the global-level `macros` instead of a
+/// copy of `settings.macros`, and leaves the global-level macros in `macros`
+/// afterwards, even if parsing fails. Internal macros such as `\df@tag`
+/// are left out.
+/// With `settings.global_group`, this keeps the definitions made at the top
+/// level of `to_parse` for the next call.
+pub fn parse_tree_with_macros(
+    to_parse: &str,
+    settings: &Settings,
+    macros: &mut MacroMap,
+) -> Result<Vec<AnyParseNode>, ParseError> {
+    parse_tree_with_macros_and_trace(to_parse, settings, macros, None)
+}
+
+/// Like `parse_tree`, but also records each macro expansion step, as with
+/// TeX's `\tracingmacros`. The steps are returned even if parsing fails.
+pub fn parse_tree_with_trace(
+    to_parse: &str,
+    settings: &Settings,
+) -> (Result<Vec<AnyParseNode>, ParseError>, Vec<ExpansionStep>) {
+    let mut macros = settings.macros.clone();
+    let mut trace = Vec::new();
+    let result =
+        parse_tree_with_macros_and_trace(to_parse, settings, &mut macros, Some(&mut trace));
+    (result, trace)
+}
+
+/// `parse_tree_with_macros`, which also replaces the contents of `trace`, if
+/// given, with the expansion steps of `to_parse`.
+pub fn parse_tree_with_macros_and_trace(
+    to_parse: &str,
+    settings: &Settings,
+    macros: &mut MacroMap,
+    trace: Option<&mut Vec<ExpansionStep>>,
+) -> Result<Vec<AnyParseNode>, ParseError> {
+    let mut parser = Parser::with_macros(to_parse, settings, std::mem::take(macros));
+    if trace.is_some() {
+        parser.gullet.trace = Some(Vec::new());
+    }
+    let result = parse_with_parser(&mut parser, settings);
+    // `parse` ends all groups, even on error, so only global macros remain.
+    *macros = std::mem::take(&mut parser.gullet.macros.current);
+    macros.retain(|name, _| !INTERNAL_MACROS.contains(&name.as_str()));
+    if let Some(trace) = trace {
+        *trace = parser.gullet.trace.take().unwrap_or_default();
+    }
+    result
+}
 
+fn parse_with_parser(
+    parser: &mut Parser,
+    settings: &Settings,
+) -> Result<Vec<AnyParseNode>, ParseError> {
     // Blank out any \df@tag to avoid spurious "Duplicate \tag" errors
     parser.gullet.macros.current.remove("\\df@tag");
+    parser.gullet.macros.current.remove("\\@eqnsw");
 
-    let tree = parser.parse()?;
+    let tree = parser.parse();
 
     // Prevent a color definition from persisting between calls to katex.render().
     parser.gullet.macros.current.remove("\\current@color");
     parser.gullet.macros.current.remove("\\color");
 
+    let tree = tree?;
+
+    // \nonumber and \notag set \@eqnsw to 0, which leaves the tree untagged.
+    let numbered = parser.gullet.expand_macro_as_text("\\@eqnsw")?.as_deref() != Some("0");
+
     // If the input used \tag, it will set the \df@tag macro to the tag.
     // In this case, we separately parse the tag and wrap the tree.
-    if parser.gullet.macros.contains_key("\\df@tag") {
+    if numbered && parser.gullet.macros.has("\\df@tag") {
         if !settings.display_mode {
             return Err(ParseError::new(
-                "\\tag works only in display equations".into(),
+                "\\tag works only in display equations",
                 None,
             ));
         }
@@ -34,3 +99,37 @@ pub fn parse_tree(to_parse: &str, settings: &Settings) -> Result<Vec<AnyParseNod
         Ok(tree)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn internal_macros_are_not_kept() {
+        let settings = Settings {
+            display_mode: true,
+            global_group: true,
+            ..Default::default()
+        };
+        let mut macros = MacroMap::new();
+        let input = "\\gdef\\df@tag{1}\\gdef\\@eqnsw{0}\\gdef\\foo{x}";
+        parse_tree_with_macros(input, &settings, &mut macros).unwrap();
+        assert!(!macros.contains_key("\\df@tag"));
+        assert!(!macros.contains_key("\\@eqnsw"));
+        assert!(macros.contains_key("\\foo"));
+    }
+
+    #[test]
+    fn color_is_cleaned_up_after_errors() {
+        let settings = Settings {
+            global_group: true,
+            ..Default::default()
+        };
+        let mut macros = MacroMap::new();
+        let input = "\\gdef\\current@color{red}\\gdef\\color{x}\\gdef\\foo{x}\\undefined";
+        assert!(parse_tree_with_macros(input, &settings, &mut macros).is_err());
+        assert!(!macros.contains_key("\\current@color"));
+        assert!(!macros.contains_key("\\color"));
+        assert!(macros.contains_key("\\foo"));
+    }
+}
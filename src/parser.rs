@@ -39,9 +39,15 @@ pub struct Parser<'a> {
 
 impl<'a> Parser<'a> {
     pub fn new(input: &'a str, settings: &'a Settings) -> Parser<'a> {
+        Self::with_macros(input, settings, settings.macros.clone())
+    }
+
+    /// Like `new`, but starts from the given global-level macros instead of
+    /// a copy of `settings.macros`.
+    pub fn with_macros(input: &'a str, settings: &'a Settings, macros: MacroMap) -> Parser<'a> {
         Self {
             mode: Mode::Math,
-            gullet: MacroExpander::new(input, settings, Mode::Math),
+            gullet: MacroExpander::new(input, settings, Mode::Math, macros),
             settings,
             leftright_depth: 0,
             next_token: None,
@@ -49,41 +55,39 @@ impl<'a> Parser<'a> {
     }
     /// Checks a result to make sure it has the right type, and throws an
     /// appropriate error otherwise.
-    fn expect(&mut self, text: &str, consume: bool) -> Result<(), ParseError> {
-        if let Some(next_token) = self.fetch() {
-            if next_token.text != text {
-                return Err(ParseError::new(
-                    &format!("Expected '{}', got '{}'", text, next_token.text),
-                    Some(next_token),
-                ));
-            }
-            if consume {
-                self.consume();
-            }
-            Ok(())
-        } else {
-            Err(ParseError::new("Unexpected end of input", None))
+    pub fn expect(&mut self, text: &str, consume: bool) -> Result<(), ParseError> {
+        let next_token = self.fetch()?;
+        if next_token.text != text {
+            return Err(ParseError::new(
+                &format!("Expected '{}', got '{}'", text, next_token.text),
+                Some(next_token),
+            ));
         }
+        if consume {
+            self.consume();
+        }
+        Ok(())
     }
 
     /// Discards the current lookahead token, considering it consumed.
-    fn consume(&mut self) {
+    pub fn consume(&mut self) {
         self.next_token = None;
     }
 
     /// Return the current lookahead token, or if there isn't one (at the
     /// beginning, or if the previous lookahead token was consume()d),
     /// fetch the next token as the new lookahead token and return it.
-    fn fetch(&mut self) -> Option<&Token> {
+    pub fn fetch(&mut self) -> Result<&Token, ParseError> {
         if self.next_token.is_none() {
-            self.next_token = self.gullet.expand_next_token();
+            self.next_token = Some(self.gullet.expand_next_token()?);
         }
-        self.next_token.as_ref()
+        Ok(self.next_token.as_ref().unwrap())
     }
 
     /// Switches between "text" and "math" modes.
-    fn switch_mode(&mut self, new_mode: Mode) {
+    pub fn switch_mode(&mut self, new_mode: Mode) {
         self.mode = new_mode;
+        self.gullet.switch_mode(new_mode);
     }
 
     /// Main parsing function, which parses an entire input.
@@ -98,7 +102,9 @@ impl<'a> Parser<'a> {
         // We do this within the group for the math expression, so it doesn't
         // pollute settings.macros.
         if self.settings.color_is_text_color {
-            self.gullet.macros.insert("\\color", "\\textcolor");
+            self.gullet
+                .macros
+                .set("\\color", Some("\\textcolor".into()), false);
         }
 
         // Try to parse the input
@@ -115,9 +121,9 @@ impl<'a> Parser<'a> {
         }
 
         // Close any leftover groups in case of a parse error.
-        self.gullet.endGroups();
+        self.gullet.end_groups();
 
-        Ok(parse?)
+        parse
     }
 
     /// Fully parse a separate sequence of tokens as a separate job.
@@ -129,7 +135,7 @@ impl<'a> Parser<'a> {
 
         // Run the new job, terminating it with an excess '}'
         self.gullet.push_token(Token::new("}".into(), None));
-        self.gullet.push_tokens(tokens);
+        self.gullet.push_tokens(tokens.to_vec());
         let parse = self.parse_expression(false, None)?;
         self.expect("}", true)?;
 
@@ -148,7 +154,7 @@ impl<'a> Parser<'a> {
     /// `break_on_token_text`: The text of the token that the expression should end
     ///                        with, or `None` if something else should end the
     ///                        expression.
-    fn parse_expression(
+    pub fn parse_expression(
         &mut self,
         break_on_infix: bool,
         break_on_token_text: Option<&str>,
@@ -160,14 +166,12 @@ impl<'a> Parser<'a> {
         loop {
             // Ignore spaces in math mode
             if let Mode::Math = self.mode {
-                self.consume_spaces();
+                self.consume_spaces()?;
             }
 
-            let lex = self
-                .fetch()
-                .ok_or(ParseError::new("Unexpected end of input", None))?;
+            let lex = self.fetch()?;
 
-            if END_OF_EXPRESSION.contains(&lex.text) {
+            if END_OF_EXPRESSION.contains(&lex.text.as_str()) {
                 break;
             }
 
@@ -177,19 +181,20 @@ impl<'a> Parser<'a> {
                 }
             }
 
-            // if break_on_infix && functions.get(lex.text).map_or(false, |func| func.infix) {
-            //     break;
-            // } // TODO: functions.
+            if break_on_infix
+                && functions::FUNCTIONS
+                    .get(lex.text.as_str())
+                    .is_some_and(|func| func.infix)
+            {
+                break;
+            }
 
             let atom = self.parse_atom(break_on_token_text)?;
 
-            if let Some(atom) = atom {
-                if atom.type_ == "internal" {
-                    continue;
-                }
-                body.push(atom);
-            } else {
-                break;
+            match atom {
+                None => break,
+                Some(AnyParseNode::Internal(_)) => continue,
+                Some(atom) => body.push(atom),
             }
         }
 
@@ -197,7 +202,7 @@ impl<'a> Parser<'a> {
             self.form_ligatures(&mut body);
         }
 
-        Ok(self.handle_infix_nodes(body)?)
+        self.handle_infix_nodes(body)
     }
 
     /// Rewrites infix operators such as \over with corresponding commands such
@@ -205,67 +210,814 @@ impl<'a> Parser<'a> {
     ///
     /// There can only be one infix operator per group. If there's more than one
     /// then the expression is ambiguous. This can be resolved by adding {}.
-    fn handle_infix_nodes(&self, body: Vec<AnyParseNode>) -> Result<Vec<AnyParseNode>, ParseError> {
-        let mut over_index = -1;
-        let mut func_name = "";
+    fn handle_infix_nodes(
+        &mut self,
+        mut body: Vec<AnyParseNode>,
+    ) -> Result<Vec<AnyParseNode>, ParseError> {
+        let mut over_index = None;
 
         for (i, node) in body.iter().enumerate() {
             if let AnyParseNode::Infix(infix_node) = node {
-                if over_index != -1 {
+                if over_index.is_some() {
                     return Err(ParseError::new(
                         "only one infix operator per group",
-                        infix_node.token,
+                        infix_node.token.as_ref(),
                     ));
                 }
-                over_index = i;
-                func_name = infix_node.replace_with.as_str();
+                over_index = Some(i);
             }
         }
 
-        if over_index != -1 && !func_name.is_empty() {
-            let numer_node;
-            let denom_node;
+        let Some(over_index) = over_index else {
+            return Ok(body);
+        };
+
+        let denom_body = body.split_off(over_index + 1);
+        let Some(AnyParseNode::Infix(infix_node)) = body.pop() else {
+            unreachable!();
+        };
+        let numer_body = body;
+        let func_name = infix_node.replace_with.clone();
+
+        let numer_node = self.infix_operand(numer_body);
+        let denom_node = self.infix_operand(denom_body);
+
+        let node = if func_name == "\\\\abovefrac" {
+            self.call_function(
+                &func_name,
+                vec![
+                    numer_node,
+                    AnyParseNode::Infix(infix_node),
+                    denom_node,
+                ],
+                vec![],
+                None,
+                None,
+            )?
+        } else {
+            self.call_function(&func_name, vec![numer_node, denom_node], vec![], None, None)?
+        };
+        Ok(vec![node])
+    }
 
-            let numer_body = body[..over_index].to_vec();
-            let denom_body = body[over_index + 1..].to_vec();
+    /// Wrap one side of an infix operator in an ordgroup, unless it already
+    /// is a single one.
+    fn infix_operand(&self, mut body: Vec<AnyParseNode>) -> AnyParseNode {
+        if body.len() == 1 && matches!(body[0], AnyParseNode::OrdGroup(_)) {
+            body.remove(0)
+        } else {
+            AnyParseNode::OrdGroup(OrdGroupParseNode {
+                type_: "ordgroup".into(),
+                mode: self.mode.to_string(),
+                loc: None,
+                body,
+                semisimple: None,
+            })
+        }
+    }
 
-            if numer_body.len() == 1 && numer_body[0].type_ == "ordgroup" {
-                numer_node = numer_body[0].clone();
-            } else {
-                numer_node = AnyParseNode::OrdGroup(OrdGroupNode {
-                    mode: self.mode,
-                    body: numer_body,
-                });
+    /// Handle a subscript or superscript with nice errors.
+    fn handle_sup_subscript(&mut self, name: &str) -> Result<AnyParseNode, ParseError> {
+        let symbol_token = self.fetch()?.clone();
+        let symbol = symbol_token.text.clone();
+        self.consume();
+        self.consume_spaces()?; // ignore spaces before sup/subscript argument
+        loop {
+            match self.parse_group(name, None)? {
+                Some(AnyParseNode::Internal(_)) => continue,
+                Some(group) => return Ok(group),
+                None => {
+                    return Err(ParseError::new(
+                        &format!("Expected group after '{}'", symbol),
+                        Some(&symbol_token),
+                    ))
+                }
             }
+        }
+    }
 
-            if denom_body.len() == 1 && denom_body[0].type_ == "ordgroup" {
-                denom_node = denom_body[0].clone();
-            } else {
-                denom_node = AnyParseNode::OrdGroup(OrdGroupNode {
-                    mode: self.mode,
-                    body: denom_body,
+    /// Converts the textual input of an unsupported command into a text node
+    /// contained within a color node whose color is determined by errorColor
+    pub fn format_unsupported_cmd(&self, text: &str) -> AnyParseNode {
+        let textord_array = text
+            .chars()
+            .map(|ch| {
+                AnyParseNode::TextOrd(TextOrdParseNode {
+                    type_: "textord".into(),
+                    mode: "text".into(),
+                    loc: None,
+                    text: ch.to_string(),
+                })
+            })
+            .collect();
+
+        let text_node = AnyParseNode::Text(TextParseNode {
+            type_: "text".into(),
+            mode: self.mode.to_string(),
+            loc: None,
+            body: textord_array,
+            font: None,
+        });
+
+        AnyParseNode::Color(ColorParseNode {
+            type_: "color".into(),
+            mode: self.mode.to_string(),
+            loc: None,
+            color: self.settings.error_color.clone(),
+            body: vec![text_node],
+        })
+    }
+
+    /// Parses a group with optional super/subscripts.
+    fn parse_atom(
+        &mut self,
+        break_on_token_text: Option<&str>,
+    ) -> Result<Option<AnyParseNode>, ParseError> {
+        // The body of an atom is an implicit group, so that things like
+        // \left(x\right)^2 work correctly.
+        let mut base = self.parse_group("atom", break_on_token_text)?;
+
+        // Internal nodes (e.g. \relax) cannot support super/subscripts.
+        // Instead we will pick up super/subscripts with blank base next round.
+        if let Some(AnyParseNode::Internal(_)) = base {
+            return Ok(base);
+        }
+
+        // In text mode, we don't have superscripts or subscripts
+        if self.mode == Mode::Text {
+            return Ok(base);
+        }
+
+        // Note that base may be empty (i.e. None) at this point.
+
+        let mut superscript = None;
+        let mut subscript = None;
+        loop {
+            // Guaranteed in math mode, so eat any spaces first.
+            self.consume_spaces()?;
+
+            // Lex the first token
+            let lex = self.fetch()?.clone();
+
+            if lex.text == "\\limits" || lex.text == "\\nolimits" {
+                // We got a limit control
+                let limits = lex.text == "\\limits";
+                match &mut base {
+                    Some(AnyParseNode::Op(op)) => {
+                        op.limits = limits;
+                        op.always_handle_sup_sub = Some(true);
+                    }
+                    Some(AnyParseNode::Operatorname(operatorname)) => {
+                        if operatorname.always_handle_sup_sub {
+                            operatorname.limits = limits;
+                        }
+                    }
+                    _ => {
+                        return Err(ParseError::new(
+                            "Limit controls must follow a math operator",
+                            Some(&lex),
+                        ))
+                    }
+                }
+                self.consume();
+            } else if lex.text == "^" {
+                // We got a superscript start
+                if superscript.is_some() {
+                    return Err(ParseError::new("Double superscript", Some(&lex)));
+                }
+                superscript = Some(self.handle_sup_subscript("superscript")?);
+            } else if lex.text == "_" {
+                // We got a subscript start
+                if subscript.is_some() {
+                    return Err(ParseError::new("Double subscript", Some(&lex)));
+                }
+                subscript = Some(self.handle_sup_subscript("subscript")?);
+            } else if lex.text == "'" {
+                // We got a prime
+                if superscript.is_some() {
+                    return Err(ParseError::new("Double superscript", Some(&lex)));
+                }
+                let prime = AnyParseNode::TextOrd(TextOrdParseNode {
+                    type_: "textord".into(),
+                    mode: self.mode.to_string(),
+                    loc: None,
+                    text: "\\prime".into(),
                 });
+
+                // Many primes can be strung together, and they're all
+                // counted here
+                let mut primes = vec![prime.clone()];
+                self.consume();
+                // Keep lexing tokens until we get something that's not a prime
+                while self.fetch()?.text == "'" {
+                    // For each one, add another prime to the list
+                    primes.push(prime.clone());
+                    self.consume();
+                }
+                // If there's a superscript following the primes, combine that
+                // superscript in with the primes.
+                if self.fetch()?.text == "^" {
+                    primes.push(self.handle_sup_subscript("superscript")?);
+                }
+                // Put everything into an ordgroup as the superscript
+                superscript = Some(AnyParseNode::OrdGroup(OrdGroupParseNode {
+                    type_: "ordgroup".into(),
+                    mode: self.mode.to_string(),
+                    loc: None,
+                    body: primes,
+                    semisimple: None,
+                }));
+            } else {
+                // If it wasn't ^, _, or ', stop parsing super/subscripts
+                break;
+            }
+        }
+
+        // Base must be set if superscript or subscript are set per logic above,
+        // but need to check here for type check to pass.
+        if superscript.is_some() || subscript.is_some() {
+            // If we got either a superscript or subscript, create a supsub
+            Ok(Some(AnyParseNode::SupSub(SupSubParseNode {
+                type_: "supsub".into(),
+                mode: self.mode.to_string(),
+                loc: None,
+                base: base.map(Box::new),
+                sup: superscript.map(Box::new),
+                sub: subscript.map(Box::new),
+            })))
+        } else {
+            // Otherwise return the original body
+            Ok(base)
+        }
+    }
+
+    /// Parses an entire function, including its base and all of its arguments.
+    pub fn parse_function(
+        &mut self,
+        break_on_token_text: Option<&str>,
+        name: Option<&str>, // For determining its context
+    ) -> Result<Option<AnyParseNode>, ParseError> {
+        let token = self.fetch()?.clone();
+        let func = token.text.clone();
+        let Some(func_data) = functions::FUNCTIONS.get(func.as_str()) else {
+            return Ok(None);
+        };
+        self.consume(); // consume command token
+
+        if name.is_some_and(|name| name != "atom") && !func_data.allowed_in_argument {
+            return Err(ParseError::new(
+                &format!(
+                    "Got function '{}' with no arguments as {}",
+                    func,
+                    name.unwrap()
+                ),
+                Some(&token),
+            ));
+        } else if self.mode == Mode::Text && !func_data.allowed_in_text {
+            return Err(ParseError::new(
+                &format!("Can't use function '{}' in text mode", func),
+                Some(&token),
+            ));
+        } else if self.mode == Mode::Math && !func_data.allowed_in_math {
+            return Err(ParseError::new(
+                &format!("Can't use function '{}' in math mode", func),
+                Some(&token),
+            ));
+        }
+
+        let (args, opt_args) = self.parse_arguments(&func, func_data)?;
+        self.call_function(&func, args, opt_args, Some(token), break_on_token_text)
+            .map(Some)
+    }
+
+    /// Call a function handler with a suitable context and arguments.
+    pub fn call_function(
+        &mut self,
+        name: &str,
+        args: Vec<AnyParseNode>,
+        opt_args: Vec<Option<AnyParseNode>>,
+        token: Option<Token>,
+        break_on_token_text: Option<&str>,
+    ) -> Result<AnyParseNode, ParseError> {
+        let context = FunctionContext {
+            func_name: name.to_string(),
+            parser: self,
+            token,
+            break_on_token_text: break_on_token_text.map(str::to_string),
+        };
+        match functions::FUNCTIONS.get(name).and_then(|func| func.handler) {
+            Some(handler) => handler(context, args, opt_args),
+            None => Err(ParseError::new(
+                &format!("No function handler for {}", name),
+                None,
+            )),
+        }
+    }
+
+    /// Parses the arguments of a function or environment
+    pub fn parse_arguments(
+        &mut self,
+        func: &str, // Should look like "\\name" or "\\begin{name}".
+        func_data: &FunctionSpec,
+    ) -> Result<(Vec<AnyParseNode>, Vec<Option<AnyParseNode>>), ParseError> {
+        let total_args = func_data.num_args + func_data.num_optional_args;
+        let mut args = Vec::new();
+        let mut opt_args: Vec<Option<AnyParseNode>> = Vec::new();
+
+        for i in 0..total_args {
+            let mut arg_type = func_data
+                .arg_types
+                .as_ref()
+                .and_then(|arg_types| arg_types.get(i).copied());
+            let is_optional = i < func_data.num_optional_args;
+
+            if (func_data.primitive && arg_type.is_none())
+                // \sqrt expands into primitive if optional argument doesn't exist
+                || (func_data.node_type == "sqrt" && i == 1 && opt_args[0].is_none())
+            {
+                arg_type = Some(ArgType::Primitive);
             }
 
-            let node = if func_name == "\\\\abovefrac" {
-                self.call_function(
-                    func_name,
-                    vec![numer_node, body[over_index].clone(), denom_node],
-                    vec![],
-                )?
+            let arg =
+                self.parse_group_of_type(&format!("argument to '{}'", func), arg_type, is_optional)?;
+            if is_optional {
+                opt_args.push(arg);
+            } else if let Some(arg) = arg {
+                args.push(arg);
             } else {
-                self.call_function(func_name, vec![numer_node, denom_node], vec![])?
-            };
+                // should be unreachable
+                return Err(ParseError::new(
+                    "Null argument, please report this as bug",
+                    None,
+                ));
+            }
+        }
+
+        Ok((args, opt_args))
+    }
 
-            Ok(vec![node])
+    /// Parses a group when the mode is changing.
+    fn parse_group_of_type(
+        &mut self,
+        name: &str,
+        arg_type: Option<ArgType>,
+        optional: bool,
+    ) -> Result<Option<AnyParseNode>, ParseError> {
+        match arg_type {
+            Some(ArgType::Mode(mode)) => self.parse_argument_group(optional, Some(mode)),
+            Some(ArgType::Hbox) => {
+                // hbox argument type wraps the argument in the equivalent of
+                // \hbox, which is like \text but switching to \textstyle size.
+                let group = self.parse_argument_group(optional, Some(Mode::Text))?;
+                Ok(group.map(|group| {
+                    AnyParseNode::Styling(StylingParseNode {
+                        type_: "styling".into(),
+                        mode: group.mode().to_string(),
+                        loc: None,
+                        style: "text".into(), // simulate \textstyle
+                        body: vec![group],
+                    })
+                }))
+            }
+            Some(ArgType::Raw) => {
+                let token = self.parse_string_group("raw", optional)?;
+                Ok(token.map(|token| {
+                    AnyParseNode::Raw(RawParseNode {
+                        type_: "raw".into(),
+                        mode: "text".into(),
+                        loc: None,
+                        string: token.text,
+                    })
+                }))
+            }
+            Some(ArgType::Primitive) => {
+                if optional {
+                    return Err(ParseError::new(
+                        "A primitive argument cannot be optional",
+                        None,
+                    ));
+                }
+                match self.parse_group(name, None)? {
+                    Some(group) => Ok(Some(group)),
+                    None => {
+                        let token = self.fetch()?.clone();
+                        Err(ParseError::new(
+                            &format!("Expected group as {}", name),
+                            Some(&token),
+                        ))
+                    }
+                }
+            }
+            Some(ArgType::Color) => self.parse_color_group(optional),
+            Some(ArgType::Size) => self.parse_size_group(optional),
+            Some(ArgType::Url) => self.parse_url_group(optional),
+            Some(ArgType::Original) | None => self.parse_argument_group(optional, None),
+        }
+    }
+
+    /// Discard any space tokens, fetching the next non-space token.
+    pub fn consume_spaces(&mut self) -> Result<(), ParseError> {
+        while self.fetch()?.text == " " {
+            self.consume();
+        }
+        Ok(())
+    }
+
+    /// Parses a group, essentially returning the string formed by the
+    /// brace-enclosed tokens plus some position information.
+    fn parse_string_group(
+        &mut self,
+        _mode_name: &str, // Used to describe the mode in error messages.
+        optional: bool,
+    ) -> Result<Option<Token>, ParseError> {
+        let Some(mut arg_token) = self.gullet.scan_argument(optional)? else {
+            return Ok(None);
+        };
+        let mut string = String::new();
+        loop {
+            let next_token = self.fetch()?;
+            if next_token.text == "EOF" {
+                break;
+            }
+            string += &next_token.text;
+            self.consume();
+        }
+        self.consume(); // consume the end of the argument
+        arg_token.text = string;
+        Ok(Some(arg_token))
+    }
+
+    /// Parses a regex-delimited group: the largest sequence of tokens
+    /// whose concatenated strings match `regex`. Returns the string
+    /// formed by the tokens plus some position information.
+    fn parse_regex_group(
+        &mut self,
+        regex: &regex::Regex,
+        mode_name: &str, // Used to describe the mode in error messages.
+    ) -> Result<Token, ParseError> {
+        let first_token = self.fetch()?.clone();
+        let mut last_token = first_token.clone();
+        let mut string = String::new();
+        loop {
+            let next_token = self.fetch()?;
+            if next_token.text == "EOF" || !regex.is_match(&(string.clone() + &next_token.text)) {
+                break;
+            }
+            last_token = next_token.clone();
+            string += &last_token.text;
+            self.consume();
+        }
+        if string.is_empty() {
+            return Err(ParseError::new(
+                &format!("Invalid {}: '{}'", mode_name, first_token.text),
+                Some(&first_token),
+            ));
+        }
+        Ok(first_token.range(&last_token, string))
+    }
+
+    /// Parses a color description.
+    fn parse_color_group(&mut self, optional: bool) -> Result<Option<AnyParseNode>, ParseError> {
+        let Some(res) = self.parse_string_group("color", optional)? else {
+            return Ok(None);
+        };
+        // A color is a hex code or one of the names CSS knows.
+        if !regex!(r"(?i)^(#[a-f0-9]{3}|#?[a-f0-9]{6})$").is_match(&res.text)
+            && !is_named_color(&res.text)
+        {
+            return Err(ParseError::new(
+                &format!("Invalid color: '{}'", res.text),
+                Some(&res),
+            ));
+        }
+        let mut color = res.text.clone();
+        if regex!(r"(?i)^[0-9a-f]{6}$").is_match(&color) {
+            // We allow a 6-digit HTML color spec without a leading "#".
+            // This follows the xcolor package's HTML color model.
+            // Predefined color names are all missed by this RegEx pattern.
+            color = format!("#{}", color);
+        }
+        Ok(Some(AnyParseNode::ColorToken(ColorTokenParseNode {
+            type_: "color-token".into(),
+            mode: self.mode.to_string(),
+            loc: None,
+            color,
+        })))
+    }
+
+    /// Parses an URL, checking escaped letters and allowed protocols,
+    /// and setting the catcodes of % as an active character (as in \href).
+    fn parse_url_group(&mut self, optional: bool) -> Result<Option<AnyParseNode>, ParseError> {
+        self.gullet.lexer.catcodes.insert('%', 13); // active character
+        self.gullet.lexer.catcodes.insert('~', 12); // other character
+        let res = self.parse_string_group("url", optional);
+        self.gullet.lexer.catcodes.insert('%', 14); // comment character
+        self.gullet.lexer.catcodes.insert('~', 13); // active character
+        let Some(res) = res? else {
+            return Ok(None);
+        };
+        // hyperref package allows backslashes alone in href, but doesn't
+        // generate valid links in such cases; we interpret this as
+        // "undefined" behaviour, and keep them as-is. Some browser will
+        // replace backslashes with forward slashes.
+        let url = regex!(r"\\([#$%&~_^{}])").replace_all(&res.text, "$1");
+        Ok(Some(AnyParseNode::Url(UrlParseNode {
+            type_: "url".into(),
+            mode: self.mode.to_string(),
+            loc: None,
+            url: url.into_owned(),
+        })))
+    }
+
+    /// Parses a size specification, consisting of magnitude and unit.
+    fn parse_size_group(&mut self, optional: bool) -> Result<Option<AnyParseNode>, ParseError> {
+        let mut is_blank = false;
+        // don't expand before parse_string_group
+        self.gullet.consume_spaces()?;
+        let res = if !optional && self.gullet.future()?.text != "{" {
+            Some(self.parse_regex_group(
+                regex!(r"^[-+]? *(?:$|\d+|\d+\.\d*|\.\d*) *[a-z]{0,2} *$"),
+                "size",
+            )?)
         } else {
-            Ok(body)
+            self.parse_string_group("size", optional)?
+        };
+        let Some(mut res) = res else {
+            return Ok(None);
+        };
+        if !optional && res.text.is_empty() {
+            // Because we've tested for what is !optional, this block won't
+            // affect \kern, \hspace, etc. It will capture the mandatory arguments
+            // to \genfrac and \above.
+            res.text = "0pt".into(); // Enable \above{}
+            is_blank = true; // This is here specifically for \genfrac
+        }
+        let Some(captures) = regex!(r"([-+]?) *(\d+(?:\.\d*)?|\.\d+) *([a-z]{2})").captures(&res.text)
+        else {
+            return Err(ParseError::new(
+                &format!("Invalid size: '{}'", res.text),
+                Some(&res),
+            ));
+        };
+        let data = Measurement {
+            // sign + magnitude, cast to number
+            number: format!("{}{}", &captures[1], &captures[2])
+                .parse()
+                .unwrap_or(0.0),
+            unit: captures[3].to_string(),
+        };
+        if !valid_unit(&data.unit) {
+            return Err(ParseError::new(
+                &format!("Invalid unit: '{}'", data.unit),
+                Some(&res),
+            ));
+        }
+        Ok(Some(AnyParseNode::Size(SizeParseNode {
+            type_: "size".into(),
+            mode: self.mode.to_string(),
+            loc: None,
+            value: data,
+            is_blank,
+        })))
+    }
+
+    /// Parses an argument with the mode specified.
+    fn parse_argument_group(
+        &mut self,
+        optional: bool,
+        mode: Option<Mode>,
+    ) -> Result<Option<AnyParseNode>, ParseError> {
+        let Some(arg_token) = self.gullet.scan_argument(optional)? else {
+            return Ok(None);
+        };
+        let outer_mode = self.mode;
+        if let Some(mode) = mode {
+            // Switch to specified mode
+            self.switch_mode(mode);
+        }
+
+        self.gullet.begin_group();
+        let expression = self.parse_expression(false, Some("EOF"))?;
+        // TODO: find an alternative way to denote the end
+        self.expect("EOF", true)?; // expect the end of the argument
+        self.gullet.end_group();
+        let result = AnyParseNode::OrdGroup(OrdGroupParseNode {
+            type_: "ordgroup".into(),
+            mode: self.mode.to_string(),
+            loc: arg_token.loc,
+            body: expression,
+            semisimple: None,
+        });
+
+        if mode.is_some() {
+            // Switch mode back
+            self.switch_mode(outer_mode);
+        }
+        Ok(Some(result))
+    }
+
+    /// Parses an ordinary group, which is either a single nucleus (like "x")
+    /// or an expression in braces (like "{x+y}") or an implicit group, a group
+    /// that starts at the current position, and ends right before a higher
+    /// explicit group ends, like \color or \textcolor.
+    fn parse_group(
+        &mut self,
+        name: &str, // For error reporting.
+        break_on_token_text: Option<&str>,
+    ) -> Result<Option<AnyParseNode>, ParseError> {
+        let first_token = self.fetch()?.clone();
+        let text = first_token.text.clone();
+
+        // Try to parse an open brace or \begingroup
+        if text == "{" || text == "\\begingroup" {
+            self.consume();
+            let group_end = if text == "{" { "}" } else { "\\endgroup" };
+
+            self.gullet.begin_group();
+            // If we get a brace, parse an expression
+            let expression = self.parse_expression(false, Some(group_end))?;
+            let last_token = self.fetch()?.clone();
+            self.expect(group_end, true)?; // Check that we got a matching closing brace
+            self.gullet.end_group();
+            return Ok(Some(AnyParseNode::OrdGroup(OrdGroupParseNode {
+                type_: "ordgroup".into(),
+                mode: self.mode.to_string(),
+                loc: SourceLocation::range(first_token.loc.as_ref(), last_token.loc.as_ref()),
+                body: expression,
+                // A group formed by \begingroup...\endgroup is a semi-simple group
+                // which doesn't affect spacing in math mode, i.e., is transparent.
+                // https://tex.stackexchange.com/questions/1930/when-should-one-
+                // use-begingroup-instead-of-bgroup
+                semisimple: (text == "\\begingroup").then_some(true),
+            })));
+        }
+
+        // If there exists a function with this name, parse the function.
+        // Otherwise, just return a nucleus
+        let mut result = self.parse_function(break_on_token_text, Some(name))?;
+        if result.is_none() {
+            result = self.parse_symbol()?;
+        }
+        if result.is_none() && text.starts_with('\\') && !IMPLICIT_COMMANDS.contains(&text.as_str())
+        {
+            if self.settings.throw_on_error {
+                return Err(ParseError::new(
+                    &format!("Undefined control sequence: {}", text),
+                    Some(&first_token),
+                ));
+            }
+            result = Some(self.format_unsupported_cmd(&text));
+            self.consume();
+        }
+        Ok(result)
+    }
+
+    /// Form ligature-like combinations of characters for text mode.
+    /// This includes inputs like "--", "---", "``" and "''".
+    /// The result will simply replace multiple textord nodes with a single
+    /// character in each value by a single textord node having multiple
+    /// characters in its value.  The representation is still ASCII source.
+    /// The group will be modified in place.
+    fn form_ligatures(&self, group: &mut Vec<AnyParseNode>) {
+        let text_of = |node: Option<&AnyParseNode>| match node {
+            Some(AnyParseNode::TextOrd(node)) => Some(node.text.clone()),
+            _ => None,
+        };
+        let ligature = |first: &AnyParseNode, last: &AnyParseNode, text: &str| {
+            AnyParseNode::TextOrd(TextOrdParseNode {
+                type_: "textord".into(),
+                mode: "text".into(),
+                loc: SourceLocation::range(first.loc(), last.loc()),
+                text: text.into(),
+            })
+        };
+        let mut i = 0;
+        while i + 1 < group.len() {
+            let v = text_of(group.get(i));
+            let next = text_of(group.get(i + 1));
+            if v.as_deref() == Some("-") && next.as_deref() == Some("-") {
+                if text_of(group.get(i + 2)).as_deref() == Some("-") {
+                    let node = ligature(&group[i], &group[i + 2], "---");
+                    group.splice(i..i + 3, [node]);
+                } else {
+                    let node = ligature(&group[i], &group[i + 1], "--");
+                    group.splice(i..i + 2, [node]);
+                }
+            } else if matches!(v.as_deref(), Some("'") | Some("`")) && next == v {
+                let text = v.unwrap().repeat(2);
+                let node = ligature(&group[i], &group[i + 1], &text);
+                group.splice(i..i + 2, [node]);
+            }
+            i += 1;
         }
     }
+
+    /// Parse a single symbol out of the string. Here, we handle single character
+    /// symbols and special functions like \verb.
+    fn parse_symbol(&mut self) -> Result<Option<AnyParseNode>, ParseError> {
+        let nucleus = self.fetch()?.clone();
+        let text = nucleus.text.clone();
+
+        // Recognize base symbol
+        let loc = SourceLocation::range(nucleus.loc.as_ref(), None);
+        let mode = self.mode.to_string();
+        let symbol = if let Some(info) = symbols::SYMBOLS.get(self.mode).get(text.as_str()) {
+            match info.group {
+                family if family.is_atom() => AnyParseNode::Atom(AtomParseNode {
+                    type_: "atom".into(),
+                    mode,
+                    loc,
+                    family,
+                    text,
+                }),
+                Group::AccentToken => AnyParseNode::AccentToken(AccentTokenParseNode {
+                    type_: "accent-token".into(),
+                    mode,
+                    loc,
+                    text,
+                }),
+                Group::MathOrd => AnyParseNode::MathOrd(MathOrdParseNode {
+                    type_: "mathord".into(),
+                    mode,
+                    loc,
+                    text,
+                }),
+                Group::OpToken => AnyParseNode::OpToken(OpTokenParseNode {
+                    type_: "op-token".into(),
+                    mode,
+                    loc,
+                    text,
+                }),
+                Group::Spacing => AnyParseNode::Spacing(SpacingParseNode {
+                    type_: "spacing".into(),
+                    mode,
+                    loc,
+                    text,
+                }),
+                _ => AnyParseNode::TextOrd(TextOrdParseNode {
+                    type_: "textord".into(),
+                    mode,
+                    loc,
+                    text,
+                }),
+            }
+        } else if text.chars().next().is_some_and(|ch| ch as u32 >= 0x80) {
+            // no symbol for e.g. ^
+            // All nonmathematical Unicode characters are rendered as if they
+            // are in text mode (wrapped in \text) because that's what it
+            // takes to render them in LaTeX.
+            AnyParseNode::TextOrd(TextOrdParseNode {
+                type_: "textord".into(),
+                mode: "text".into(),
+                loc,
+                text,
+            })
+        } else {
+            return Ok(None); // EOF, ^, _, {, }, etc.
+        };
+        self.consume();
+        Ok(Some(symbol))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Mode {
     Math,
     Text,
 }
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mode::Math => write!(f, "math"),
+            Mode::Text => write!(f, "text"),
+        }
+    }
+}
+
+impl From<&str> for Mode {
+    /// The mode of a parse node, as stored in its `mode` field.
+    fn from(mode: &str) -> Self {
+        match mode {
+            "text" => Mode::Text,
+            _ => Mode::Math,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switch_mode_switches_the_gullet_too() {
+        let settings = Settings::default();
+        let mut parser = Parser::new("\\TextOrMath{a}{b}", &settings);
+        parser.switch_mode(Mode::Text);
+        let tree = parser.parse().unwrap();
+        let [AnyParseNode::TextOrd(node)] = &tree[..] else {
+            panic!("expected a single textord");
+        };
+        assert_eq!(node.text, "a");
+    }
+}
@@ -0,0 +1,279 @@
+//! Loading macro definitions from a LaTeX preamble, such as a shared `.sty`
+//! or `.tex` file of `\newcommand`s, into a `MacroMap` for `Settings.macros`.
+use std::{fs, io, path::Path};
+
+use super::*;
+
+/// Commands that only make sense to LaTeX itself. Statements starting with
+/// them are skipped with a warning.
+const UNSUPPORTED_COMMANDS: &[&str] = &[
+    "\\usepackage",
+    "\\RequirePackage",
+    "\\documentclass",
+    "\\NeedsTeXFormat",
+    "\\ProvidesPackage",
+    "\\ProvidesFile",
+];
+
+/// Commands skipped silently, because KaTeX always behaves as they ask.
+const IGNORED_COMMANDS: &[&str] = &["\\makeatletter", "\\makeatother"];
+
+/// A problem found while loading a preamble.
+#[derive(Debug)]
+pub struct PreambleDiagnostic {
+    /// The 1-based line number in the preamble source.
+    pub line: usize,
+    pub message: String,
+}
+
+/// The macros defined by a preamble, and what went wrong while loading it.
+#[derive(Debug, Default)]
+pub struct Preamble {
+    pub macros: MacroMap,
+    /// Skipped statements, such as `\usepackage` lines.
+    pub warnings: Vec<PreambleDiagnostic>,
+    /// Statements that failed to parse. Their definitions are not in
+    /// `macros`, but the statements after them are still loaded.
+    pub errors: Vec<PreambleDiagnostic>,
+}
+
+/// Read and load the preamble at `path`. See `parse_preamble`.
+pub fn load_preamble(path: impl AsRef<Path>, settings: &Settings) -> io::Result<Preamble> {
+    Ok(parse_preamble(&fs::read_to_string(path)?, settings))
+}
+
+/// Load the macros defined in the preamble `source`.
+///
+/// The source is split into statements of whole lines with balanced braces,
+/// with comments removed. A statement that runs out of input while reading
+/// its arguments, like `\newcommand{\foo}` with its body on the next line,
+/// is kept open until the following lines complete it. Each statement is parsed in the global group, so
+/// top-level `\newcommand`, `\def`, `\let`, `\DeclareMathOperator`, etc. are
+/// kept for the statements after it. The other `settings`, such as `strict`
+/// and `max_expand`, apply as given.
+pub fn parse_preamble(source: &str, settings: &Settings) -> Preamble {
+    let settings = Settings {
+        global_group: true,
+        ..settings.clone()
+    };
+    let mut preamble = Preamble::default();
+    let mut statement = String::new();
+    let mut start_line = 1;
+    let mut depth = 0;
+    let lines: Vec<&str> = source.lines().collect();
+    for (index, line) in lines.iter().enumerate() {
+        let line = strip_comment(line);
+        if statement.trim().is_empty() {
+            statement.clear();
+            start_line = index + 1;
+        } else {
+            statement.push('\n');
+        }
+        statement += line;
+        depth += brace_depth(line);
+        let last = index + 1 == lines.len();
+        if depth <= 0 && load_statement(&mut preamble, &statement, start_line, &settings, last) {
+            statement.clear();
+            depth = 0;
+        }
+    }
+    // An unbalanced statement at the end reports its missing '}'.
+    load_statement(&mut preamble, &statement, start_line, &settings, true);
+    preamble
+}
+
+/// Load one statement, returning false if it needs more input and `last` is
+/// false. The statement is then loaded again with the next line added.
+fn load_statement(
+    preamble: &mut Preamble,
+    statement: &str,
+    line: usize,
+    settings: &Settings,
+    last: bool,
+) -> bool {
+    let statement = statement.trim_end();
+    let trimmed = statement.trim_start();
+    if trimmed.is_empty() {
+        return true;
+    }
+    let leading = &statement[..statement.len() - trimmed.len()];
+    let line = line + leading.matches('\n').count();
+    let command = regex!(r"^\\(?:[a-zA-Z@]+|.)")
+        .find(trimmed)
+        .map_or("", |command| command.as_str());
+    if IGNORED_COMMANDS.contains(&command) {
+        return true;
+    }
+    if UNSUPPORTED_COMMANDS.contains(&command) {
+        preamble.warnings.push(PreambleDiagnostic {
+            line,
+            message: format!("Ignoring unsupported {}", command),
+        });
+        return true;
+    }
+
+    // A failed statement may have defined some macros before its error;
+    // drop those along with the rest of the statement.
+    let macros = preamble.macros.clone();
+    if let Err(error) = parse_tree_with_macros(trimmed, settings, &mut preamble.macros) {
+        preamble.macros = macros;
+        if !last && error.position().is_some_and(|position| position >= trimmed.len()) {
+            return false;
+        }
+        let error_line = error
+            .position()
+            .and_then(|position| trimmed.get(..position))
+            .unwrap_or(trimmed)
+            .matches('\n')
+            .count();
+        preamble.errors.push(PreambleDiagnostic {
+            line: line + error_line,
+            message: error.to_string(),
+        });
+    }
+    true
+}
+
+/// Remove a `%` comment from `line`, unless the `%` is escaped as `\%`.
+fn strip_comment(line: &str) -> &str {
+    let mut escaped = false;
+    for (index, ch) in line.char_indices() {
+        match ch {
+            '%' if !escaped => return &line[..index],
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    line
+}
+
+/// The change in brace nesting over `line`, ignoring `\{` and `\}`.
+fn brace_depth(line: &str) -> isize {
+    let mut depth = 0;
+    let mut escaped = false;
+    for ch in line.chars() {
+        match ch {
+            '{' if !escaped => depth += 1,
+            '}' if !escaped => depth -= 1,
+            _ => {}
+        }
+        escaped = ch == '\\' && !escaped;
+    }
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn definitions_carry_over_between_statements() {
+        let preamble = parse_preamble(
+            "% shared macros\n\
+             \\newcommand{\\R}{\\mathbb{R}}\n\
+             \\newcommand{\\pair}[2]{\n\
+               (#1, #2) % a pair\n\
+             }\n\
+             \\def\\RR{\\R\\R}\n",
+            &Settings::default(),
+        );
+        assert!(preamble.errors.is_empty(), "{:?}", preamble.errors);
+        for name in ["\\R", "\\pair", "\\RR"] {
+            assert!(preamble.macros.contains_key(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn declare_math_operator_defines_the_command() {
+        let preamble = parse_preamble(
+            "\\DeclareMathOperator{\\tr}{tr}\n\\DeclareMathOperator*{\\argmax}{arg\\,max}",
+            &Settings::default(),
+        );
+        assert!(preamble.errors.is_empty(), "{:?}", preamble.errors);
+        assert!(preamble.macros.contains_key("\\tr"));
+        assert!(preamble.macros.contains_key("\\argmax"));
+        assert!(!preamble.macros.contains_key("\\DeclareMathOperator"));
+    }
+
+    #[test]
+    fn latex_only_commands_are_skipped() {
+        let preamble = parse_preamble(
+            "\\makeatletter\n\\usepackage{amsmath}\n\\def\\a@b{x}\n\\makeatother",
+            &Settings::default(),
+        );
+        assert!(preamble.errors.is_empty(), "{:?}", preamble.errors);
+        assert_eq!(preamble.warnings.len(), 1);
+        assert_eq!(preamble.warnings[0].line, 2);
+        assert!(preamble.macros.contains_key("\\a@b"));
+    }
+
+    #[test]
+    fn errors_report_their_line_and_loading_continues() {
+        let preamble = parse_preamble(
+            "\\def\\a{a}\n\n\\def\\b{\n  b}\\undefinedcs\n\\def\\c{c}",
+            &Settings::default(),
+        );
+        assert_eq!(preamble.errors.len(), 1, "{:?}", preamble.errors);
+        assert_eq!(preamble.errors[0].line, 4);
+        assert!(preamble.macros.contains_key("\\a"));
+        assert!(preamble.macros.contains_key("\\c"));
+    }
+
+    #[test]
+    fn failed_statements_leave_no_definitions() {
+        let preamble = parse_preamble(
+            "\\def\\a{a}\\def\\b{b}\\undefinedcs",
+            &Settings::default(),
+        );
+        assert_eq!(preamble.errors.len(), 1, "{:?}", preamble.errors);
+        assert!(preamble.macros.is_empty());
+    }
+
+    #[test]
+    fn errors_inside_macro_bodies_do_not_panic() {
+        let preamble = parse_preamble(
+            "\\def\\x{\u{e9}\u{e9}\u{e9}\u{e9}\\undefinedcs}\n\\x",
+            &Settings::default(),
+        );
+        assert_eq!(preamble.errors.len(), 1, "{:?}", preamble.errors);
+        assert_eq!(preamble.errors[0].line, 2);
+    }
+
+    #[test]
+    fn settings_apply_to_every_statement() {
+        let settings = Settings {
+            max_expand: 0,
+            ..Default::default()
+        };
+        let preamble = parse_preamble("\\def\\a{a}\n\\def\\b{\\a}\\b", &settings);
+        assert_eq!(preamble.errors.len(), 1, "{:?}", preamble.errors);
+        assert_eq!(preamble.errors[0].line, 2);
+        assert!(preamble.macros.contains_key("\\a"));
+    }
+
+    #[test]
+    fn arguments_may_continue_on_the_next_lines() {
+        let preamble = parse_preamble(
+            "\\newcommand{\\foo}\n{bar}\n\\def\\b\n{\\foo}\n\\DeclareMathOperator{\\tr}\n\n{tr}",
+            &Settings::default(),
+        );
+        assert!(preamble.errors.is_empty(), "{:?}", preamble.errors);
+        for name in ["\\foo", "\\b", "\\tr"] {
+            assert!(preamble.macros.contains_key(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn a_missing_argument_is_reported_at_the_end() {
+        let preamble = parse_preamble("\\def\\a{a}\n\\newcommand{\\foo}", &Settings::default());
+        assert_eq!(preamble.errors.len(), 1, "{:?}", preamble.errors);
+        assert_eq!(preamble.errors[0].line, 2);
+        assert!(preamble.macros.contains_key("\\a"));
+    }
+
+    #[test]
+    fn escaped_percent_signs_are_kept() {
+        assert_eq!(strip_comment("\\def\\p{50\\%} % percent"), "\\def\\p{50\\%} ");
+        assert_eq!(brace_depth("\\{ { \\}"), 1);
+    }
+}
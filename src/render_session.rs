@@ -0,0 +1,142 @@
+//! Rendering a sequence of expressions that share macros, like the formulas
+//! of one LaTeX document.
+use super::*;
+
+/// Renders expressions one after another with `global_group` semantics, so
+/// macros defined at the top level of one expression (with \def,
+/// \newcommand, etc.) are available to the following expressions.
+pub struct RenderSession {
+    settings: Settings,
+    /// The global macros defined so far, including the initial
+    /// `settings.macros`.
+    macros: MacroMap,
+    /// The expansion steps of the last expression, if tracing is on.
+    trace: Option<Vec<ExpansionStep>>,
+}
+
+impl RenderSession {
+    /// Start a session with `settings`, whose `macros` become the initial
+    /// global macros. `settings.global_group` is always turned on.
+    pub fn new(mut settings: Settings) -> Self {
+        settings.global_group = true;
+        let macros = std::mem::take(&mut settings.macros);
+        RenderSession {
+            settings,
+            macros,
+            trace: None,
+        }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// The global macros defined so far.
+    pub fn macros(&self) -> &MacroMap {
+        &self.macros
+    }
+
+    /// Turn recording of macro expansion steps on or off for the following
+    /// expressions. See `parse_tree_with_trace`.
+    pub fn set_tracing(&mut self, tracing: bool) {
+        self.trace = tracing.then(Vec::new);
+    }
+
+    /// The macro expansion steps of the last expression, recorded if tracing
+    /// was on. The steps are kept even if the expression failed to parse.
+    pub fn trace(&self) -> &[ExpansionStep] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// End the session, returning the global macros defined so far.
+    pub fn into_macros(self) -> MacroMap {
+        self.macros
+    }
+
+    /// Parse the next expression of the session, keeping its global macros.
+    pub fn parse(&mut self, expression: &str) -> Result<Vec<AnyParseNode>, ParseError> {
+        parse_tree_with_macros_and_trace(
+            expression,
+            &self.settings,
+            &mut self.macros,
+            self.trace.as_mut(),
+        )
+    }
+
+    /// Parse and build the next expression of the session, and return the
+    /// markup for that.
+    pub fn render_to_string(&mut self, expression: &str) -> String {
+        self.render_to_dom_tree(expression).to_markup()
+    }
+
+    /// Generates and returns the KaTeX build tree of the next expression of
+    /// the session.
+    pub fn render_to_dom_tree(&mut self, expression: &str) -> DomSpan {
+        match self.parse(expression) {
+            Ok(tree) => build_tree(tree, expression, &self.settings),
+            Err(error) => render_error(error, expression, &self.settings),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_level_definitions_persist() {
+        let mut session = RenderSession::new(Settings::default());
+        session.render_to_string("\\def\\foo{x}");
+        session.render_to_string("\\newcommand{\\baz}[1]{#1y}");
+        let markup = session.render_to_string("\\foo\\baz{z}");
+        assert!(!markup.contains("katex-error"));
+        assert!(markup.contains(">x<") && markup.contains(">z<") && markup.contains(">y<"));
+        assert!(session.macros().contains_key("\\foo"));
+    }
+
+    #[test]
+    fn local_definitions_do_not_persist() {
+        let mut session = RenderSession::new(Settings::default());
+        session.render_to_string("{\\def\\foo{x}}{\\gdef\\qux{y}}");
+        assert!(!session.macros().contains_key("\\foo"));
+        assert!(session.macros().contains_key("\\qux"));
+        assert!(session.parse("\\foo").is_err());
+    }
+
+    #[test]
+    fn definitions_do_not_leak_out_of_parse_tree() {
+        let settings = Settings::default();
+        parse_tree("\\def\\foo{x}", &settings).unwrap();
+        assert!(parse_tree("\\foo", &settings).is_err());
+    }
+
+    #[test]
+    fn newcommand_checks_existence() {
+        let mut session = RenderSession::new(Settings::default());
+        session.parse("\\newcommand{\\foo}{x}").unwrap();
+        assert!(session.parse("\\newcommand{\\foo}{y}").is_err());
+        assert!(session.parse("\\renewcommand{\\baz}{y}").is_err());
+        session.parse("\\providecommand{\\foo}{y}").unwrap();
+        let tree = session.parse("\\foo").unwrap();
+        let [AnyParseNode::MathOrd(node)] = &tree[..] else {
+            panic!("expected a single mathord");
+        };
+        assert_eq!(node.text, "x");
+    }
+
+    #[test]
+    fn tracing_records_the_last_expression() {
+        let mut session = RenderSession::new(Settings::default());
+        session.parse("\\def\\foo{x}").unwrap();
+        assert!(session.trace().is_empty());
+        session.set_tracing(true);
+        session.parse("\\foo").unwrap();
+        assert_eq!(session.trace().len(), 1);
+        assert_eq!(session.trace()[0].name, "\\foo");
+        assert!(session.parse("\\foo\\foo\\undefinedcs").is_err());
+        assert_eq!(session.trace().len(), 2);
+        session.set_tracing(false);
+        session.parse("\\foo").unwrap();
+        assert!(session.trace().is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 //! default settings.
 use super::*;
 
+#[derive(Clone)]
 pub struct Settings {
     /// Render math in display mode, which puts the math in display style
     /// (so \int and \sum are large, for example), and centers the math on
@@ -32,8 +33,10 @@ pub struct Settings {
     /// Turn on strict / LaTeX faithfulness mode, which throws an error if
     /// the input uses features that are not supported by LaTeX.
     pub strict: bool,
-    /// Trust the input, enabling all HTML features such as \url.
-    pub trust: bool,
+    /// Trust the input, enabling all HTML features such as \url. Either a
+    /// fixed answer or a function deciding for each command. Off by default,
+    /// since untrusted input could otherwise inject links or styles.
+    pub trust: TrustPolicy,
     /// If non-zero, all user-specified sizes, e.g. in \rule{500em}{500em},
     /// will be capped to maxSize ems. Otherwise, elements and spaces can be
     /// arbitrarily large.
@@ -42,6 +45,9 @@ pub struct Settings {
     /// prevent e.g. infinite macro loops. If set to Infinity, the macro
     /// expander will try to fully expand as in LaTeX.
     pub max_expand: usize,
+    /// Place the expression in the global group instead of its own group,
+    /// so top-level definitions like \def persist in `macros`, as in
+    /// `RenderSession`.
     pub global_group: bool,
 }
 
@@ -58,7 +64,7 @@ impl Default for Settings {
             min_rule_thickness: 0.0,
             color_is_text_color: false,
             strict: false,
-            trust: true,
+            trust: TrustPolicy::Bool(false),
             max_size: f64::INFINITY,
             max_expand: 1000,
             global_group: false,
@@ -66,8 +72,73 @@ impl Default for Settings {
     }
 }
 
-pub type MacroMap = HashMap<String, String>;
+impl Settings {
+    /// Report nonstrict (non-LaTeX-compatible) input. In strict mode this is
+    /// an error; otherwise the input is accepted as is.
+    pub fn report_nonstrict(
+        &self,
+        error_code: &str,
+        error_msg: &str,
+        token: Option<&Token>,
+    ) -> Result<(), ParseError> {
+        if self.strict {
+            return Err(ParseError::new(
+                &format!(
+                    "LaTeX-incompatible input and strict mode is set to 'error': {} [{}]",
+                    error_msg, error_code
+                ),
+                token,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check whether to trust the given command. A command that loads a URL
+    /// whose protocol is invalid is never trusted.
+    pub fn is_trusted(&self, context: &mut TrustContext) -> bool {
+        if let (Some(url), None) = (context.url, &context.protocol) {
+            let Some(protocol) = utils::protocol_from_url(url) else {
+                return false;
+            };
+            context.protocol = Some(protocol);
+        }
+        match &self.trust {
+            TrustPolicy::Bool(trust) => *trust,
+            TrustPolicy::Fn(trust) => trust(context),
+        }
+    }
+}
+
+/// How `Settings::is_trusted` decides whether to trust a command.
+#[derive(Clone)]
+pub enum TrustPolicy {
+    /// Trust every command, or none of them.
+    Bool(bool),
+    /// Ask the function, given everything the command asks to be trusted with.
+    Fn(Arc<dyn Fn(&TrustContext) -> bool + Send + Sync>),
+}
+
+impl From<bool> for TrustPolicy {
+    fn from(trust: bool) -> Self {
+        TrustPolicy::Bool(trust)
+    }
+}
+
+/// What a command asks to be trusted with, passed to `Settings::is_trusted`.
+#[derive(Default)]
+pub struct TrustContext<'a> {
+    pub command: &'a str,
+    pub url: Option<&'a str>,
+    pub protocol: Option<String>,
+    pub class: Option<&'a str>,
+    pub id: Option<&'a str>,
+    pub style: Option<&'a str>,
+    pub attributes: Option<&'a HashMap<String, String>>,
+}
+
+pub type MacroMap = HashMap<String, MacroDefinition>;
 
+#[derive(Clone)]
 pub enum OutputFormat {
     HtmlAndMathml,
     Html,
@@ -27,11 +27,11 @@ impl SourceLocation {
         second: Option<&SourceLocation>,
     ) -> Option<SourceLocation> {
         match (first, second) {
-            (Some(first_loc), None) => Some(*first_loc),
+            (Some(first_loc), None) => Some(first_loc.clone()),
             (Some(first_loc), Some(second_loc)) => {
-                if first_loc.lexer as *const _ == second_loc.lexer as *const _ {
+                if first_loc.same_lexer(second_loc) {
                     Some(SourceLocation::new(
-                        first_loc.lexer,
+                        first_loc.lexer.clone(),
                         first_loc.start,
                         second_loc.end,
                     ))
@@ -42,4 +42,23 @@ impl SourceLocation {
             _ => None,
         }
     }
+
+    fn same_lexer(&self, other: &SourceLocation) -> bool {
+        std::ptr::addr_eq(Arc::as_ptr(&self.lexer), Arc::as_ptr(&other.lexer))
+    }
+}
+
+impl fmt::Debug for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceLocation")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+
+impl PartialEq for SourceLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_lexer(other) && self.start == other.start && self.end == other.end
+    }
 }
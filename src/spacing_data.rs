@@ -0,0 +1,83 @@
+//! Describes spaces between different classes of atoms.
+use super::*;
+
+fn mu(number: f64) -> Measurement {
+    Measurement {
+        number,
+        unit: "mu".to_string(),
+    }
+}
+
+fn thinspace() -> Measurement {
+    mu(3.0)
+}
+
+fn mediumspace() -> Measurement {
+    mu(4.0)
+}
+
+fn thickspace() -> Measurement {
+    mu(5.0)
+}
+
+/// Spacing relationships for display and text styles
+pub fn spacings(left: &str, right: &str) -> Option<Measurement> {
+    let space = match (left, right) {
+        ("mord", "mop") => thinspace(),
+        ("mord", "mbin") => mediumspace(),
+        ("mord", "mrel") => thickspace(),
+        ("mord", "minner") => thinspace(),
+
+        ("mop", "mord") => thinspace(),
+        ("mop", "mop") => thinspace(),
+        ("mop", "mrel") => thickspace(),
+        ("mop", "minner") => thinspace(),
+
+        ("mbin", "mord") => mediumspace(),
+        ("mbin", "mop") => mediumspace(),
+        ("mbin", "mopen") => mediumspace(),
+        ("mbin", "minner") => mediumspace(),
+
+        ("mrel", "mord") => thickspace(),
+        ("mrel", "mop") => thickspace(),
+        ("mrel", "mopen") => thickspace(),
+        ("mrel", "minner") => thickspace(),
+
+        ("mclose", "mop") => thinspace(),
+        ("mclose", "mbin") => mediumspace(),
+        ("mclose", "mrel") => thickspace(),
+        ("mclose", "minner") => thinspace(),
+
+        ("mpunct", "mord") => thinspace(),
+        ("mpunct", "mop") => thinspace(),
+        ("mpunct", "mrel") => thickspace(),
+        ("mpunct", "mopen") => thinspace(),
+        ("mpunct", "mclose") => thinspace(),
+        ("mpunct", "mpunct") => thinspace(),
+        ("mpunct", "minner") => thinspace(),
+
+        ("minner", "mord") => thinspace(),
+        ("minner", "mop") => thinspace(),
+        ("minner", "mbin") => mediumspace(),
+        ("minner", "mrel") => thickspace(),
+        ("minner", "mopen") => thinspace(),
+        ("minner", "mpunct") => thinspace(),
+        ("minner", "minner") => thinspace(),
+
+        _ => return None,
+    };
+    Some(space)
+}
+
+/// Spacing relationships for script and scriptscript styles
+pub fn tight_spacings(left: &str, right: &str) -> Option<Measurement> {
+    let space = match (left, right) {
+        ("mord", "mop") => thinspace(),
+        ("mop", "mord") => thinspace(),
+        ("mop", "mop") => thinspace(),
+        ("mclose", "mop") => thinspace(),
+        ("minner", "mop") => thinspace(),
+        _ => return None,
+    };
+    Some(space)
+}
@@ -0,0 +1,495 @@
+//! This file provides support to build_html.rs and build_mathml.rs for
+//! stretchy wide elements rendered from SVG files and other CSS trickery.
+use super::*;
+
+fn stretchy_code_point(label: &str) -> &'static str {
+    match label {
+        "widehat" => "^",
+        "widecheck" => "ˇ",
+        "widetilde" => "~",
+        "utilde" => "~",
+        "overleftarrow" => "\u{2190}",
+        "underleftarrow" => "\u{2190}",
+        "xleftarrow" => "\u{2190}",
+        "overrightarrow" => "\u{2192}",
+        "underrightarrow" => "\u{2192}",
+        "xrightarrow" => "\u{2192}",
+        "underbrace" => "\u{23df}",
+        "overbrace" => "\u{23de}",
+        "overgroup" => "\u{23e0}",
+        "undergroup" => "\u{23e1}",
+        "overbracket" => "\u{23b4}",
+        "underbracket" => "\u{23b5}",
+        "overleftrightarrow" => "\u{2194}",
+        "underleftrightarrow" => "\u{2194}",
+        "xleftrightarrow" => "\u{2194}",
+        "Overrightarrow" => "\u{21d2}",
+        "xRightarrow" => "\u{21d2}",
+        "overleftharpoon" => "\u{21bc}",
+        "xleftharpoonup" => "\u{21bc}",
+        "overrightharpoon" => "\u{21c0}",
+        "xrightharpoonup" => "\u{21c0}",
+        "xLeftarrow" => "\u{21d0}",
+        "xLeftrightarrow" => "\u{21d4}",
+        "xhookleftarrow" => "\u{21a9}",
+        "xhookrightarrow" => "\u{21aa}",
+        "xmapsto" => "\u{21a6}",
+        "xrightharpoondown" => "\u{21c1}",
+        "xleftharpoondown" => "\u{21bd}",
+        "xrightleftharpoons" => "\u{21cc}",
+        "xleftrightharpoons" => "\u{21cb}",
+        "xtwoheadleftarrow" => "\u{219e}",
+        "xtwoheadrightarrow" => "\u{21a0}",
+        "xlongequal" => "=",
+        "xtofrom" => "\u{21c4}",
+        "xrightleftarrows" => "\u{21c4}",
+        "xrightequilibrium" => "\u{21cc}", // Not a perfect match.
+        "xleftequilibrium" => "\u{21cb}",  // None better available.
+        "\\cdrightarrow" => "\u{2192}",
+        "\\cdleftarrow" => "\u{2190}",
+        "\\cdlongequal" => "=",
+        _ => "",
+    }
+}
+
+pub fn math_ml_node(label: &str) -> MathNode {
+    let mut node = MathNode::new(
+        MathNodeType::Mo,
+        vec![TextNode::new(stretchy_code_point(
+            label.strip_prefix('\\').unwrap_or(label),
+        ))
+        .into()],
+        vec![],
+    );
+    node.set_attribute("stretchy", "true");
+    node
+}
+
+// Many of the KaTeX SVG images have been adapted from glyphs in KaTeX fonts.
+// Copyright (c) 2009-2010, Design Science, Inc. (<www.mathjax.org>)
+// Copyright (c) 2014-2017 Khan Academy (<www.khanacademy.org>)
+// Licensed under the SIL Open Font License, Version 1.1.
+// See \nhttp://scripts.sil.org/OFL
+
+// Very Long SVGs
+//    Many of the KaTeX stretchy wide elements use a long SVG image and an
+//    overflow: hidden tactic to achieve a stretchy image while avoiding
+//    distortion of arrowheads or brace corners.
+
+//    The SVG typically contains a very long (400 em) arrow.
+
+//    The SVG is in a container span that has overflow: hidden, so the span
+//    acts like a window that exposes only part of the  SVG.
+
+//    The SVG always has a longer, thinner aspect ratio than the container span.
+//    After the SVG fills 100% of the height of the container span,
+//    there is a long arrow shaft left over. That left-over shaft is not shown.
+//    Instead, it is sliced off because the span's CSS has overflow: hidden.
+
+//    Thus, the reader sees an arrow that matches the subject matter width
+//    without distortion.
+
+//    Some functions, such as \cancel, need to vary their aspect ratio. These
+//    functions do not get the overflow SVG treatment.
+
+// Second Brush Stroke
+//    Low resolution monitors struggle to display images in fine detail.
+//    So browsers apply anti-aliasing. A long straight arrow shaft therefore
+//    will sometimes appear as if it has a blurred edge.
+
+//    To mitigate this, these SVG files contain a second "brush-stroke" on the
+//    arrow shafts. That is, a second long thin rectangular SVG path has been
+//    written directly on top of each arrow shaft. This reinforcement causes
+//    some of the screen pixels to display as black instead of the anti-aliased
+//    gray pixel that a  single path would generate. So we get arrow shafts
+//    whose edges appear to be sharper.
+
+// In katex_images_data just below, the dimensions all
+// correspond to path geometry inside the relevant SVG.
+// For example, \overrightarrow uses the same arrowhead as glyph U+2192
+// from the KaTeX Main font. The scaling factor is 1000.
+// That is, inside the font, that arrowhead is 522 units tall, which
+// corresponds to 0.522 em inside the document.
+
+/// The paths, minimum width, view box height and, for single paths, the
+/// alignment of a stretchy SVG image.
+type ImageData = (&'static [&'static str], f64, f64, Option<&'static str>);
+
+fn katex_images_data(label: &str) -> Option<ImageData> {
+    //   path(s), min_width, height, align
+    let data: ImageData = match label {
+        "overrightarrow" => (&["rightarrow"], 0.888, 522.0, Some("xMaxYMin")),
+        "overleftarrow" => (&["leftarrow"], 0.888, 522.0, Some("xMinYMin")),
+        "underrightarrow" => (&["rightarrow"], 0.888, 522.0, Some("xMaxYMin")),
+        "underleftarrow" => (&["leftarrow"], 0.888, 522.0, Some("xMinYMin")),
+        "xrightarrow" => (&["rightarrow"], 1.469, 522.0, Some("xMaxYMin")),
+        "\\cdrightarrow" => (&["rightarrow"], 3.0, 522.0, Some("xMaxYMin")), // CD minwwidth2.5pc
+        "xleftarrow" => (&["leftarrow"], 1.469, 522.0, Some("xMinYMin")),
+        "\\cdleftarrow" => (&["leftarrow"], 3.0, 522.0, Some("xMinYMin")),
+        "Overrightarrow" => (&["doublerightarrow"], 0.888, 560.0, Some("xMaxYMin")),
+        "xRightarrow" => (&["doublerightarrow"], 1.526, 560.0, Some("xMaxYMin")),
+        "xLeftarrow" => (&["doubleleftarrow"], 1.526, 560.0, Some("xMinYMin")),
+        "overleftharpoon" => (&["leftharpoon"], 0.888, 522.0, Some("xMinYMin")),
+        "xleftharpoonup" => (&["leftharpoon"], 0.888, 522.0, Some("xMinYMin")),
+        "xleftharpoondown" => (&["leftharpoondown"], 0.888, 522.0, Some("xMinYMin")),
+        "overrightharpoon" => (&["rightharpoon"], 0.888, 522.0, Some("xMaxYMin")),
+        "xrightharpoonup" => (&["rightharpoon"], 0.888, 522.0, Some("xMaxYMin")),
+        "xrightharpoondown" => (&["rightharpoondown"], 0.888, 522.0, Some("xMaxYMin")),
+        "xlongequal" => (&["longequal"], 0.888, 334.0, Some("xMinYMin")),
+        "\\cdlongequal" => (&["longequal"], 3.0, 334.0, Some("xMinYMin")),
+        "xtwoheadleftarrow" => (&["twoheadleftarrow"], 0.888, 334.0, Some("xMinYMin")),
+        "xtwoheadrightarrow" => (&["twoheadrightarrow"], 0.888, 334.0, Some("xMaxYMin")),
+
+        "overleftrightarrow" => (&["leftarrow", "rightarrow"], 0.888, 522.0, None),
+        "overbrace" => (&["leftbrace", "midbrace", "rightbrace"], 1.6, 548.0, None),
+        "underbrace" => (
+            &["leftbraceunder", "midbraceunder", "rightbraceunder"],
+            1.6,
+            548.0,
+            None,
+        ),
+        "underleftrightarrow" => (&["leftarrow", "rightarrow"], 0.888, 522.0, None),
+        "xleftrightarrow" => (&["leftarrow", "rightarrow"], 1.75, 522.0, None),
+        "xLeftrightarrow" => (&["doubleleftarrow", "doublerightarrow"], 1.75, 560.0, None),
+        "xrightleftharpoons" => (
+            &["leftharpoondownplus", "rightharpoonplus"],
+            1.75,
+            716.0,
+            None,
+        ),
+        "xleftrightharpoons" => (
+            &["leftharpoonplus", "rightharpoondownplus"],
+            1.75,
+            716.0,
+            None,
+        ),
+        "xhookleftarrow" => (&["leftarrow", "righthook"], 1.08, 522.0, None),
+        "xhookrightarrow" => (&["lefthook", "rightarrow"], 1.08, 522.0, None),
+        "overlinesegment" => (&["leftlinesegment", "rightlinesegment"], 0.888, 522.0, None),
+        "underlinesegment" => (&["leftlinesegment", "rightlinesegment"], 0.888, 522.0, None),
+        "overbracket" => (&["leftbracket", "rightbracket"], 0.888, 342.0, None),
+        "underbracket" => (
+            &["leftbracketunder", "rightbracketunder"],
+            0.888,
+            342.0,
+            None,
+        ),
+        "overgroup" => (&["leftgroup", "rightgroup"], 0.888, 342.0, None),
+        "undergroup" => (&["leftgroupunder", "rightgroupunder"], 0.888, 342.0, None),
+        "xmapsto" => (&["leftmapsto", "rightarrow"], 1.5, 522.0, None),
+        "xtofrom" => (&["leftToFrom", "rightToFrom"], 1.75, 528.0, None),
+
+        // The next three arrows are from the mhchem package.
+        // In mhchem.sty, min-length is 2.0em. But these arrows might appear in the
+        // document as \xrightarrow or \xrightleftharpoons. Those have
+        // min-length = 1.75em, so we set min-length on these next three to match.
+        "xrightleftarrows" => (
+            &["baraboveleftarrow", "rightarrowabovebar"],
+            1.75,
+            901.0,
+            None,
+        ),
+        "xrightequilibrium" => (
+            &["baraboveshortleftharpoon", "rightharpoonaboveshortbar"],
+            1.75,
+            716.0,
+            None,
+        ),
+        "xleftequilibrium" => (
+            &["shortbaraboveleftharpoon", "shortrightharpoonabovebar"],
+            1.75,
+            716.0,
+            None,
+        ),
+        _ => return None,
+    };
+    Some(data)
+}
+
+fn group_length(arg: &AnyParseNode) -> usize {
+    match arg {
+        AnyParseNode::OrdGroup(arg) => arg.body.len(),
+        _ => 1,
+    }
+}
+
+/// Create a span with inline SVG for the element.
+fn build_svg_span(group: &AnyParseNode, options: &Options) -> (DomSpan, f64, f64) {
+    let mut view_box_width = 400000.0; // default
+    let (label, base) = match group {
+        AnyParseNode::Accent(group) => (&group.label, Some(&*group.base)),
+        AnyParseNode::AccentUnder(group) => (&group.label, Some(&*group.base)),
+        AnyParseNode::XArrow(group) => (&group.label, None),
+        AnyParseNode::HorizBrace(group) => (&group.label, None),
+        group => panic!("Got group of type '{}' for a stretchy SVG", group.type_()),
+    };
+    let label = &label[1..];
+
+    if let ("widehat" | "widecheck" | "widetilde" | "utilde", Some(base)) = (label, base) {
+        // There are four SVG images available for each function.
+        // Choose a taller image when there are more characters.
+        let num_chars = group_length(base);
+        let view_box_height;
+        let path_name;
+        let height;
+
+        if num_chars > 5 {
+            if label == "widehat" || label == "widecheck" {
+                view_box_height = 420.0;
+                view_box_width = 2364.0;
+                height = 0.42;
+                path_name = format!("{}4", label);
+            } else {
+                view_box_height = 312.0;
+                view_box_width = 2340.0;
+                height = 0.34;
+                path_name = "tilde4".to_string();
+            }
+        } else {
+            let img_index = [1, 1, 2, 2, 3, 3][num_chars];
+            if label == "widehat" || label == "widecheck" {
+                view_box_width = [0.0, 1062.0, 2364.0, 2364.0, 2364.0][img_index];
+                view_box_height = [0.0, 239.0, 300.0, 360.0, 420.0][img_index];
+                height = [0.0, 0.24, 0.3, 0.3, 0.36, 0.42][img_index];
+                path_name = format!("{}{}", label, img_index);
+            } else {
+                view_box_width = [0.0, 600.0, 1033.0, 2339.0, 2340.0][img_index];
+                view_box_height = [0.0, 260.0, 286.0, 306.0, 312.0][img_index];
+                height = [0.0, 0.26, 0.286, 0.3, 0.306, 0.34][img_index];
+                path_name = format!("tilde{}", img_index);
+            }
+        }
+        let path = PathNode::new(&path_name, None);
+        let svg_node = SvgNode::new(
+            vec![SvgChildNode::SvgPathNode(path)],
+            HashMap::from([
+                ("width".to_string(), "100%".to_string()),
+                ("height".to_string(), make_em(height)),
+                (
+                    "viewBox".to_string(),
+                    format!("0 0 {} {}", view_box_width, view_box_height),
+                ),
+                ("preserveAspectRatio".to_string(), "none".to_string()),
+            ]),
+        );
+        (
+            make_svg_span(vec![], vec![svg_node.into()], Some(options), None),
+            0.0,
+            height,
+        )
+    } else {
+        let mut spans = vec![];
+
+        let (paths, min_width, view_box_height, align1) = katex_images_data(label)
+            .unwrap_or_else(|| panic!("No stretchy SVG image for '\\{}'", label));
+        let height = view_box_height / 1000.0;
+
+        let num_svg_children = paths.len();
+        let (width_classes, aligns): (&[&str], Vec<&str>) = match num_svg_children {
+            1 => (&["hide-tail"], vec![align1.unwrap()]),
+            2 => (
+                &["halfarrow-left", "halfarrow-right"],
+                vec!["xMinYMin", "xMaxYMin"],
+            ),
+            3 => (
+                &["brace-left", "brace-center", "brace-right"],
+                vec!["xMinYMin", "xMidYMin", "xMaxYMin"],
+            ),
+            _ => panic!(
+                "Correct katex_images_data or update code here to support {} children.",
+                num_svg_children
+            ),
+        };
+
+        for i in 0..num_svg_children {
+            let path = PathNode::new(paths[i], None);
+
+            let svg_node = SvgNode::new(
+                vec![SvgChildNode::SvgPathNode(path)],
+                HashMap::from([
+                    ("width".to_string(), "400em".to_string()),
+                    ("height".to_string(), make_em(height)),
+                    (
+                        "viewBox".to_string(),
+                        format!("0 0 {} {}", view_box_width, view_box_height),
+                    ),
+                    (
+                        "preserveAspectRatio".to_string(),
+                        format!("{} slice", aligns[i]),
+                    ),
+                ]),
+            );
+
+            let mut span = make_svg_span(
+                vec![width_classes[i].to_string()],
+                vec![svg_node.into()],
+                Some(options),
+                None,
+            );
+            if num_svg_children == 1 {
+                return (span, min_width, height);
+            } else {
+                span.style.insert("height".to_string(), make_em(height));
+                spans.push(span.into());
+            }
+        }
+
+        (
+            make_span(vec!["stretchy".to_string()], spans, Some(options), None),
+            min_width,
+            height,
+        )
+    }
+}
+
+/// Builds the stretchy SVG image of an accent, under-accent, extensible
+/// arrow or horizontal brace.
+pub fn svg_span(group: &AnyParseNode, options: &Options) -> DomSpan {
+    let (mut span, min_width, height) = build_svg_span(group, options);
+
+    // Note that we are returning span.depth = 0.
+    // Any adjustments relative to the baseline must be done in build_html.
+    span.height = height;
+    span.style.insert("height".to_string(), make_em(height));
+    if min_width > 0.0 {
+        span.style
+            .insert("minWidth".to_string(), make_em(min_width));
+    }
+
+    span
+}
+
+/// Return an image span for \cancel, \bcancel, \xcancel, \fbox, or \angl
+pub fn enclose_span(
+    inner: &VirtualNode,
+    label: &str,
+    top_pad: f64,
+    bottom_pad: f64,
+    options: &Options,
+) -> DomSpan {
+    let total_height = inner.height() + inner.depth() + top_pad + bottom_pad;
+
+    let mut img = if label.contains("fbox") || label.contains("color") || label.contains("angl") {
+        let mut img = make_span(
+            vec!["stretchy".to_string(), label.to_string()],
+            vec![],
+            Some(options),
+            None,
+        );
+
+        if label == "fbox" {
+            if let (Some(_), Some(color)) = (&options.color, options.get_color()) {
+                img.style.insert("borderColor".to_string(), color);
+            }
+        }
+
+        img
+    } else {
+        // \cancel, \bcancel, or \xcancel
+        // Since \cancel's SVG is inline and it omits the viewBox attribute,
+        // its stroke-width will not vary with span area.
+
+        let mut lines = vec![];
+        if label == "bcancel" || label == "xcancel" {
+            lines.push(SvgChildNode::SvgLineNode(LineNode::new(HashMap::from([
+                ("x1".to_string(), "0".to_string()),
+                ("y1".to_string(), "0".to_string()),
+                ("x2".to_string(), "100%".to_string()),
+                ("y2".to_string(), "100%".to_string()),
+                ("stroke-width".to_string(), "0.046em".to_string()),
+            ]))));
+        }
+
+        if label == "cancel" || label == "xcancel" {
+            lines.push(SvgChildNode::SvgLineNode(LineNode::new(HashMap::from([
+                ("x1".to_string(), "0".to_string()),
+                ("y1".to_string(), "100%".to_string()),
+                ("x2".to_string(), "100%".to_string()),
+                ("y2".to_string(), "0".to_string()),
+                ("stroke-width".to_string(), "0.046em".to_string()),
+            ]))));
+        }
+
+        let svg_node = SvgNode::new(
+            lines,
+            HashMap::from([
+                ("width".to_string(), "100%".to_string()),
+                ("height".to_string(), make_em(total_height)),
+            ]),
+        );
+
+        make_svg_span(vec![], vec![svg_node.into()], Some(options), None)
+    };
+
+    img.height = total_height;
+    img.style
+        .insert("height".to_string(), make_em(total_height));
+
+    img
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_image_has_path_data() {
+        let labels = [
+            "overrightarrow",
+            "overleftarrow",
+            "underrightarrow",
+            "underleftarrow",
+            "xrightarrow",
+            "\\cdrightarrow",
+            "xleftarrow",
+            "\\cdleftarrow",
+            "Overrightarrow",
+            "xRightarrow",
+            "xLeftarrow",
+            "overleftharpoon",
+            "xleftharpoonup",
+            "xleftharpoondown",
+            "overrightharpoon",
+            "xrightharpoonup",
+            "xrightharpoondown",
+            "xlongequal",
+            "\\cdlongequal",
+            "xtwoheadleftarrow",
+            "xtwoheadrightarrow",
+            "overleftrightarrow",
+            "overbrace",
+            "underbrace",
+            "underleftrightarrow",
+            "xleftrightarrow",
+            "xLeftrightarrow",
+            "xrightleftharpoons",
+            "xleftrightharpoons",
+            "xhookleftarrow",
+            "xhookrightarrow",
+            "overlinesegment",
+            "underlinesegment",
+            "overbracket",
+            "underbracket",
+            "overgroup",
+            "undergroup",
+            "xmapsto",
+            "xtofrom",
+            "xrightleftarrows",
+            "xrightequilibrium",
+            "xleftequilibrium",
+        ];
+        for label in labels {
+            let (paths, ..) = katex_images_data(label).unwrap();
+            for path in paths {
+                assert!(svg_path(path).is_some(), "{} has no path {}", label, path);
+            }
+        }
+
+        // Wide accents pick one of four sizes by the length of their base.
+        for label in ["widehat", "widecheck", "tilde"] {
+            for size in 1..=4 {
+                assert!(svg_path(&format!("{}{}", label, size)).is_some());
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! This file contains information and classes for the various kinds of styles
+//! used in TeX. It provides a generic `Style` struct, which holds information
+//! about a specific style. It then provides instances of all the different
+//! kinds of styles possible, and provides functions to move between them and
+//! get information about styles.
+
+/// The data for a specific style: an id, a size (0 for display, 1 for text,
+/// 2 for script and 3 for scriptscript), and whether it is cramped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub id: usize,
+    pub size: usize,
+    pub cramped: bool,
+}
+
+// IDs of the different styles
+const D: usize = 0;
+const DC: usize = 1;
+const T: usize = 2;
+const TC: usize = 3;
+const S: usize = 4;
+const SC: usize = 5;
+const SS: usize = 6;
+const SSC: usize = 7;
+
+// Instances of every style
+const STYLES: [Style; 8] = [
+    Style::new(D, 0, false),
+    Style::new(DC, 0, true),
+    Style::new(T, 1, false),
+    Style::new(TC, 1, true),
+    Style::new(S, 2, false),
+    Style::new(SC, 2, true),
+    Style::new(SS, 3, false),
+    Style::new(SSC, 3, true),
+];
+
+// Lookup tables for switching from one style to another
+const SUP: [usize; 8] = [S, SC, S, SC, SS, SSC, SS, SSC];
+const SUB: [usize; 8] = [SC, SC, SC, SC, SSC, SSC, SSC, SSC];
+const FRACNUM: [usize; 8] = [T, TC, S, SC, SS, SSC, SS, SSC];
+const FRACDEN: [usize; 8] = [TC, TC, SC, SC, SSC, SSC, SSC, SSC];
+const CRAMP: [usize; 8] = [DC, DC, TC, TC, SC, SC, SSC, SSC];
+const TEXT: [usize; 8] = [D, DC, T, TC, T, TC, T, TC];
+
+impl Style {
+    pub const DISPLAY: Style = STYLES[D];
+    pub const TEXT: Style = STYLES[T];
+    pub const SCRIPT: Style = STYLES[S];
+    pub const SCRIPTSCRIPT: Style = STYLES[SS];
+
+    const fn new(id: usize, size: usize, cramped: bool) -> Self {
+        Style { id, size, cramped }
+    }
+
+    /// Get the style of a superscript given a base in the current style.
+    pub fn sup(&self) -> Style {
+        STYLES[SUP[self.id]]
+    }
+
+    /// Get the style of a subscript given a base in the current style.
+    pub fn sub(&self) -> Style {
+        STYLES[SUB[self.id]]
+    }
+
+    /// Get the style of a fraction numerator given the fraction in the current
+    /// style.
+    pub fn frac_num(&self) -> Style {
+        STYLES[FRACNUM[self.id]]
+    }
+
+    /// Get the style of a fraction denominator given the fraction in the
+    /// current style.
+    pub fn frac_den(&self) -> Style {
+        STYLES[FRACDEN[self.id]]
+    }
+
+    /// Get the cramped version of a style (in particular, cramping a cramped
+    /// style doesn't change the style).
+    pub fn cramp(&self) -> Style {
+        STYLES[CRAMP[self.id]]
+    }
+
+    /// Get a text or display version of this style.
+    pub fn text(&self) -> Style {
+        STYLES[TEXT[self.id]]
+    }
+
+    /// Return true if this style is tightly spaced (scriptstyle/scriptscriptstyle)
+    pub fn is_tight(&self) -> bool {
+        self.size >= 2
+    }
+}
@@ -0,0 +1,868 @@
+//! This file holds a list of all no-argument functions and single-character
+//! symbols (like 'a' or ';').
+//!
+//! For each of the symbols, there are three properties they can have:
+//! - font (required): the font to be used for this symbol. Either "main" (the
+//!   normal font), or "ams" (the ams fonts).
+//! - group (required): the ParseNode group type the symbol should have (i.e.
+//!   "textord", "mathord", etc).
+//!   See https://github.com/KaTeX/KaTeX/wiki/Examining-TeX#group-types
+//! - replace: the character that this symbol or function should be
+//!   replaced with (i.e. "\phi" has a replace value of "ϕ", the phi
+//!   character in the main font).
+//!
+//! The outermost map in the table indicates what mode the symbols should be
+//! accepted in (e.g. "math" or "text").
+use super::*;
+
+/// The font a symbol is drawn from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Main,
+    Ams,
+}
+
+/// Atom groups, i.e. the TeX classes used for inter-atom spacing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Group {
+    Bin,
+    Close,
+    Inner,
+    Open,
+    Punct,
+    Rel,
+    /// Non-atom groups, which become their own parse node types.
+    AccentToken,
+    MathOrd,
+    OpToken,
+    Spacing,
+    TextOrd,
+}
+
+impl Group {
+    /// Whether the group is one of the TeX atom classes (as opposed to a
+    /// group that becomes its own parse node type).
+    pub fn is_atom(&self) -> bool {
+        matches!(
+            self,
+            Group::Bin | Group::Close | Group::Inner | Group::Open | Group::Punct | Group::Rel
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Group::Bin => "bin",
+            Group::Close => "close",
+            Group::Inner => "inner",
+            Group::Open => "open",
+            Group::Punct => "punct",
+            Group::Rel => "rel",
+            Group::AccentToken => "accent-token",
+            Group::MathOrd => "mathord",
+            Group::OpToken => "op-token",
+            Group::Spacing => "spacing",
+            Group::TextOrd => "textord",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolInfo {
+    pub font: Font,
+    pub group: Group,
+    pub replace: Option<&'static str>,
+}
+
+pub type SymbolTable = HashMap<&'static str, SymbolInfo>;
+
+#[derive(Debug, Default)]
+pub struct Symbols {
+    pub math: SymbolTable,
+    pub text: SymbolTable,
+}
+
+impl Symbols {
+    /// The symbol table for the given mode.
+    pub fn get(&self, mode: Mode) -> &SymbolTable {
+        match mode {
+            Mode::Math => &self.math,
+            Mode::Text => &self.text,
+        }
+    }
+
+    fn get_mut(&mut self, mode: Mode) -> &mut SymbolTable {
+        match mode {
+            Mode::Math => &mut self.math,
+            Mode::Text => &mut self.text,
+        }
+    }
+}
+
+pub static SYMBOLS: LazyLock<Symbols> = LazyLock::new(|| {
+    let mut symbols = Symbols::default();
+    define_symbols(&mut symbols);
+    symbols
+});
+
+pub static MATH_SYMBOLS: LazyLock<&'static SymbolTable> = LazyLock::new(|| &SYMBOLS.math);
+
+pub static TEXT_SYMBOLS: LazyLock<&'static SymbolTable> = LazyLock::new(|| &SYMBOLS.text);
+
+/// The text-mode ligatures formed by the parser.
+pub const LIGATURES: &[&str] = &["--", "---", "``", "''"];
+
+// Some abbreviations for commonly used strings.
+// This helps minify the code, and also spotting typos using jshint.
+
+// modes:
+const MATH: Mode = Mode::Math;
+const TEXT: Mode = Mode::Text;
+
+// fonts:
+const MAIN: Font = Font::Main;
+const AMS: Font = Font::Ams;
+
+// groups:
+const ACCENT: Group = Group::AccentToken;
+const BIN: Group = Group::Bin;
+const CLOSE: Group = Group::Close;
+const INNER: Group = Group::Inner;
+const MATHORD: Group = Group::MathOrd;
+const OP: Group = Group::OpToken;
+const OPEN: Group = Group::Open;
+const PUNCT: Group = Group::Punct;
+const REL: Group = Group::Rel;
+const SPACING: Group = Group::Spacing;
+const TEXTORD: Group = Group::TextOrd;
+
+/// `define_symbol` is used to insert symbols into the symbol table. If
+/// `accept_unicode_char` is set, the replacement character itself is also
+/// accepted as input and maps to the same symbol.
+fn define_symbol(
+    symbols: &mut Symbols,
+    mode: Mode,
+    font: Font,
+    group: Group,
+    replace: Option<&'static str>,
+    name: &'static str,
+    accept_unicode_char: bool,
+) {
+    let table = symbols.get_mut(mode);
+    let info = SymbolInfo {
+        font,
+        group,
+        replace,
+    };
+    if accept_unicode_char {
+        if let Some(replace) = replace {
+            table.insert(replace, info.clone());
+        }
+    }
+    table.insert(name, info);
+}
+
+/// Split a static string into its characters, each as a static string slice.
+fn chars(string: &'static str) -> impl Iterator<Item = &'static str> {
+    string
+        .char_indices()
+        .map(move |(i, ch)| &string[i..i + ch.len_utf8()])
+}
+
+fn define_symbols(s: &mut Symbols) {
+    // Now comes the symbol table
+
+    // Relation Symbols
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2261}"), "\\equiv", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{227a}"), "\\prec", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{227b}"), "\\succ", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{223c}"), "\\sim", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{22a5}"), "\\perp", false);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2aaf}"), "\\preceq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2ab0}"), "\\succeq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2243}"), "\\simeq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2223}"), "\\mid", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{226a}"), "\\ll", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{226b}"), "\\gg", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{224d}"), "\\asymp", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2225}"), "\\parallel", false);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{22c8}"), "\\bowtie", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2323}"), "\\smile", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2291}"), "\\sqsubseteq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2292}"), "\\sqsupseteq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2250}"), "\\doteq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2322}"), "\\frown", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{220b}"), "\\ni", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{221d}"), "\\propto", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{22a2}"), "\\vdash", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{22a3}"), "\\dashv", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{220b}"), "\\owns", false);
+
+    // Punctuation
+    define_symbol(s, MATH, MAIN, PUNCT, Some("\u{002e}"), "\\ldotp", false);
+    define_symbol(s, MATH, MAIN, PUNCT, Some("\u{22c5}"), "\\cdotp", false);
+
+    // Misc Symbols
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{0023}"), "\\#", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{0023}"), "\\#", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{0026}"), "\\&", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{0026}"), "\\&", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2135}"), "\\aleph", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2200}"), "\\forall", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{210f}"), "\\hbar", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2203}"), "\\exists", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2207}"), "\\nabla", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{266d}"), "\\flat", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2113}"), "\\ell", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{266e}"), "\\natural", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2663}"), "\\clubsuit", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2118}"), "\\wp", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{266f}"), "\\sharp", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2662}"), "\\diamondsuit", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{211c}"), "\\Re", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2661}"), "\\heartsuit", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2111}"), "\\Im", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2660}"), "\\spadesuit", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{00a7}"), "\\S", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00a7}"), "\\S", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{00b6}"), "\\P", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00b6}"), "\\P", false);
+
+    // Math and Text
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2020}"), "\\dag", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2020}"), "\\dag", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2020}"), "\\textdagger", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2021}"), "\\ddag", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2021}"), "\\ddag", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2021}"), "\\textdaggerdbl", false);
+
+    // Large Delimiters
+    define_symbol(s, MATH, MAIN, CLOSE, Some("\u{23b1}"), "\\rmoustache", true);
+    define_symbol(s, MATH, MAIN, OPEN, Some("\u{23b0}"), "\\lmoustache", true);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("\u{27ef}"), "\\rgroup", true);
+    define_symbol(s, MATH, MAIN, OPEN, Some("\u{27ee}"), "\\lgroup", true);
+
+    // Binary Operators
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2213}"), "\\mp", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2296}"), "\\ominus", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{228e}"), "\\uplus", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2293}"), "\\sqcap", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2217}"), "\\ast", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2294}"), "\\sqcup", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{25ef}"), "\\bigcirc", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2219}"), "\\bullet", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2021}"), "\\ddagger", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2240}"), "\\wr", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2a3f}"), "\\amalg", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{0026}"), "\\And", false); // from amsmath
+
+    // Arrow Symbols
+    define_symbol(s, MATH, MAIN, REL, Some("\u{27f5}"), "\\longleftarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21d0}"), "\\Leftarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{27f8}"), "\\Longleftarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{27f6}"), "\\longrightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21d2}"), "\\Rightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{27f9}"), "\\Longrightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2194}"), "\\leftrightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{27f7}"), "\\longleftrightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21d4}"), "\\Leftrightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{27fa}"), "\\Longleftrightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21a6}"), "\\mapsto", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{27fc}"), "\\longmapsto", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2197}"), "\\nearrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21a9}"), "\\hookleftarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21aa}"), "\\hookrightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2198}"), "\\searrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21bc}"), "\\leftharpoonup", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21c0}"), "\\rightharpoonup", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2199}"), "\\swarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21bd}"), "\\leftharpoondown", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21c1}"), "\\rightharpoondown", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2196}"), "\\nwarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21cc}"), "\\rightleftharpoons", true);
+
+    // AMS Negated Binary Relations
+    define_symbol(s, MATH, AMS, REL, Some("\u{226e}"), "\\nless", true);
+    // Symbol names preceded by "@" each have a corresponding macro.
+    define_symbol(s, MATH, AMS, REL, Some("\u{e010}"), "\\@nleqslant", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e011}"), "\\@nleqq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a87}"), "\\lneq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2268}"), "\\lneqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e00c}"), "\\@lvertneqq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22e6}"), "\\lnsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a89}"), "\\lnapprox", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2280}"), "\\nprec", true);
+    // unicode-math maps ⋠ to \npreccurlyeq. We'll use the AMS synonym.
+    define_symbol(s, MATH, AMS, REL, Some("\u{22e0}"), "\\npreceq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22e8}"), "\\precnsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2ab9}"), "\\precnapprox", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2241}"), "\\nsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e006}"), "\\@nshortmid", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2224}"), "\\nmid", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22ac}"), "\\nvdash", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22ad}"), "\\nvDash", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22ea}"), "\\ntriangleleft", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22ec}"), "\\ntrianglelefteq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{228a}"), "\\subsetneq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e01a}"), "\\@varsubsetneq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2acb}"), "\\subsetneqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e017}"), "\\@varsubsetneqq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{226f}"), "\\ngtr", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e00f}"), "\\@ngeqslant", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e00e}"), "\\@ngeqq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a88}"), "\\gneq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2269}"), "\\gneqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e00d}"), "\\@gvertneqq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22e7}"), "\\gnsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a8a}"), "\\gnapprox", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2281}"), "\\nsucc", true);
+    // unicode-math maps ⋡ to \nsucccurlyeq. We'll use the AMS synonym.
+    define_symbol(s, MATH, AMS, REL, Some("\u{22e1}"), "\\nsucceq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22e9}"), "\\succnsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2aba}"), "\\succnapprox", true);
+    // unicode-math maps ≆ to \simneqq. We'll use the AMS synonym.
+    define_symbol(s, MATH, AMS, REL, Some("\u{2246}"), "\\ncong", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e007}"), "\\@nshortparallel", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2226}"), "\\nparallel", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22af}"), "\\nVDash", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22eb}"), "\\ntriangleright", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22ed}"), "\\ntrianglerighteq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e018}"), "\\@nsupseteqq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{228b}"), "\\supsetneq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e01b}"), "\\@varsupsetneq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2acc}"), "\\supsetneqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e019}"), "\\@varsupsetneqq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22ae}"), "\\nVdash", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2ab5}"), "\\precneqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2ab6}"), "\\succneqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{e016}"), "\\@nsubseteqq", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22b4}"), "\\unlhd", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22b5}"), "\\unrhd", false);
+
+    // AMS Negated Arrows
+    define_symbol(s, MATH, AMS, REL, Some("\u{219a}"), "\\nleftarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{219b}"), "\\nrightarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21cd}"), "\\nLeftarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21cf}"), "\\nRightarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21ae}"), "\\nleftrightarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21ce}"), "\\nLeftrightarrow", true);
+
+    // AMS Misc
+    define_symbol(s, MATH, AMS, REL, Some("\u{25b3}"), "\\vartriangle", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{210f}"), "\\hslash", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{25bd}"), "\\triangledown", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{25ca}"), "\\lozenge", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{24c8}"), "\\circledS", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{00ae}"), "\\circledR", false);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("\u{00ae}"), "\\circledR", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2221}"), "\\measuredangle", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2204}"), "\\nexists", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2127}"), "\\mho", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2132}"), "\\Finv", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2141}"), "\\Game", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2035}"), "\\backprime", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{25b2}"), "\\blacktriangle", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{25bc}"), "\\blacktriangledown", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{25a0}"), "\\blacksquare", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{29eb}"), "\\blacklozenge", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2605}"), "\\bigstar", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2222}"), "\\sphericalangle", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2201}"), "\\complement", true);
+    // unicode-math maps U+F0 to \matheth. We map to AMS function \eth
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{00f0}"), "\\eth", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00f0}"), "\u{00f0}", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2571}"), "\\diagup", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2572}"), "\\diagdown", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{25a1}"), "\\square", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{25a1}"), "\\Box", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{25ca}"), "\\Diamond", false);
+    // unicode-math maps U+A5 to \mathyen. We map to AMS function \yen
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{00a5}"), "\\yen", true);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("\u{00a5}"), "\\yen", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2713}"), "\\checkmark", true);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("\u{2713}"), "\\checkmark", false);
+
+    // AMS Hebrew
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2136}"), "\\beth", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2138}"), "\\daleth", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2137}"), "\\gimel", true);
+
+    // AMS Greek
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{03dd}"), "\\digamma", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{03f0}"), "\\varkappa", false);
+
+    // AMS Delimiters
+    define_symbol(s, MATH, AMS, OPEN, Some("\u{250c}"), "\\@ulcorner", true);
+    define_symbol(s, MATH, AMS, CLOSE, Some("\u{2510}"), "\\@urcorner", true);
+    define_symbol(s, MATH, AMS, OPEN, Some("\u{2514}"), "\\@llcorner", true);
+    define_symbol(s, MATH, AMS, CLOSE, Some("\u{2518}"), "\\@lrcorner", true);
+
+    // AMS Binary Relations
+    define_symbol(s, MATH, AMS, REL, Some("\u{2266}"), "\\leqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a7d}"), "\\leqslant", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a95}"), "\\eqslantless", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2272}"), "\\lesssim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a85}"), "\\lessapprox", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{224a}"), "\\approxeq", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22d6}"), "\\lessdot", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22d8}"), "\\lll", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2276}"), "\\lessgtr", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22da}"), "\\lesseqgtr", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a8b}"), "\\lesseqqgtr", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2251}"), "\\doteqdot", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2253}"), "\\risingdotseq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2252}"), "\\fallingdotseq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{223d}"), "\\backsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22cd}"), "\\backsimeq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2ac5}"), "\\subseteqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22d0}"), "\\Subset", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{228f}"), "\\sqsubset", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{227c}"), "\\preccurlyeq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22de}"), "\\curlyeqprec", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{227e}"), "\\precsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2ab7}"), "\\precapprox", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22b2}"), "\\vartriangleleft", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22b4}"), "\\trianglelefteq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22a8}"), "\\vDash", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22aa}"), "\\Vvdash", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2323}"), "\\smallsmile", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2322}"), "\\smallfrown", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{224f}"), "\\bumpeq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{224e}"), "\\Bumpeq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2267}"), "\\geqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a7e}"), "\\geqslant", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a96}"), "\\eqslantgtr", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2273}"), "\\gtrsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a86}"), "\\gtrapprox", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22d7}"), "\\gtrdot", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22d9}"), "\\ggg", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2277}"), "\\gtrless", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22db}"), "\\gtreqless", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2a8c}"), "\\gtreqqless", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2256}"), "\\eqcirc", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2257}"), "\\circeq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{225c}"), "\\triangleq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{223c}"), "\\thicksim", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2248}"), "\\thickapprox", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2ac6}"), "\\supseteqq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22d1}"), "\\Supset", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2290}"), "\\sqsupset", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{227d}"), "\\succcurlyeq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22df}"), "\\curlyeqsucc", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{227f}"), "\\succsim", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2ab8}"), "\\succapprox", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22b3}"), "\\vartriangleright", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22b5}"), "\\trianglerighteq", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22a9}"), "\\Vdash", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2223}"), "\\shortmid", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2225}"), "\\shortparallel", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{226c}"), "\\between", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22d4}"), "\\pitchfork", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{221d}"), "\\varpropto", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{25c0}"), "\\blacktriangleleft", false);
+    // unicode-math says that \therefore is a mathord atom.
+    // We kept the amssymb atom type, which is rel.
+    define_symbol(s, MATH, AMS, REL, Some("\u{2234}"), "\\therefore", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{220d}"), "\\backepsilon", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{25b6}"), "\\blacktriangleright", false);
+    // unicode-math says that \because is a mathord atom.
+    // We kept the amssymb atom type, which is rel.
+    define_symbol(s, MATH, AMS, REL, Some("\u{2235}"), "\\because", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22d8}"), "\\llless", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{22d9}"), "\\gggtr", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22b2}"), "\\lhd", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22b3}"), "\\rhd", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2242}"), "\\eqsim", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{22c8}"), "\\Join", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2251}"), "\\Doteq", true);
+
+    // AMS Binary Operators
+    define_symbol(s, MATH, AMS, BIN, Some("\u{2214}"), "\\dotplus", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{2216}"), "\\smallsetminus", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22d2}"), "\\Cap", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22d3}"), "\\Cup", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{2a5e}"), "\\doublebarwedge", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{229f}"), "\\boxminus", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{229e}"), "\\boxplus", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22c7}"), "\\divideontimes", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22c9}"), "\\ltimes", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22ca}"), "\\rtimes", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22cb}"), "\\leftthreetimes", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22cc}"), "\\rightthreetimes", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22cf}"), "\\curlywedge", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22ce}"), "\\curlyvee", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{229d}"), "\\circleddash", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{229b}"), "\\circledast", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22c5}"), "\\centerdot", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22ba}"), "\\intercal", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22d2}"), "\\doublecap", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22d3}"), "\\doublecup", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22a0}"), "\\boxtimes", true);
+
+    // AMS Arrows
+    // Note: unicode-math maps ⇢ to their own function \rightdasharrow.
+    // We'll map it to AMS function \dashrightarrow. It produces the same atom.
+    define_symbol(s, MATH, AMS, REL, Some("\u{21e2}"), "\\dashrightarrow", true);
+    // unicode-math maps ⇠ to \leftdasharrow. We'll use the AMS synonym.
+    define_symbol(s, MATH, AMS, REL, Some("\u{21e0}"), "\\dashleftarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21c7}"), "\\leftleftarrows", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21c6}"), "\\leftrightarrows", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21da}"), "\\Lleftarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{219e}"), "\\twoheadleftarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21a2}"), "\\leftarrowtail", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21ab}"), "\\looparrowleft", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21cb}"), "\\leftrightharpoons", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21b6}"), "\\curvearrowleft", true);
+    // unicode-math maps ↺ to \acwopencirclearrow. We'll use the AMS synonym.
+    define_symbol(s, MATH, AMS, REL, Some("\u{21ba}"), "\\circlearrowleft", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21b0}"), "\\Lsh", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21c8}"), "\\upuparrows", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21bf}"), "\\upharpoonleft", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21c3}"), "\\downharpoonleft", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{22b6}"), "\\origof", true); // not in font
+    define_symbol(s, MATH, MAIN, REL, Some("\u{22b7}"), "\\imageof", true); // not in font
+    define_symbol(s, MATH, AMS, REL, Some("\u{22b8}"), "\\multimap", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21ad}"), "\\leftrightsquigarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21c9}"), "\\rightrightarrows", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21c4}"), "\\rightleftarrows", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21a0}"), "\\twoheadrightarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21a3}"), "\\rightarrowtail", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21ac}"), "\\looparrowright", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21b7}"), "\\curvearrowright", true);
+    // unicode-math maps ↻ to \cwopencirclearrow. We'll use the AMS synonym.
+    define_symbol(s, MATH, AMS, REL, Some("\u{21bb}"), "\\circlearrowright", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21b1}"), "\\Rsh", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21ca}"), "\\downdownarrows", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21be}"), "\\upharpoonright", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21c2}"), "\\downharpoonright", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21dd}"), "\\rightsquigarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21dd}"), "\\leadsto", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21db}"), "\\Rrightarrow", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{21be}"), "\\restriction", false);
+
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2018}"), "`", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("$"), "\\$", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("$"), "\\$", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("$"), "\\textdollar", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("%"), "\\%", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("%"), "\\%", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("_"), "\\_", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("_"), "\\_", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("_"), "\\textunderscore", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2220}"), "\\angle", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{221e}"), "\\infty", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2032}"), "\\prime", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{25b3}"), "\\triangle", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{0393}"), "\\Gamma", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{0394}"), "\\Delta", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{0398}"), "\\Theta", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{039b}"), "\\Lambda", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{039e}"), "\\Xi", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{03a0}"), "\\Pi", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{03a3}"), "\\Sigma", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{03a5}"), "\\Upsilon", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{03a6}"), "\\Phi", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{03a8}"), "\\Psi", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{03a9}"), "\\Omega", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("A"), "\u{0391}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("B"), "\u{0392}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("E"), "\u{0395}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("Z"), "\u{0396}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("H"), "\u{0397}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("I"), "\u{0399}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("K"), "\u{039A}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("M"), "\u{039C}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("N"), "\u{039D}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("O"), "\u{039F}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("P"), "\u{03A1}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("T"), "\u{03A4}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("X"), "\u{03A7}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{00ac}"), "\\neg", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{00ac}"), "\\lnot", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{22a4}"), "\\top", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{22a5}"), "\\bot", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2205}"), "\\emptyset", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2205}"), "\\varnothing", false);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b1}"), "\\alpha", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b2}"), "\\beta", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b3}"), "\\gamma", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b4}"), "\\delta", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03f5}"), "\\epsilon", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b6}"), "\\zeta", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b7}"), "\\eta", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b8}"), "\\theta", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b9}"), "\\iota", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03ba}"), "\\kappa", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03bb}"), "\\lambda", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03bc}"), "\\mu", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03bd}"), "\\nu", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03be}"), "\\xi", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03bf}"), "\\omicron", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c0}"), "\\pi", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c1}"), "\\rho", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c3}"), "\\sigma", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c4}"), "\\tau", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c5}"), "\\upsilon", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03d5}"), "\\phi", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c7}"), "\\chi", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c8}"), "\\psi", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c9}"), "\\omega", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03b5}"), "\\varepsilon", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03d1}"), "\\vartheta", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03d6}"), "\\varpi", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03f1}"), "\\varrho", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c2}"), "\\varsigma", true);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{03c6}"), "\\varphi", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2217}"), "*", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("+"), "+", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2212}"), "-", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{22c5}"), "\\cdot", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2218}"), "\\circ", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{00f7}"), "\\div", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{00b1}"), "\\pm", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{00d7}"), "\\times", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2229}"), "\\cap", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{222a}"), "\\cup", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2216}"), "\\setminus", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2227}"), "\\land", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2228}"), "\\lor", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2227}"), "\\wedge", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2228}"), "\\vee", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{221a}"), "\\surd", false);
+    define_symbol(s, MATH, MAIN, OPEN, Some("\u{27e8}"), "\\langle", true);
+    define_symbol(s, MATH, MAIN, OPEN, Some("\u{2223}"), "\\lvert", false);
+    define_symbol(s, MATH, MAIN, OPEN, Some("\u{2225}"), "\\lVert", false);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("?"), "?", false);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("!"), "!", false);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("\u{27e9}"), "\\rangle", true);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("\u{2223}"), "\\rvert", false);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("\u{2225}"), "\\rVert", false);
+    define_symbol(s, MATH, MAIN, REL, Some("="), "=", false);
+    define_symbol(s, MATH, MAIN, REL, Some(":"), ":", false);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2248}"), "\\approx", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2245}"), "\\cong", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2265}"), "\\ge", false);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2265}"), "\\geq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2190}"), "\\gets", false);
+    define_symbol(s, MATH, MAIN, REL, Some(">"), "\\gt", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2208}"), "\\in", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{e020}"), "\\@not", false);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2282}"), "\\subset", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2283}"), "\\supset", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2286}"), "\\subseteq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2287}"), "\\supseteq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2288}"), "\\nsubseteq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2289}"), "\\nsupseteq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{22a8}"), "\\models", false);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2190}"), "\\leftarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2264}"), "\\le", false);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2264}"), "\\leq", true);
+    define_symbol(s, MATH, MAIN, REL, Some("<"), "\\lt", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2192}"), "\\rightarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2192}"), "\\to", false);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2271}"), "\\ngeq", true);
+    define_symbol(s, MATH, AMS, REL, Some("\u{2270}"), "\\nleq", true);
+    define_symbol(s, MATH, MAIN, SPACING, Some("\u{00a0}"), "\\ ", false);
+    define_symbol(s, MATH, MAIN, SPACING, Some("\u{00a0}"), "\\space", false);
+    // Ref: LaTeX Source 2e: \DeclareRobustCommand{\nobreakspace}{%
+    define_symbol(s, MATH, MAIN, SPACING, Some("\u{00a0}"), "\\nobreakspace", false);
+    define_symbol(s, TEXT, MAIN, SPACING, Some("\u{00a0}"), "\\ ", false);
+    define_symbol(s, TEXT, MAIN, SPACING, Some("\u{00a0}"), " ", false);
+    define_symbol(s, TEXT, MAIN, SPACING, Some("\u{00a0}"), "\\space", false);
+    define_symbol(s, TEXT, MAIN, SPACING, Some("\u{00a0}"), "\\nobreakspace", false);
+    define_symbol(s, MATH, MAIN, SPACING, None, "\\nobreak", false);
+    define_symbol(s, MATH, MAIN, SPACING, None, "\\allowbreak", false);
+    define_symbol(s, MATH, MAIN, PUNCT, Some(","), ",", false);
+    define_symbol(s, MATH, MAIN, PUNCT, Some(";"), ";", false);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22bc}"), "\\barwedge", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22bb}"), "\\veebar", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2299}"), "\\odot", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2295}"), "\\oplus", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2297}"), "\\otimes", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2202}"), "\\partial", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2298}"), "\\oslash", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{229a}"), "\\circledcirc", true);
+    define_symbol(s, MATH, AMS, BIN, Some("\u{22a1}"), "\\boxdot", true);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{25b3}"), "\\bigtriangleup", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{25bd}"), "\\bigtriangledown", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{2020}"), "\\dagger", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{22c4}"), "\\diamond", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{22c6}"), "\\star", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{25c3}"), "\\triangleleft", false);
+    define_symbol(s, MATH, MAIN, BIN, Some("\u{25b9}"), "\\triangleright", false);
+    define_symbol(s, MATH, MAIN, OPEN, Some("{"), "\\{", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("{"), "\\{", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("{"), "\\textbraceleft", false);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("}"), "\\}", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("}"), "\\}", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("}"), "\\textbraceright", false);
+    define_symbol(s, MATH, MAIN, OPEN, Some("{"), "\\lbrace", false);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("}"), "\\rbrace", false);
+    define_symbol(s, MATH, MAIN, OPEN, Some("["), "\\lbrack", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("["), "\\lbrack", true);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("]"), "\\rbrack", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("]"), "\\rbrack", true);
+    define_symbol(s, MATH, MAIN, OPEN, Some("("), "\\lparen", true);
+    define_symbol(s, MATH, MAIN, CLOSE, Some(")"), "\\rparen", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("<"), "\\textless", true); // in T1 fontenc
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some(">"), "\\textgreater", true); // in T1 fontenc
+    define_symbol(s, MATH, MAIN, OPEN, Some("\u{230a}"), "\\lfloor", true);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("\u{230b}"), "\\rfloor", true);
+    define_symbol(s, MATH, MAIN, OPEN, Some("\u{2308}"), "\\lceil", true);
+    define_symbol(s, MATH, MAIN, CLOSE, Some("\u{2309}"), "\\rceil", true);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\\"), "\\backslash", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2223}"), "|", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2223}"), "\\vert", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("|"), "\\textbar", true); // in T1 fontenc
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2225}"), "\\|", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{2225}"), "\\Vert", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2225}"), "\\textbardbl", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("~"), "\\textasciitilde", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\\"), "\\textbackslash", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("^"), "\\textasciicircum", false);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2191}"), "\\uparrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21d1}"), "\\Uparrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2193}"), "\\downarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21d3}"), "\\Downarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{2195}"), "\\updownarrow", true);
+    define_symbol(s, MATH, MAIN, REL, Some("\u{21d5}"), "\\Updownarrow", true);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{2210}"), "\\coprod", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{22c1}"), "\\bigvee", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{22c0}"), "\\bigwedge", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{2a04}"), "\\biguplus", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{22c2}"), "\\bigcap", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{22c3}"), "\\bigcup", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{222b}"), "\\int", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{222b}"), "\\intop", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{222c}"), "\\iint", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{222d}"), "\\iiint", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{220f}"), "\\prod", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{2211}"), "\\sum", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{2a02}"), "\\bigotimes", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{2a01}"), "\\bigoplus", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{2a00}"), "\\bigodot", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{222e}"), "\\oint", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{222f}"), "\\oiint", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{2230}"), "\\oiiint", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{2a06}"), "\\bigsqcup", false);
+    define_symbol(s, MATH, MAIN, OP, Some("\u{222b}"), "\\smallint", false);
+    define_symbol(s, TEXT, MAIN, INNER, Some("\u{2026}"), "\\textellipsis", false);
+    define_symbol(s, MATH, MAIN, INNER, Some("\u{2026}"), "\\mathellipsis", false);
+    define_symbol(s, TEXT, MAIN, INNER, Some("\u{2026}"), "\\ldots", true);
+    define_symbol(s, MATH, MAIN, INNER, Some("\u{2026}"), "\\ldots", true);
+    define_symbol(s, MATH, MAIN, INNER, Some("\u{22ef}"), "\\@cdots", true);
+    define_symbol(s, MATH, MAIN, INNER, Some("\u{22f1}"), "\\ddots", true);
+    // \vdots is a mathord in unicode-math, but a textord here.
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{22ee}"), "\\varvdots", false); // \vdots is a macro
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{22ee}"), "\\varvdots", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{02ca}"), "\\acute", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{02cb}"), "\\grave", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{00a8}"), "\\ddot", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{007e}"), "\\tilde", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{02c9}"), "\\bar", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{02d8}"), "\\breve", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{02c7}"), "\\check", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{005e}"), "\\hat", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{20d7}"), "\\vec", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{02d9}"), "\\dot", false);
+    define_symbol(s, MATH, MAIN, ACCENT, Some("\u{02da}"), "\\mathring", false);
+    // \imath and \jmath should be invariant to \mathrm, \mathbf, etc., so use PUA
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{e131}"), "\\@imath", false);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("\u{e237}"), "\\@jmath", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{0131}"), "\u{0131}", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{0237}"), "\u{0237}", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{0131}"), "\\i", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{0237}"), "\\j", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00df}"), "\\ss", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00e6}"), "\\ae", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{0153}"), "\\oe", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00f8}"), "\\o", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00c6}"), "\\AE", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{0152}"), "\\OE", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00d8}"), "\\O", true);
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02ca}"), "\\'", false); // acute
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02cb}"), "\\`", false); // grave
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02c6}"), "\\^", false); // circumflex
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02dc}"), "\\~", false); // tilde
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02c9}"), "\\=", false); // macron
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02d8}"), "\\u", false); // breve
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02d9}"), "\\.", false); // dot above
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{00b8}"), "\\c", false); // cedilla
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02da}"), "\\r", false); // ring above
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02c7}"), "\\v", false); // caron
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{00a8}"), "\\\"", false); // diaeresis
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{02dd}"), "\\H", false); // double acute
+    define_symbol(s, TEXT, MAIN, ACCENT, Some("\u{25ef}"), "\\textcircled", false); // \bigcirc glyph
+
+    // These ligatures are detected and created in Parser.js's `form_ligatures`.
+    for (name, replace) in [
+        ("--", "\u{2013}"),
+        ("---", "\u{2014}"),
+        ("``", "\u{201c}"),
+        ("''", "\u{201d}"),
+    ] {
+        define_symbol(s, TEXT, MAIN, TEXTORD, Some(replace), name, false);
+    }
+
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2013}"), "\\textendash", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2014}"), "\\textemdash", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2018}"), "\\textquoteleft", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2018}"), "`", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2019}"), "\\textquoteright", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{2019}"), "'", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{201c}"), "\\textquotedblleft", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{201d}"), "\\textquotedblright", false);
+    //  \degree from gensymb package
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{00b0}"), "\\degree", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00b0}"), "\\degree", false);
+    // \textdegree from inputenc package
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00b0}"), "\\textdegree", true);
+    // TODO: In LaTeX, \pounds can generate a different character in text and math
+    // mode, but among our fonts, only Main-Regular defines this character "163".
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{00a3}"), "\\pounds", false);
+    define_symbol(s, MATH, MAIN, TEXTORD, Some("\u{00a3}"), "\\mathsterling", true);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00a3}"), "\\pounds", false);
+    define_symbol(s, TEXT, MAIN, TEXTORD, Some("\u{00a3}"), "\\textsterling", true);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("\u{2720}"), "\\maltese", false);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("\u{2720}"), "\\maltese", false);
+
+    // There are lots of symbols which are the same, so we add them in afterwards.
+    // All of these are textords in math mode
+    for ch in chars("0123456789/@.\"") {
+        define_symbol(s, MATH, MAIN, TEXTORD, Some(ch), ch, false);
+    }
+
+    // All of these are textords in text mode
+    for ch in chars("0123456789!@*()-=+\";:?/.,") {
+        define_symbol(s, TEXT, MAIN, TEXTORD, Some(ch), ch, false);
+    }
+
+    // All of these are textords in text mode, and mathords in math mode
+    for ch in chars("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ") {
+        define_symbol(s, MATH, MAIN, MATHORD, Some(ch), ch, false);
+        define_symbol(s, TEXT, MAIN, TEXTORD, Some(ch), ch, false);
+    }
+
+    // Blackboard bold and script letters in Unicode range
+    define_symbol(s, MATH, AMS, TEXTORD, Some("C"), "\u{2102}", false); // blackboard bold
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("C"), "\u{2102}", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("H"), "\u{210D}", false);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("H"), "\u{210D}", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("N"), "\u{2115}", false);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("N"), "\u{2115}", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("P"), "\u{2119}", false);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("P"), "\u{2119}", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("Q"), "\u{211A}", false);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("Q"), "\u{211A}", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("R"), "\u{211D}", false);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("R"), "\u{211D}", false);
+    define_symbol(s, MATH, AMS, TEXTORD, Some("Z"), "\u{2124}", false);
+    define_symbol(s, TEXT, AMS, TEXTORD, Some("Z"), "\u{2124}", false);
+    define_symbol(s, MATH, MAIN, MATHORD, Some("h"), "\u{210E}", false); // italic h, Planck constant
+    define_symbol(s, TEXT, MAIN, MATHORD, Some("h"), "\u{210E}", false);
+
+    // Some more letters in Unicode Basic Multilingual Plane.
+    for ch in chars("\u{00d0}\u{00de}\u{00fe}") {
+        define_symbol(s, MATH, MAIN, MATHORD, Some(ch), ch, false);
+        define_symbol(s, TEXT, MAIN, TEXTORD, Some(ch), ch, false);
+    }
+}
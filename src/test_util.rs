@@ -0,0 +1,39 @@
+//! Fixtures shared by the unit tests of the function modules.
+use super::*;
+
+/// Settings that render both the HTML and the MathML of an expression.
+pub fn html_and_mathml() -> Settings {
+    Settings {
+        output: OutputFormat::HtmlAndMathml,
+        ..Default::default()
+    }
+}
+
+/// Render `expression` to HTML and MathML markup with otherwise default
+/// settings.
+pub fn render(expression: &str) -> String {
+    render_to_string(expression, &html_and_mathml())
+}
+
+/// Render `expression` to HTML and MathML markup in display mode.
+pub fn render_display(expression: &str) -> String {
+    let settings = Settings {
+        display_mode: true,
+        ..html_and_mathml()
+    };
+    render_to_string(expression, &settings)
+}
+
+/// Parse `expression`, which must make a single node.
+pub fn parse_one(expression: &str) -> AnyParseNode {
+    let mut tree = parse_tree(expression, &Settings::default()).unwrap();
+    assert_eq!(tree.len(), 1, "{}", expression);
+    tree.remove(0)
+}
+
+/// Build the HTML of `expression`, which must make a single node, in text
+/// style. Its height and depth are those TeX would give the box.
+pub fn build_one(expression: &str) -> VirtualNode {
+    let node = parse_one(expression);
+    build_html::build_group(Some(&node), &Options::new(OptionsData::default()), None)
+}
@@ -11,7 +11,7 @@ use super::*;
 /// The position information is optional, so it is OK to construct synthetic
 /// tokens if appropriate. Not providing available position information may
 /// lead to degraded error reporting, though.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Token {
     pub text: String,
     pub loc: Option<SourceLocation>,
@@ -40,10 +40,14 @@ impl Token {
     }
 }
 
-impl LexerInterface for Token {}
-
-impl LexerInterface for SourceLocation {}
+impl LexerInterface for String {
+    fn input(&self) -> &str {
+        self
+    }
+}
 
 /// Interface required to break circular dependency between Token, Lexer, and
 /// ParseError.
-pub trait LexerInterface {}
+pub trait LexerInterface: Send + Sync {
+    fn input(&self) -> &str;
+}
@@ -0,0 +1,84 @@
+//! This file does conversion between units.  In particular, it provides
+//! calculate_size to convert other units into ems.
+use super::*;
+
+/// Conversion factor from the given absolute unit to TeX points.
+///
+/// This table is used by both `valid_unit` and `calculate_size`.
+fn pt_per_unit(unit: &str) -> Option<f64> {
+    let pt = match unit {
+        // https://en.wikibooks.org/wiki/LaTeX/Lengths and
+        // https://tex.stackexchange.com/a/8263
+        "pt" => 1.0,              // TeX point
+        "mm" => 7227.0 / 2540.0,  // millimeter
+        "cm" => 7227.0 / 254.0,   // centimeter
+        "in" => 72.27,            // inch
+        "bp" => 803.0 / 800.0,    // big (PostScript) points
+        "pc" => 12.0,             // pica
+        "dd" => 1238.0 / 1157.0,  // didot
+        "cc" => 14856.0 / 1157.0, // cicero (12 didot)
+        "nd" => 685.0 / 642.0,    // new didot
+        "nc" => 1370.0 / 107.0,   // new cicero (12 new didot)
+        "sp" => 1.0 / 65536.0,    // scaled point (TeX's internal smallest unit)
+        // https://tex.stackexchange.com/a/41371
+        "px" => 803.0 / 800.0, // \pdfpxdimen defaults to 1 bp in pdfTeX and LuaTeX
+        _ => return None,
+    };
+    Some(pt)
+}
+
+/// Relative units, for fast validity testing.
+const RELATIVE_UNIT: &[&str] = &["ex", "em", "mu"];
+
+/// Determine whether the specified unit is valid.
+pub fn valid_unit(unit: &str) -> bool {
+    pt_per_unit(unit).is_some() || RELATIVE_UNIT.contains(&unit)
+}
+
+/// Convert a measurement (as parsed by the `ArgType::Size` argument type)
+/// into a CSS em value for the current style/scale.  `options` gives the
+/// current options.
+///
+/// The unit must have been checked with `valid_unit` when it was parsed.
+pub fn calculate_size(size_value: &Measurement, options: &Options) -> f64 {
+    let scale = if let Some(pt) = pt_per_unit(&size_value.unit) {
+        // Absolute units
+        pt // Convert unit to pt
+           / options.font_metrics().pt_per_em // Convert pt to CSS em
+           / options.size_multiplier // Unscale to make absolute units
+    } else if size_value.unit == "mu" {
+        // `mu` units scale with scriptstyle/scriptscriptstyle.
+        options.font_metrics().css_em_per_mu
+    } else {
+        // Other relative units always refer to the *textstyle* font
+        // in the current size.
+        let unit_options = if options.style.is_tight() {
+            // is_tight() means current style is script/scriptscript.
+            options.having_style(options.style.text())
+        } else {
+            options.clone()
+        };
+
+        // TODO: In TeX these units are relative to the quad of the current
+        // *text* font, e.g. cmr10. KaTeX instead uses values from the
+        // comparably-sized *Computer Modern symbol* font. At 10pt, these
+        // match. At 7pt and 5pt, they differ: cmr7=1.138894, cmsy7=1.170641;
+        // cmr5=1.361133, cmsy5=1.472241. Consider $\scriptsize a\kern1emb$.
+        // TeX \showlists shows a kern of 1.13889 * fontsize;
+        // KaTeX shows a kern of 1.171 * fontsize.
+        let scale = match size_value.unit.as_str() {
+            "ex" => unit_options.font_metrics().x_height,
+            "em" => unit_options.font_metrics().quad,
+            unit => panic!("Invalid unit: '{}'", unit),
+        };
+        scale * unit_options.size_multiplier / options.size_multiplier
+    };
+    (size_value.number * scale).min(options.max_size)
+}
+
+/// Round `n` to 4 decimal places, or to the nearest 1/10,000th em. See
+/// https://github.com/KaTeX/KaTeX/pull/2460.
+pub fn make_em(n: f64) -> String {
+    // Adding 0.0 turns -0 into 0.
+    format!("{}em", (n * 10000.0).round() / 10000.0 + 0.0)
+}
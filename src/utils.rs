@@ -35,7 +35,9 @@ pub fn escape(text: &str) -> String {
         .replace_all(text, |captures: &regex::Captures| {
             escape_lookup
                 .get(&captures[0].chars().next().unwrap())
+                .copied()
                 .unwrap_or(&captures[0])
+                .to_string()
         })
         .to_string()
 }
@@ -43,23 +45,14 @@ pub fn escape(text: &str) -> String {
 /// Sometimes we want to pull out the innermost element of a group. In most
 /// cases, this will just be the group itself, but when ordgroups and colors have
 /// a single element, we want to pull that out.
-pub fn get_base_elem(group: &AnyParseNode) -> AnyParseNode {
-    if let Some(ord_group) = group.as_ordgroup() {
-        if ord_group.body.len() == 1 {
-            return get_base_elem(&ord_group.body[0]);
-        } else {
-            return group.clone();
+pub fn get_base_elem(group: &AnyParseNode) -> &AnyParseNode {
+    match group {
+        AnyParseNode::OrdGroup(ord_group) if ord_group.body.len() == 1 => {
+            get_base_elem(&ord_group.body[0])
         }
-    } else if let Some(color) = group.as_color() {
-        if color.body.len() == 1 {
-            return get_base_elem(&color.body[0]);
-        } else {
-            return group.clone();
-        }
-    } else if let Some(font) = group.as_font() {
-        return get_base_elem(&font.body);
-    } else {
-        return group.clone();
+        AnyParseNode::Color(color) if color.body.len() == 1 => get_base_elem(&color.body[0]),
+        AnyParseNode::Font(font) => get_base_elem(&font.body),
+        group => group,
     }
 }
 
@@ -70,7 +63,10 @@ pub fn is_character_box(group: &AnyParseNode) -> bool {
     let base_elem = get_base_elem(group);
 
     // These are all they types of groups which hold single characters
-    base_elem.is_mathord() || base_elem.is_textord() || base_elem.is_atom()
+    matches!(
+        base_elem,
+        AnyParseNode::MathOrd(_) | AnyParseNode::TextOrd(_) | AnyParseNode::Atom(_)
+    )
 }
 
 /// Return the protocol of a URL, or "_relative" if the URL does not specify a